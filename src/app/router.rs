@@ -23,8 +23,28 @@ pub fn execute(raw: &str) -> OutputBlock {
                 "  /scan history      — Scan git history for secrets",
                 "  /scan ignored      — Manage ignored findings",
                 "  /plan              — Open task planner overlay",
+                "  /plan import <owner/repo> [--closed]  — Import GitHub issues as tasks",
+                "  /plan issue <task-id> <owner/repo>    — Open a GitHub issue from a task",
+                "  /plan link <task-id> <owner/repo#n>   — Link a task to an existing issue",
+                "  /plan unlink <task-id>                — Stop syncing a task with its issue",
+                "  /plan sync         — Two-way sync of linked tasks and issues",
+                "  /plan commits      — Link commits and branches mentioning a task's gl-xxxx ref",
+                "  /plan autoclose on|off                — Complete tasks from 'Closes gl-xxxx' on the default branch",
+                "  /plan columns [a, b, …]               — Show or set the board columns; the last holds done tasks",
                 "  /prp               — Start a poly-repo commit session",
-                "  /prp list          — List PRP session groups",
+                "  /prp list          — List PRP session groups and workspaces",
+                "  /prp groups        — Browse Group-IDs with commit details and combined diffs",
+                "  /prp --set <name>  — Start a PRP session on a named workspace",
+                "  /prp set add <name> <path|glob>...  — Create or extend a workspace",
+                "  /prp set remove <name> [entry]      — Remove a workspace or one entry",
+                "  /prp branch <name> — Create a branch in every PRP repo (--push to publish)",
+                "  /prp switch <name> — Check out a branch in every PRP repo",
+                "  /prp pr [id]       — Open linked pull requests for a PRP group",
+                "  /prp pr status     — Show review/merge status of a PRP group",
+                "  /prp resume [id]   — Finish an interrupted PRP push",
+                "  /prp undo <id>     — Undo every commit of a PRP session",
+                "  /prp exec <cmd>    — Run a shell command in every PRP repo (--changed-only, -j N)",
+                "  /workspace sync    — Clone/fetch repos from .gitlink/manifest.json and report drift",
                 "  /show-activity     — Show GitHub contribution activity",
                 "  /commits           — Show 3 most recent commits globally",
                 "  /pull-requests     — Show your open pull requests",
@@ -107,9 +127,161 @@ pub fn execute(raw: &str) -> OutputBlock {
         }
 
         // ── Plan ──────────────────────────────────────────────────────────────
-        "plan" => OutputBlock {
-            kind: OutputKind::Info,
-            content: "Opening planner...".to_string(),
+        "plan" => match sub {
+            "import" | "issue" | "link" | "sync" => run_async(async {
+                use crate::auth::token_store;
+                use crate::github::graphql::GraphQLClient;
+                use crate::planner::issues::{self, SyncOutcome};
+                use crate::planner::storage::{load_tasks, save_tasks};
+                use crate::planner::task::{find_by_prefix, IssueLink};
+
+                let usage = "Usage: /plan import <owner/repo> [--closed] · /plan issue <task-id> <owner/repo> · \
+/plan link <task-id> <owner/repo#number> · /plan sync";
+                let token = token_store::load_token()
+                    .map_err(|_| "Not authenticated. Run /auth login first.".to_string())?;
+                let client = GraphQLClient::new(token);
+                let mut list = load_tasks();
+
+                let out = match sub {
+                    "import" => {
+                        let (owner, repo) = parts.get(2).and_then(|r| r.split_once('/')).ok_or(usage)?;
+                        let closed = parts.contains(&"--closed");
+                        let (imported, linked) = issues::import_issues(&client, owner, repo, closed, &mut list.tasks).await?;
+                        format!(
+                            "Imported {} issue(s) from {}/{} as tasks ({} already linked).",
+                            imported, owner, repo, linked
+                        )
+                    }
+                    "issue" => {
+                        let id = parts.get(2).ok_or(usage)?;
+                        let (owner, repo) = parts.get(3).and_then(|r| r.split_once('/')).ok_or(usage)?;
+                        let task = find_by_prefix(&mut list.tasks, id)?;
+                        let link = issues::create_issue(&client, task, owner, repo).await?;
+                        format!("Opened {} from task \"{}\".", link, task.title)
+                    }
+                    "link" => {
+                        let id = parts.get(2).ok_or(usage)?;
+                        let link = parts.get(3).and_then(|l| IssueLink::parse(l)).ok_or(usage)?;
+                        let task = find_by_prefix(&mut list.tasks, id)?;
+                        task.issue = Some(link.clone());
+                        let outcome = match issues::sync_task(&client, task).await {
+                            Ok(o) => o,
+                            Err(e) => {
+                                task.issue = None;
+                                return Err(e);
+                            }
+                        };
+                        let how = match outcome {
+                            SyncOutcome::UpToDate => "already in sync",
+                            SyncOutcome::Pushed => "issue updated from the task",
+                            SyncOutcome::Pulled => "task updated from the issue",
+                        };
+                        format!("Linked \"{}\" to {} — {}.", task.title, link, how)
+                    }
+                    _ => {
+                        let results = issues::sync_all(&client, &mut list.tasks).await;
+                        if results.is_empty() {
+                            return Ok("No tasks are linked to issues. Use /plan import or /plan link first.".to_string());
+                        }
+                        let mut out = format!("Issue sync — {} linked task(s)\n{}\n", results.len(), "─".repeat(60));
+                        for (i, result) in &results {
+                            let task = &list.tasks[*i];
+                            let link = task.issue.as_ref().map(|l| l.to_string()).unwrap_or_default();
+                            let status = match result {
+                                Ok(SyncOutcome::UpToDate) => "✓ in sync ".to_string(),
+                                Ok(SyncOutcome::Pushed) => "⬆ pushed  ".to_string(),
+                                Ok(SyncOutcome::Pulled) => "⬇ pulled  ".to_string(),
+                                Err(e) => format!("❌ {}\n   ", e),
+                            };
+                            out.push_str(&format!("  {} {}  {}\n", status, link, task.title));
+                        }
+                        let failed = results.iter().filter(|(_, r)| r.is_err()).count();
+                        if failed > 0 {
+                            out.push_str(&format!("\n{} task(s) could not be synced.", failed));
+                        }
+                        out
+                    }
+                };
+
+                save_tasks(&list).map_err(|e| e.to_string())?;
+                Ok(out)
+            }),
+            "unlink" => run_sync(|| {
+                use crate::planner::storage::{load_tasks, save_tasks};
+
+                let id = parts.get(2).ok_or("Usage: /plan unlink <task-id>")?;
+                let mut list = load_tasks();
+                let task = crate::planner::task::find_by_prefix(&mut list.tasks, id)?;
+                let link = task.issue.take().ok_or_else(|| format!("Task \"{}\" is not linked to an issue", task.title))?;
+                let out = format!("Unlinked \"{}\" from {}. The issue is left as it is.", task.title, link);
+                save_tasks(&list)?;
+                Ok(out)
+            }),
+            "commits" => run_sync(|| {
+                use crate::planner::storage::{load_tasks, save_tasks};
+
+                let mut list = load_tasks();
+                let close = list.settings.close_from_commits;
+                let report = crate::planner::commits::link_commits(&mut list.tasks, close);
+                save_tasks(&list)?;
+
+                let mut out = format!("Linked {} new commit(s) to tasks.", report.linked);
+                for task in list.tasks.iter().filter(|t| report.closed.contains(&t.id)) {
+                    out.push_str(&format!("\n  ✔ Closed \"{}\"", task.title));
+                }
+                let linked: Vec<_> = list.tasks.iter().filter(|t| !t.commits.is_empty()).collect();
+                if !linked.is_empty() {
+                    out.push_str(&format!("\n\n{} task(s) with commits\n{}", linked.len(), "─".repeat(60)));
+                    for task in linked {
+                        out.push_str(&format!("\n  {}  {}  ({} commit(s))", task.short_ref(&list.tasks), task.title, task.commits.len()));
+                    }
+                }
+                if !close {
+                    out.push_str("\n\nTip: /plan autoclose on completes tasks from 'Closes gl-xxxx' trailers.");
+                }
+                Ok(out)
+            }),
+            "autoclose" => run_sync(|| {
+                use crate::planner::storage::{load_tasks, save_tasks};
+
+                let on = match parts.get(2).copied() {
+                    Some("on") => true,
+                    Some("off") => false,
+                    _ => return Err("Usage: /plan autoclose on|off".into()),
+                };
+                let mut list = load_tasks();
+                list.settings.close_from_commits = on;
+                save_tasks(&list)?;
+                Ok(if on {
+                    "Tasks named in a 'Closes gl-xxxx' trailer will be completed once the commit is on the default branch.".to_string()
+                } else {
+                    "Commits will be linked to tasks but no longer complete them.".to_string()
+                })
+            }),
+            "columns" => run_sync(|| {
+                use crate::planner::board::{by_column, parse_columns};
+                use crate::planner::storage::{load_tasks, save_tasks};
+
+                let mut list = load_tasks();
+                let input = parts[2..].join(" ");
+                let changed = !input.trim().is_empty();
+                if changed {
+                    list.settings.columns = parse_columns(&input)?;
+                    save_tasks(&list)?;
+                }
+
+                let board = by_column(&list.tasks, &list.settings.columns);
+                let mut out = if changed { "Board columns updated.\n".to_string() } else { String::new() };
+                for (name, tasks) in list.settings.columns.iter().zip(&board) {
+                    out.push_str(&format!("\n  {:<16} {} task(s)", name, tasks.len()));
+                }
+                out.push_str("\n\nPress w in the planner to open the board. Tasks in a removed column move to the first one.");
+                Ok(out)
+            }),
+            _ => OutputBlock {
+                kind: OutputKind::Info,
+                content: "Opening planner...".to_string(),
+            },
         },
 
         // ── PRP ───────────────────────────────────────────────────────────────
@@ -119,12 +291,400 @@ pub fn execute(raw: &str) -> OutputBlock {
                 Ok("PRP group list complete.".to_string())
             }),
             "" => run_sync(|| {
-                crate::prp_hub::run_prp_start()?;
+                crate::prp_hub::run_prp_start(None)?;
                 Ok("PRP session completed.".to_string())
             }),
+            "--set" => run_sync(|| {
+                let name = parts.get(2).ok_or("Usage: /prp --set <workspace>")?;
+                crate::prp_hub::run_prp_start(Some(name))?;
+                Ok("PRP session completed.".to_string())
+            }),
+            "set" => run_sync(|| {
+                use crate::prp_hub::workspace::{
+                    format_workspaces, is_valid_name, load_workspaces, normalize_entry, save_workspaces,
+                    workspaces_location,
+                };
+
+                let mut store = load_workspaces();
+                let action = parts.get(2).copied().unwrap_or("list");
+                let out = match action {
+                    "list" => format!(
+                        "PRP Workspaces ({})\n{}\n\n{}",
+                        workspaces_location(),
+                        "─".repeat(60),
+                        format_workspaces(&store)
+                    ),
+                    "add" => {
+                        let name = parts.get(3).ok_or("Usage: /prp set add <name> <path|glob>...")?;
+                        if !is_valid_name(name) {
+                            return Err(format!(
+                                "Invalid workspace name '{}': use letters, digits, '-', '_' or '.'",
+                                name
+                            )
+                            .into());
+                        }
+                        let entries: Vec<String> = parts[4..].iter().map(|e| normalize_entry(e)).collect();
+                        if entries.is_empty() {
+                            return Err("Usage: /prp set add <name> <path|glob>...".into());
+                        }
+                        let created = store.add_entries(name, &entries);
+                        save_workspaces(&store)?;
+                        format!(
+                            "✅ Workspace '{}' {}.\n\n{}",
+                            name,
+                            if created { "created" } else { "updated" },
+                            format_workspaces(&crate::prp_hub::workspace::WorkspaceStore {
+                                workspaces: store.find(name).cloned().into_iter().collect(),
+                            })
+                        )
+                    }
+                    "remove" => {
+                        let name = parts.get(3).ok_or("Usage: /prp set remove <name> [entry]")?;
+                        match parts.get(4) {
+                            Some(entry) => {
+                                // Entries are stored normalized; accept either spelling
+                                let entry = store
+                                    .find(name)
+                                    .and_then(|w| w.entries.iter().find(|e| e.as_str() == *entry).cloned())
+                                    .unwrap_or_else(|| normalize_entry(entry));
+                                store.remove_entry(name, &entry)?;
+                                save_workspaces(&store)?;
+                                format!("✅ Removed '{}' from workspace '{}'.\n", entry, name)
+                            }
+                            None => {
+                                if !store.remove(name) {
+                                    return Err(format!("No workspace named '{}'", name).into());
+                                }
+                                save_workspaces(&store)?;
+                                format!("✅ Workspace '{}' deleted.\n", name)
+                            }
+                        }
+                    }
+                    other => {
+                        return Err(format!(
+                            "Unknown set action '{}'. Try /prp set, /prp set add or /prp set remove.",
+                            other
+                        )
+                        .into())
+                    }
+                };
+                Ok(out)
+            }),
+            "pr" => run_async(async {
+                use crate::auth::token_store;
+                use crate::github::pulls_client::PullsClient;
+                use crate::prp_hub::journal::{list_journals, load_journal, SessionPhase};
+
+                let want_status = parts.get(2) == Some(&"status");
+                let gid_arg = if want_status { parts.get(3) } else { parts.get(2) };
+
+                let mut journal = match gid_arg {
+                    Some(gid) => load_journal(gid)
+                        .map_err(|e| format!("No session journal for '{}': {}", gid, e))?,
+                    None if want_status => list_journals()
+                        .into_iter()
+                        .find(|j| j.repos.iter().any(|r| r.pull_request.is_some()))
+                        .ok_or("No PRP group has pull requests yet. Open them with /prp pr.")?,
+                    None => list_journals()
+                        .into_iter()
+                        .find(|j| j.phase == SessionPhase::Pushed)
+                        .ok_or("No pushed PRP session found. Push one first, or pass a Group-ID.")?,
+                };
+
+                let token = token_store::load_token()
+                    .map_err(|_| "Not authenticated. Run /auth login first.".to_string())?;
+                let client = PullsClient::new(token);
+
+                if want_status {
+                    let statuses = crate::prp_hub::group::fetch_group_pr_status(&client, &journal).await;
+                    return Ok(crate::prp_hub::group::format_group_pr_status(&journal, &statuses));
+                }
+
+                let mut out = format!(
+                    "PRP Pull Requests — {}\n{}\n\n",
+                    journal.group_id,
+                    "─".repeat(60)
+                );
+                let outcomes = crate::prp_hub::pull_request::open_group_prs(&client, &mut journal).await;
+                let failed = outcomes.iter().filter(|(_, o)| o.is_err()).count();
+                for (name, outcome) in outcomes {
+                    match outcome {
+                        Ok(what) => out.push_str(&format!("   {}:  ✅ {}\n", name, what)),
+                        Err(e) => out.push_str(&format!("{}\n", e)),
+                    }
+                }
+                if failed > 0 {
+                    out.push_str("\n⚠️  Some pull requests could not be opened; fix the problem and run /prp pr again.");
+                    return Err(out);
+                }
+                out.push_str("\n✅ Pull requests are linked. Track them with /prp pr status.\n");
+                Ok(out)
+            }),
+            "tag" => run_async(async {
+                use crate::auth::token_store;
+                use crate::github::pulls_client::PullsClient;
+                use crate::prp_hub::discovery::resolve_repositories;
+                use crate::prp_hub::errors::PrpError;
+                use crate::prp_hub::tag::{create_releases, parse_tag_args, tag_all};
+
+                let request = parse_tag_args(parts.get(2..).unwrap_or(&[]))?;
+                let repos = resolve_repositories(request.set.as_deref()).map_err(|e| e.to_string())?;
+
+                let mut out = format!("PRP Tag — {}\n{}\n\n", request.name, "─".repeat(60));
+                let group = match tag_all(&repos, &request) {
+                    Ok(g) => g,
+                    Err(errors) => {
+                        let leftover = errors.iter().any(|e| matches!(e, PrpError::RollbackFailed { .. }));
+                        out.push_str(if leftover {
+                            "❌ Tagging failed, and some tags could not be removed:\n"
+                        } else {
+                            "❌ Tagging failed; no tags were left behind:\n"
+                        });
+                        for e in &errors {
+                            out.push_str(&format!("{}\n", e));
+                        }
+                        return Err(out);
+                    }
+                };
+                for t in &group.repos {
+                    out.push_str(&format!("   {}:  ✅ tagged and pushed to {}\n", t.info.name, t.remote));
+                }
+                out.push_str(&format!("\n🆔 Group-ID: {}\n", group.group_id));
+
+                if !request.release {
+                    return Ok(out);
+                }
+                let token = token_store::load_token()
+                    .map_err(|_| format!("{}\nNot authenticated, so no releases were created. Run /auth login first.", out))?;
+                let client = PullsClient::new(token);
+
+                out.push('\n');
+                let outcomes = create_releases(&client, &group).await;
+                let failed = outcomes.iter().filter(|(_, o)| o.is_err()).count();
+                for (name, outcome) in outcomes {
+                    match outcome {
+                        Ok(what) => out.push_str(&format!("   {}:  ✅ {}\n", name, what)),
+                        Err(e) => out.push_str(&format!("{}\n", e)),
+                    }
+                }
+                if failed > 0 {
+                    out.push_str("\n⚠️  Tags are pushed, but some releases could not be created.");
+                    return Err(out);
+                }
+                out.push_str("\n✅ Released.\n");
+                Ok(out)
+            }),
+            "resume" => run_sync(|| {
+                use crate::prp_hub::journal::{latest_resumable, load_journal};
+
+                let mut journal = match parts.get(2) {
+                    Some(gid) => load_journal(gid)
+                        .map_err(|e| format!("No session journal for '{}': {}", gid, e))?,
+                    None => latest_resumable().ok_or("No interrupted PRP session to resume.")?,
+                };
+                if !journal.phase.is_resumable() {
+                    return Err(format!(
+                        "Session {} is {}; nothing to resume.",
+                        journal.group_id,
+                        journal.phase.label()
+                    )
+                    .into());
+                }
+
+                let mut out = format!(
+                    "PRP Resume — {}\n{}\n\n   Message:  {}\n\n",
+                    journal.group_id,
+                    "─".repeat(60),
+                    journal.message,
+                );
+                let result = crate::prp_hub::journal::resume_session(&mut journal, |info, result| {
+                    let icon = if result.is_ok() { "✅ pushed" } else { "❌ failed" };
+                    out.push_str(&format!("   {}:  {}\n", info.name, icon));
+                });
+                match result {
+                    Ok(()) => {
+                        out.push_str("\n✅ All repositories pushed.\n");
+                        Ok(out)
+                    }
+                    Err(errors) => {
+                        for e in &errors {
+                            out.push_str(&format!("{}\n", e));
+                        }
+                        out.push_str(&format!(
+                            "\n⚠️  Session is {}. Fix the problem and run /prp resume again.",
+                            journal.phase.label()
+                        ));
+                        Err(out.into())
+                    }
+                }
+            }),
+            "undo" => run_sync(|| {
+                use crate::prp_hub::journal::{load_journal, undo_session, SessionPhase};
+
+                let gid = parts.get(2).ok_or("Usage: /prp undo <group-id>")?;
+                let mut journal = load_journal(gid)
+                    .map_err(|e| format!("No session journal for '{}': {}", gid, e))?;
+                if matches!(journal.phase, SessionPhase::RolledBack | SessionPhase::Undone) {
+                    return Err(format!(
+                        "Session {} is already {}.",
+                        journal.group_id,
+                        journal.phase.label()
+                    )
+                    .into());
+                }
+
+                let mut out = format!("PRP Undo — {}\n{}\n\n", journal.group_id, "─".repeat(60));
+                let outcomes = undo_session(&mut journal);
+                let failed = outcomes.iter().filter(|(_, o)| o.is_err()).count();
+                for (name, outcome) in outcomes {
+                    match outcome {
+                        Ok(what) => out.push_str(&format!("   {}:  ✅ {}\n", name, what)),
+                        Err(e) => out.push_str(&format!("{}\n", e)),
+                    }
+                }
+                if failed > 0 {
+                    out.push_str(&format!("\n⚠️  {} repository/repositories could not be undone.", failed));
+                    return Err(out.into());
+                }
+                out.push_str("\n✅ Session undone.\n");
+                Ok(out)
+            }),
+            "groups" => run_sync(|| {
+                use crate::prp_hub::discovery::resolve_repositories;
+                use crate::prp_hub::group::{collect_groups, format_groups};
+
+                let repos = resolve_repositories(flag_value(&parts, "--set"))?;
+                Ok(format!("PRP Groups\n{}\n\n{}", "─".repeat(60), format_groups(&collect_groups(&repos))))
+            }),
+            "exec" => run_sync(|| {
+                use crate::prp_hub::exec::{parse_exec_args, run_collected};
+
+                let rest = input.trim_start().trim_start_matches("prp").trim_start();
+                let request = parse_exec_args(rest.strip_prefix("exec").unwrap_or(rest))?;
+                let (output, ok) = run_collected(&request)?;
+                let out = format!("PRP Exec — {}\n{}\n\n{}", request.command, "─".repeat(60), output);
+                if !ok {
+                    return Err(out.into());
+                }
+                Ok(out)
+            }),
+            "pull" => run_sync(|| {
+                use crate::prp_hub::config::load_config;
+                use crate::prp_hub::discovery::resolve_repositories;
+                use crate::prp_hub::pull::{format_pull_report, pull_all, pull_summary};
+
+                let check_only = parts.contains(&"--check");
+                let mut options = load_config().pull;
+                if parts.contains(&"--rebase") {
+                    options.rebase = true;
+                }
+                let repos = resolve_repositories(flag_value(&parts, "--set"))?;
+
+                let title = if check_only { "PRP Pull — check" } else { "PRP Pull" };
+                let mut out = format!("{}\n{}\n\n", title, "─".repeat(60));
+                let reports = pull_all(&repos, &options, check_only);
+                for report in &reports {
+                    out.push_str(&format_pull_report(report));
+                }
+                let (summary, ok) = pull_summary(&reports);
+                out.push_str(&format!("\n{}\n", summary));
+                if !ok {
+                    return Err(out.into());
+                }
+                Ok(out)
+            }),
+            "branch" | "switch" => run_sync(|| {
+                use crate::prp_hub::branch::{create_branch_all, has_upstream, publish_branch, switch_branch_all};
+                use crate::prp_hub::discovery::resolve_repositories;
+
+                let name = parts
+                    .get(2)
+                    .filter(|n| !n.starts_with("--"))
+                    .ok_or_else(|| format!("Usage: /prp {} <branch-name> [--set <workspace>]", sub))?;
+                let publish = parts.contains(&"--push");
+                let repos = resolve_repositories(flag_value(&parts, "--set"))?;
+
+                let title = if sub == "branch" { "PRP Branch" } else { "PRP Switch" };
+                let mut out = format!("{} — {}\n{}\n\n", title, name, "─".repeat(60));
+
+                let planned = if sub == "branch" {
+                    create_branch_all(&repos, name)
+                } else {
+                    switch_branch_all(&repos, name)
+                };
+                let outcomes = match planned {
+                    Ok(o) => o,
+                    Err(errors) => {
+                        out.push_str("❌ Nothing was changed:\n");
+                        for e in &errors {
+                            out.push_str(&format!("{}\n", e));
+                        }
+                        return Err(out.into());
+                    }
+                };
+
+                let mut failed = 0;
+                for ((label, outcome), info) in outcomes.into_iter().zip(&repos) {
+                    let outcome = outcome.and_then(|what| {
+                        if publish {
+                            publish_branch(info, name).map(|()| format!("{}, published", what))
+                        } else {
+                            Ok(what)
+                        }
+                    });
+                    match outcome {
+                        Ok(what) => out.push_str(&format!("   {}:  ✅ {}\n", label, what)),
+                        Err(e) => {
+                            failed += 1;
+                            out.push_str(&format!("{}\n", e));
+                        }
+                    }
+                }
+
+                if failed > 0 {
+                    out.push_str(&format!("\n⚠️  {} repository/repositories failed.", failed));
+                    return Err(out.into());
+                }
+                out.push_str(&format!("\n✅ {} repositories on '{}'.\n", repos.len(), name));
+                if !publish && repos.iter().any(|r| !has_upstream(r, name)) {
+                    out.push_str(&format!(
+                        "   PRP sessions need an upstream: publish with /prp switch {} --push\n",
+                        name
+                    ));
+                }
+                Ok(out)
+            }),
             _ => OutputBlock {
                 kind: OutputKind::Error,
-                content: format!("Unknown prp subcommand: '{}'. Try /prp or /prp list.", sub),
+                content: format!(
+                    "Unknown prp subcommand: '{}'. Try /prp, /prp list, /prp groups, /prp set, /prp branch, /prp switch, /prp pr, /prp pull, /prp tag, /prp exec, /prp resume or /prp undo.",
+                    sub
+                ),
+            },
+        },
+
+        // ── Workspace ─────────────────────────────────────────────────────────
+        "workspace" => match sub {
+            "sync" => run_sync(|| {
+                use crate::prp_hub::manifest::{
+                    format_sync_report, load_manifest, sync_summary, sync_workspace, MANIFEST_PATH,
+                };
+
+                let path = flag_value(&parts, "--manifest").unwrap_or(MANIFEST_PATH);
+                let manifest = load_manifest(std::path::Path::new(path))?;
+
+                let mut out = format!("Workspace Sync — {}\n{}\n\n", path, "─".repeat(60));
+                let reports = sync_workspace(&manifest, |r| out.push_str(&format_sync_report(r)));
+                let (summary, ok) = sync_summary(&reports);
+                out.push_str(&format!("\n{}\n", summary));
+                if !ok {
+                    return Err(out.into());
+                }
+                Ok(out)
+            }),
+            _ => OutputBlock {
+                kind: OutputKind::Error,
+                content: format!("Unknown workspace subcommand: '{}'. Try /workspace sync.", sub),
             },
         },
 
@@ -822,6 +1382,14 @@ where
     }
 }
 
+/// Value following `flag` in the command, e.g. `--set backend`.
+fn flag_value<'a>(parts: &[&'a str], flag: &str) -> Option<&'a str> {
+    parts
+        .iter()
+        .position(|p| *p == flag)
+        .and_then(|i| parts.get(i + 1).copied())
+}
+
 /// Detect the name of the git repository rooted in the current working directory.
 fn detect_local_repo_name() -> Option<String> {
     let output = std::process::Command::new("git")
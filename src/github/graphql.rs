@@ -4,17 +4,31 @@ use std::error::Error;
 
 const GITHUB_GRAPHQL_ENDPOINT: &str = "https://api.github.com/graphql";
 
+/// Overrides the endpoint of every client, e.g. for GitHub Enterprise or a mock server
+const ENDPOINT_ENV: &str = "GITLINK_GRAPHQL_URL";
+
 /// GraphQL client wrapper for GitHub API
 pub struct GraphQLClient {
     client: Client,
     token: String,
+    endpoint: String,
 }
 
 impl GraphQLClient {
+    /// Client for GitHub, or for `GITLINK_GRAPHQL_URL` when that is set
     pub fn new(token: String) -> Self {
+        match std::env::var(ENDPOINT_ENV) {
+            Ok(endpoint) if !endpoint.trim().is_empty() => Self::with_endpoint(token, endpoint.trim()),
+            _ => Self::with_endpoint(token, GITHUB_GRAPHQL_ENDPOINT),
+        }
+    }
+
+    /// Client for another GraphQL endpoint, e.g. GitHub Enterprise or a mock server
+    pub fn with_endpoint(token: String, endpoint: &str) -> Self {
         Self {
             client: Client::new(),
             token,
+            endpoint: endpoint.to_string(),
         }
     }
 
@@ -31,7 +45,7 @@ impl GraphQLClient {
 
         let response = self
             .client
-            .post(GITHUB_GRAPHQL_ENDPOINT)
+            .post(&self.endpoint)
             .header("Authorization", format!("Bearer {}", self.token))
             .header("User-Agent", "gitlink")
             .json(&body)
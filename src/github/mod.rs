@@ -4,4 +4,5 @@ pub mod graphql;
 pub mod repo_selector;
 pub mod sync_checker;
 pub mod push_checker;
-pub mod actions_client;
\ No newline at end of file
+pub mod actions_client;
+pub mod pulls_client;
@@ -0,0 +1,189 @@
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+
+const GITHUB_API_BASE: &str = "https://api.github.com";
+
+/// Pull request client for the GitHub REST API
+pub struct PullsClient {
+    client: Client,
+    token: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PullRequest {
+    pub number: u64,
+    pub html_url: String,
+    pub state: String,
+    pub title: String,
+    #[serde(default)]
+    pub draft: bool,
+    /// Only present when fetching a single pull request
+    #[serde(default)]
+    pub merged: bool,
+    #[serde(default)]
+    pub mergeable_state: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Review {
+    pub user: Option<ReviewUser>,
+    pub state: String,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ReviewUser {
+    pub login: String,
+}
+
+#[derive(Serialize)]
+struct CreatePull<'a> {
+    title: &'a str,
+    body: &'a str,
+    head: &'a str,
+    base: &'a str,
+}
+
+#[derive(Serialize)]
+struct UpdatePullBody<'a> {
+    body: &'a str,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Release {
+    pub id: u64,
+    pub html_url: String,
+}
+
+#[derive(Serialize)]
+struct CreateRelease<'a> {
+    tag_name: &'a str,
+    name: &'a str,
+    body: &'a str,
+}
+
+impl PullsClient {
+    pub fn new(token: String) -> Self {
+        Self {
+            client: Client::new(),
+            token,
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, url: &str) -> reqwest::RequestBuilder {
+        self.client
+            .request(method, url)
+            .header("Authorization", format!("Bearer {}", self.token))
+            .header("User-Agent", "gitlink")
+            .header("Accept", "application/vnd.github.v3+json")
+    }
+
+    async fn check(response: reqwest::Response) -> Result<reqwest::Response, Box<dyn Error>> {
+        if response.status().is_success() {
+            return Ok(response);
+        }
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        // GitHub explains validation failures (e.g. "No commits between ...") in `message`/`errors`
+        Err(format!("GitHub API error: {} {}", status, body.trim()).into())
+    }
+
+    /// Open a pull request from `head` into `base`
+    pub async fn create_pull(
+        &self,
+        owner: &str,
+        repo: &str,
+        title: &str,
+        body: &str,
+        head: &str,
+        base: &str,
+    ) -> Result<PullRequest, Box<dyn Error>> {
+        let url = format!("{}/repos/{}/{}/pulls", GITHUB_API_BASE, owner, repo);
+        let response = self
+            .request(reqwest::Method::POST, &url)
+            .json(&CreatePull { title, body, head, base })
+            .send()
+            .await?;
+        let pull: PullRequest = Self::check(response).await?.json().await?;
+        Ok(pull)
+    }
+
+    /// Replace the body of an existing pull request
+    pub async fn update_pull_body(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        body: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        let url = format!("{}/repos/{}/{}/pulls/{}", GITHUB_API_BASE, owner, repo, number);
+        let response = self
+            .request(reqwest::Method::PATCH, &url)
+            .json(&UpdatePullBody { body })
+            .send()
+            .await?;
+        Self::check(response).await?;
+        Ok(())
+    }
+
+    pub async fn fetch_pull(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<PullRequest, Box<dyn Error>> {
+        let url = format!("{}/repos/{}/{}/pulls/{}", GITHUB_API_BASE, owner, repo, number);
+        let response = self.request(reqwest::Method::GET, &url).send().await?;
+        let pull: PullRequest = Self::check(response).await?.json().await?;
+        Ok(pull)
+    }
+
+    pub async fn fetch_reviews(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+    ) -> Result<Vec<Review>, Box<dyn Error>> {
+        let url = format!(
+            "{}/repos/{}/{}/pulls/{}/reviews?per_page=100",
+            GITHUB_API_BASE, owner, repo, number
+        );
+        let response = self.request(reqwest::Method::GET, &url).send().await?;
+        let reviews: Vec<Review> = Self::check(response).await?.json().await?;
+        Ok(reviews)
+    }
+
+    /// Publish a release for an existing tag
+    pub async fn create_release(
+        &self,
+        owner: &str,
+        repo: &str,
+        tag: &str,
+        name: &str,
+        body: &str,
+    ) -> Result<Release, Box<dyn Error>> {
+        let url = format!("{}/repos/{}/{}/releases", GITHUB_API_BASE, owner, repo);
+        let response = self
+            .request(reqwest::Method::POST, &url)
+            .json(&CreateRelease { tag_name: tag, name, body })
+            .send()
+            .await?;
+        let release: Release = Self::check(response).await?.json().await?;
+        Ok(release)
+    }
+}
+
+/// Extract `(owner, repo)` from a GitHub remote URL.
+/// Handles `git@github.com:o/r.git`, `ssh://git@github.com/o/r.git` and `https://github.com/o/r`.
+pub fn parse_github_remote(url: &str) -> Option<(String, String)> {
+    let rest = url
+        .strip_prefix("git@github.com:")
+        .or_else(|| url.split_once("github.com/").map(|(_, r)| r))?;
+
+    let rest = rest.trim_end_matches('/').trim_end_matches(".git");
+    let (owner, repo) = rest.split_once('/')?;
+    if owner.is_empty() || repo.is_empty() || repo.contains('/') {
+        return None;
+    }
+    Some((owner.to_string(), repo.to_string()))
+}
@@ -54,6 +54,15 @@ impl PushStatus {
     }
 }
 
+/// Remote-tracking ref of `branch`: its configured upstream, else `origin/<branch>`
+fn tracking_ref_name(repo: &Repository, branch: &str) -> String {
+    repo.branch_upstream_name(&format!("refs/heads/{}", branch))
+        .ok()
+        .and_then(|name| name.as_str().map(str::to_string))
+        .filter(|name| name.starts_with("refs/remotes/"))
+        .unwrap_or_else(|| format!("refs/remotes/origin/{}", branch))
+}
+
 /// Check push status using local git repository
 pub fn check_push_status(branch: &str) -> Result<PushStatus, Box<dyn Error>> {
     let repo = Repository::discover(".")?;
@@ -82,7 +91,7 @@ pub fn check_push_status(branch: &str) -> Result<PushStatus, Box<dyn Error>> {
     // ----------------------------------
     // Get remote tracking branch
     // ----------------------------------
-    let remote_ref_name = format!("refs/remotes/origin/{}", branch);
+    let remote_ref_name = tracking_ref_name(&repo, branch);
 
     let remote_ref = match repo.find_reference(&remote_ref_name) {
         Ok(r) => r,
@@ -138,7 +147,7 @@ pub fn generate_push_preview(branch: &str) -> Result<Option<PushPreview>, Box<dy
     let head = repo.head()?;
     let local_oid = head.target().ok_or("No local HEAD")?;
 
-    let remote_ref_name = format!("refs/remotes/origin/{}", branch);
+    let remote_ref_name = tracking_ref_name(&repo, branch);
     let remote_ref = match repo.find_reference(&remote_ref_name) {
         Ok(r) => r,
         Err(_) => return Ok(None),
@@ -11,7 +11,79 @@ mod tui;
 //Used async tokio for running the main
 #[tokio::main]
 async fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    // Non-interactive subcommands; everything else opens the TUI
+    if args.first().map(String::as_str) == Some("workspace") {
+        std::process::exit(run_workspace_command(&args[1..]));
+    }
+    if args.first().map(String::as_str) == Some("prp") {
+        std::process::exit(run_prp_command(&args[1..]));
+    }
+
     if let Err(e) = tui::run() {
         eprintln!("GitLink crashed: {}", e);
     }
-}
\ No newline at end of file
+}
+
+/// `gitlink workspace sync [--manifest <path>]`
+fn run_workspace_command(args: &[String]) -> i32 {
+    let manifest = args
+        .iter()
+        .position(|a| a == "--manifest")
+        .and_then(|i| args.get(i + 1))
+        .map(String::as_str);
+
+    match args.first().map(String::as_str) {
+        Some("sync") => match prp_hub::manifest::run_workspace_sync(manifest) {
+            Ok(true) => 0,
+            Ok(false) => 1,
+            Err(e) => {
+                eprintln!("{}", e);
+                1
+            }
+        },
+        _ => {
+            eprintln!("Usage: gitlink workspace sync [--manifest <path>]");
+            2
+        }
+    }
+}
+
+/// `gitlink prp -m <message> [options]`: a whole PRP session without prompts,
+/// reported as JSON on stdout. Exit code 0 on success, 1 on failure, 2 on bad usage.
+fn run_prp_command(args: &[String]) -> i32 {
+    let options = match prp_hub::headless::parse_headless_args(args) {
+        Ok(o) => o,
+        Err(usage) => {
+            eprintln!("{}", usage);
+            return 2;
+        }
+    };
+
+    let report = prp_hub::headless::run_headless(&options);
+    match serde_json::to_string_pretty(&report) {
+        Ok(json) => println!("{}", json),
+        Err(e) => {
+            eprintln!("Cannot encode report: {}", e);
+            return 1;
+        }
+    }
+    if report.ok { 0 } else { 1 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn bad_usage_exits_with_2() {
+        assert_eq!(run_prp_command(&args(&["--push"])), 2);
+        assert_eq!(run_prp_command(&args(&["-m", "Bump", "--force"])), 2);
+        assert_eq!(run_workspace_command(&args(&["status"])), 2);
+    }
+}
@@ -0,0 +1,69 @@
+use chrono::{DateTime, Duration, Utc};
+
+use super::task::Task;
+use super::tree::descendant_ids;
+
+/// Ids of tasks completed more than `days` ago. A task is held back while
+/// any of its subtasks is open or was completed more recently, so subtrees
+/// are archived together.
+pub fn due_for_archive(tasks: &[Task], days: u32, now: DateTime<Utc>) -> Vec<String> {
+    if days == 0 {
+        return Vec::new();
+    }
+    let cutoff = now - Duration::days(days as i64);
+    let old_enough = |t: &Task| t.completed && t.completed_at.is_some_and(|at| at < cutoff);
+
+    tasks
+        .iter()
+        .filter(|t| old_enough(t))
+        .filter(|t| {
+            descendant_ids(tasks, &t.id)
+                .iter()
+                .all(|d| tasks.iter().find(|s| &s.id == d).is_some_and(old_enough))
+        })
+        .map(|t| t.id.clone())
+        .collect()
+}
+
+/// The task and all of its subtasks.
+pub fn subtree_ids(tasks: &[Task], id: &str) -> Vec<String> {
+    let mut ids = vec![id.to_string()];
+    ids.extend(descendant_ids(tasks, id));
+    ids
+}
+
+/// Remove the tasks with the given ids, returning each with the index it
+/// had, in ascending order so they can be put back one by one.
+pub fn take_tasks(tasks: &mut Vec<Task>, ids: &[String]) -> Vec<(usize, Task)> {
+    let mut taken = Vec::new();
+    let mut index = 0;
+    let mut original = 0;
+    while index < tasks.len() {
+        if ids.contains(&tasks[index].id) {
+            taken.push((original, tasks.remove(index)));
+        } else {
+            index += 1;
+        }
+        original += 1;
+    }
+    taken
+}
+
+/// Archived tasks matching `query` in title, tags or description, most
+/// recently completed first. Returns indices into `archive`.
+pub fn search(archive: &[Task], query: &str) -> Vec<usize> {
+    let query = query.trim().to_lowercase();
+    let mut found: Vec<usize> = archive
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| {
+            query.is_empty()
+                || t.title.to_lowercase().contains(&query)
+                || t.tags.iter().any(|tag| tag.to_lowercase().contains(&query))
+                || t.description.as_deref().is_some_and(|d| d.to_lowercase().contains(&query))
+        })
+        .map(|(i, _)| i)
+        .collect();
+    found.sort_by_key(|i| std::cmp::Reverse(archive[*i].completed_at.unwrap_or(archive[*i].updated_at)));
+    found
+}
@@ -0,0 +1,53 @@
+use super::task::Task;
+
+/// Column a task shows in: the last one once completed, otherwise its own
+/// column, falling back to the first when unset or no longer configured.
+pub fn column_index(task: &Task, columns: &[String]) -> usize {
+    let last = columns.len().saturating_sub(1);
+    if task.completed {
+        return last;
+    }
+    task.column
+        .as_deref()
+        .and_then(|c| columns.iter().position(|name| name == c))
+        .filter(|i| *i < last)
+        .unwrap_or(0)
+}
+
+/// Indices into `tasks` of each column's tasks, in list order.
+pub fn by_column(tasks: &[Task], columns: &[String]) -> Vec<Vec<usize>> {
+    let mut board = vec![Vec::new(); columns.len()];
+    for (i, task) in tasks.iter().enumerate() {
+        if let Some(col) = board.get_mut(column_index(task, columns)) {
+            col.push(i);
+        }
+    }
+    board
+}
+
+/// (column, completed) after moving `task` to column `to`. Moving to the
+/// last column completes the task but keeps its previous column, so that
+/// reopening puts it back where it was.
+pub fn move_to(task: &Task, columns: &[String], to: usize) -> (Option<String>, bool) {
+    if to + 1 >= columns.len() {
+        (task.column.clone(), true)
+    } else {
+        (Some(columns[to].clone()), false)
+    }
+}
+
+/// Parse "Backlog, In progress, Review, Done" into column names.
+pub fn parse_columns(input: &str) -> Result<Vec<String>, String> {
+    let columns: Vec<String> = input
+        .split(',')
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .collect();
+    if columns.len() < 2 {
+        return Err("Give at least two columns, separated by commas; the last holds completed tasks".to_string());
+    }
+    if let Some(dup) = columns.iter().enumerate().find(|(i, c)| columns[..*i].contains(c)).map(|(_, c)| c) {
+        return Err(format!("Column '{}' appears twice", dup));
+    }
+    Ok(columns)
+}
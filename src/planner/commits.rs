@@ -0,0 +1,205 @@
+use chrono::{DateTime, Utc};
+use git2::{BranchType, Commit, Oid, Repository, Sort};
+use regex::Regex;
+
+use super::task::{CommitLink, Task};
+use crate::prp_hub::branch::default_branch;
+use crate::prp_hub::types::RepositoryInfo;
+
+/// How far back each repository's history is read
+const MAX_COMMITS: usize = 1000;
+
+/// What a scan changed
+#[derive(Debug, Default)]
+pub struct LinkReport {
+    /// Newly linked commits
+    pub linked: usize,
+    /// Ids of tasks completed by a `Closes` trailer
+    pub closed: Vec<String>,
+}
+
+impl LinkReport {
+    pub fn is_empty(&self) -> bool {
+        self.linked == 0 && self.closed.is_empty()
+    }
+}
+
+fn ref_pattern() -> Regex {
+    Regex::new(r"(?i)\bgl-([0-9a-f]{4,})\b").expect("valid regex")
+}
+
+/// Hex parts of every task reference in `text`, lowercased.
+fn references(pattern: &Regex, text: &str) -> Vec<String> {
+    pattern.captures_iter(text).map(|c| c[1].to_lowercase()).collect()
+}
+
+/// References in `Closes gl-xxxx` trailer lines.
+fn closing_references(pattern: &Regex, message: &str) -> Vec<String> {
+    message
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.trim().split_once([':', ' '])?;
+            key.eq_ignore_ascii_case("closes").then_some(value)
+        })
+        .flat_map(|value| references(pattern, value))
+        .collect()
+}
+
+// Index of the one task whose id starts with `hex`. Ambiguous prefixes link nothing.
+fn resolve(tasks: &[Task], hex: &str) -> Option<usize> {
+    let mut found = tasks.iter().enumerate().filter(|(_, t)| t.ref_hex().starts_with(hex));
+    let (index, _) = found.next()?;
+    found.next().is_none().then_some(index)
+}
+
+fn commit_link(repo_name: &str, commit: &Commit, branch: Option<&str>) -> CommitLink {
+    CommitLink {
+        repo: repo_name.to_string(),
+        sha: commit.id().to_string(),
+        summary: commit.summary().unwrap_or("(no message)").to_string(),
+        branch: branch.map(str::to_string),
+        closes: false,
+        committed_at: DateTime::<Utc>::from_timestamp(commit.time().seconds(), 0).unwrap_or_else(Utc::now),
+    }
+}
+
+// Commits reachable from `tips`, newest first, not reachable from `hide`.
+fn walk(repo: &Repository, tips: &[Oid], hide: Option<Oid>) -> Vec<Oid> {
+    let mut walk = match repo.revwalk() {
+        Ok(w) => w,
+        Err(_) => return Vec::new(),
+    };
+    let _ = walk.set_sorting(Sort::TIME);
+    for tip in tips {
+        let _ = walk.push(*tip);
+    }
+    if let Some(oid) = hide {
+        let _ = walk.hide(oid);
+    }
+    walk.filter_map(|o| o.ok()).take(MAX_COMMITS).collect()
+}
+
+fn default_tip(repo: &Repository) -> Option<Oid> {
+    let name = default_branch(repo)?;
+    repo.find_branch(&name, BranchType::Local)
+        .or_else(|_| repo.find_branch(&format!("origin/{}", name), BranchType::Remote))
+        .ok()?
+        .get()
+        .target()
+}
+
+/// Link the commits of one repository to the tasks they reference: by
+/// mentioning the task's short ref in the message, or by being on a local
+/// branch whose name contains it. With `close`, open tasks named in a
+/// `Closes` trailer on the default branch are completed.
+fn link_repository(tasks: &mut [Task], info: &RepositoryInfo, close: bool, report: &mut LinkReport) {
+    let repo = match Repository::open(&info.path) {
+        Ok(r) => r,
+        Err(_) => return,
+    };
+    let pattern = ref_pattern();
+    let default_tip = default_tip(&repo);
+
+    let branches: Vec<(String, Oid)> = repo
+        .branches(Some(BranchType::Local))
+        .map(|it| {
+            it.filter_map(|b| b.ok())
+                .filter_map(|(b, _)| Some((b.name().ok()??.to_string(), b.get().target()?)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut link = |tasks: &mut [Task], hex: &str, commit: &Commit, branch: Option<&str>| {
+        if let Some(i) = resolve(tasks, hex) {
+            if tasks[i].add_commit(commit_link(&info.name, commit, branch)) {
+                report.linked += 1;
+            }
+        }
+    };
+
+    // Mentions in commit messages, on any local branch
+    let tips: Vec<Oid> = branches.iter().map(|(_, oid)| *oid).chain(default_tip).collect();
+    for oid in walk(&repo, &tips, None) {
+        let Ok(commit) = repo.find_commit(oid) else { continue };
+        for hex in references(&pattern, commit.message().unwrap_or("")) {
+            link(tasks, &hex, &commit, None);
+        }
+    }
+
+    // Work on branches named after a task, up to where they leave the default branch
+    for (name, tip) in &branches {
+        let refs = references(&pattern, name);
+        if refs.is_empty() {
+            continue;
+        }
+        for oid in walk(&repo, &[*tip], default_tip.filter(|d| d != tip)) {
+            let Ok(commit) = repo.find_commit(oid) else { continue };
+            for hex in &refs {
+                link(tasks, hex, &commit, Some(name));
+            }
+        }
+    }
+
+    if !close {
+        return;
+    }
+    let Some(default_tip) = default_tip else { return };
+    for oid in walk(&repo, &[default_tip], None) {
+        let Ok(commit) = repo.find_commit(oid) else { continue };
+        for hex in closing_references(&pattern, commit.message().unwrap_or("")) {
+            let Some(i) = resolve(tasks, &hex) else { continue };
+            let task = &mut tasks[i];
+            let sha = commit.id().to_string();
+            let Some(link) = task.commits.iter_mut().find(|c| c.repo == info.name && c.sha == sha) else { continue };
+            // Each trailer closes once, so a task reopened afterwards stays open
+            if std::mem::replace(&mut link.closes, true) {
+                continue;
+            }
+            let landed = link.committed_at;
+            if !task.completed {
+                task.toggle();
+                task.completed_at = Some(landed);
+                report.closed.push(task.id.clone());
+            }
+        }
+    }
+}
+
+/// Scan every repository under the working directory. See [`link_repository`].
+pub fn link_commits(tasks: &mut [Task], close: bool) -> LinkReport {
+    let mut report = LinkReport::default();
+    if tasks.is_empty() {
+        return report;
+    }
+    let repos = crate::prp_hub::discovery::discover_repositories(".").unwrap_or_default();
+    for info in &repos {
+        link_repository(tasks, info, close, &mut report);
+    }
+    report
+}
+
+/// Copy what [`link_commits`] found on `scanned`, a snapshot of the list, onto
+/// `tasks`, which may have been edited while the scan ran. Tasks deleted since
+/// are skipped, and a task reopened since is only closed by a new trailer.
+pub fn merge_links(tasks: &mut [Task], scanned: Vec<Task>, report: &LinkReport) {
+    for found in scanned {
+        let Some(task) = tasks.iter_mut().find(|t| t.id == found.id) else { continue };
+        for link in found.commits {
+            let closes = link.closes;
+            let (repo, sha) = (link.repo.clone(), link.sha.clone());
+            task.add_commit(link);
+            if let Some(existing) = task.commits.iter_mut().find(|c| c.repo == repo && c.sha == sha) {
+                existing.closes |= closes;
+            }
+        }
+        if report.closed.contains(&task.id) && !task.completed {
+            task.toggle();
+            task.completed_at = found.completed_at;
+        }
+    }
+}
+
+/// "repo@abc1234"
+pub fn commit_label(link: &CommitLink) -> String {
+    format!("{}@{}", link.repo, &link.sha[..7.min(link.sha.len())])
+}
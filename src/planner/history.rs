@@ -1,4 +1,5 @@
-use super::task::Task;
+use super::task::{Priority, Task};
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -9,6 +10,24 @@ pub enum Action {
     UpdateTitle { id: String, old_title: String, new_title: String },
     UpdateDescription { id: String, old_desc: Option<String>, new_desc: Option<String> },
     UpdateTags { id: String, old_tags: Vec<String>, new_tags: Vec<String> },
+    UpdateDue { id: String, old_due: Option<NaiveDate>, new_due: Option<NaiveDate> },
+    UpdatePriority { id: String, old_priority: Priority, new_priority: Priority },
+    UpdateEffort { id: String, old_effort: Option<u32>, new_effort: Option<u32> },
+    SetParent { id: String, old_parent: Option<String>, new_parent: Option<String> },
+    AddBlocker { id: String, blocker: String },
+    RemoveBlocker { id: String, blocker: String },
+    /// Moved between board columns; reaching or leaving the last column completes or reopens it
+    MoveColumn {
+        id: String,
+        old_column: Option<String>,
+        new_column: Option<String>,
+        old_completed: bool,
+        new_completed: bool,
+    },
+    /// Tasks moved to the archive, with the list positions they had
+    Archive { tasks: Vec<(usize, Task)> },
+    /// Tasks moved back from the archive
+    Restore { tasks: Vec<Task> },
 }
 
 pub struct History {
@@ -37,7 +56,7 @@ impl History {
         !self.redo_stack.is_empty()
     }
 
-    pub fn undo(&mut self, tasks: &mut Vec<Task>) -> bool {
+    pub fn undo(&mut self, tasks: &mut Vec<Task>, archive: &mut Vec<Task>) -> bool {
         if let Some(action) = self.undo_stack.pop() {
             match &action {
                 Action::Add { task } => {
@@ -66,6 +85,51 @@ impl History {
                         task.set_tags(old_tags.clone());
                     }
                 }
+                Action::UpdateDue { id, old_due, .. } => {
+                    if let Some(task) = tasks.iter_mut().find(|t| &t.id == id) {
+                        task.set_due(*old_due);
+                    }
+                }
+                Action::UpdatePriority { id, old_priority, .. } => {
+                    if let Some(task) = tasks.iter_mut().find(|t| &t.id == id) {
+                        task.set_priority(*old_priority);
+                    }
+                }
+                Action::UpdateEffort { id, old_effort, .. } => {
+                    if let Some(task) = tasks.iter_mut().find(|t| &t.id == id) {
+                        task.set_effort(*old_effort);
+                    }
+                }
+                Action::SetParent { id, old_parent, .. } => {
+                    if let Some(task) = tasks.iter_mut().find(|t| &t.id == id) {
+                        task.set_parent(old_parent.clone());
+                    }
+                }
+                Action::MoveColumn { id, old_column, old_completed, .. } => {
+                    if let Some(task) = tasks.iter_mut().find(|t| &t.id == id) {
+                        task.set_column(old_column.clone(), *old_completed);
+                    }
+                }
+                Action::AddBlocker { id, blocker } => {
+                    if let Some(task) = tasks.iter_mut().find(|t| &t.id == id) {
+                        task.remove_blocker(blocker);
+                    }
+                }
+                Action::RemoveBlocker { id, blocker } => {
+                    if let Some(task) = tasks.iter_mut().find(|t| &t.id == id) {
+                        task.add_blocker(blocker.clone());
+                    }
+                }
+                Action::Archive { tasks: archived } => {
+                    archive.retain(|a| !archived.iter().any(|(_, t)| t.id == a.id));
+                    for (index, task) in archived {
+                        tasks.insert((*index).min(tasks.len()), task.clone());
+                    }
+                }
+                Action::Restore { tasks: restored } => {
+                    tasks.retain(|t| !restored.iter().any(|r| r.id == t.id));
+                    archive.extend(restored.iter().cloned());
+                }
             }
             self.redo_stack.push(action);
             true
@@ -74,7 +138,7 @@ impl History {
         }
     }
 
-    pub fn redo(&mut self, tasks: &mut Vec<Task>) -> bool {
+    pub fn redo(&mut self, tasks: &mut Vec<Task>, archive: &mut Vec<Task>) -> bool {
         if let Some(action) = self.redo_stack.pop() {
             match &action {
                 Action::Add { task } => {
@@ -103,6 +167,49 @@ impl History {
                         task.set_tags(new_tags.clone());
                     }
                 }
+                Action::UpdateDue { id, new_due, .. } => {
+                    if let Some(task) = tasks.iter_mut().find(|t| &t.id == id) {
+                        task.set_due(*new_due);
+                    }
+                }
+                Action::UpdatePriority { id, new_priority, .. } => {
+                    if let Some(task) = tasks.iter_mut().find(|t| &t.id == id) {
+                        task.set_priority(*new_priority);
+                    }
+                }
+                Action::UpdateEffort { id, new_effort, .. } => {
+                    if let Some(task) = tasks.iter_mut().find(|t| &t.id == id) {
+                        task.set_effort(*new_effort);
+                    }
+                }
+                Action::SetParent { id, new_parent, .. } => {
+                    if let Some(task) = tasks.iter_mut().find(|t| &t.id == id) {
+                        task.set_parent(new_parent.clone());
+                    }
+                }
+                Action::MoveColumn { id, new_column, new_completed, .. } => {
+                    if let Some(task) = tasks.iter_mut().find(|t| &t.id == id) {
+                        task.set_column(new_column.clone(), *new_completed);
+                    }
+                }
+                Action::AddBlocker { id, blocker } => {
+                    if let Some(task) = tasks.iter_mut().find(|t| &t.id == id) {
+                        task.add_blocker(blocker.clone());
+                    }
+                }
+                Action::RemoveBlocker { id, blocker } => {
+                    if let Some(task) = tasks.iter_mut().find(|t| &t.id == id) {
+                        task.remove_blocker(blocker);
+                    }
+                }
+                Action::Archive { tasks: archived } => {
+                    tasks.retain(|t| !archived.iter().any(|(_, a)| a.id == t.id));
+                    archive.extend(archived.iter().map(|(_, t)| t.clone()));
+                }
+                Action::Restore { tasks: restored } => {
+                    archive.retain(|a| !restored.iter().any(|r| r.id == a.id));
+                    tasks.extend(restored.iter().cloned());
+                }
             }
             self.undo_stack.push(action);
             true
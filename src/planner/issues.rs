@@ -0,0 +1,579 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::json;
+
+use super::task::{IssueLink, Task};
+use crate::github::graphql::GraphQLClient;
+
+const ISSUE_FIELDS: &str = "id number title body state updatedAt closedAt labels(first: 50) { nodes { name } }";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Nodes<T> {
+    pub nodes: Vec<T>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Label {
+    /// Only requested for repository labels
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteIssue {
+    pub id: String,
+    pub number: u64,
+    pub title: String,
+    #[serde(default)]
+    pub body: String,
+    /// OPEN or CLOSED
+    pub state: String,
+    pub updated_at: DateTime<Utc>,
+    pub closed_at: Option<DateTime<Utc>>,
+    pub labels: Nodes<Label>,
+}
+
+/// Repository id and labels, needed to create issues and map tags to labels
+#[derive(Debug, Clone, Deserialize)]
+pub struct RepoMeta {
+    pub id: String,
+    pub labels: Nodes<Label>,
+}
+
+impl RepoMeta {
+    fn has_label(&self, tag: &str) -> bool {
+        self.labels.nodes.iter().any(|l| l.name.eq_ignore_ascii_case(tag))
+    }
+
+    /// Ids of the repository labels named like the tags; other tags stay local
+    fn label_ids(&self, tags: &[String]) -> Vec<String> {
+        self.labels
+            .nodes
+            .iter()
+            .filter(|l| tags.iter().any(|t| l.name.eq_ignore_ascii_case(t)))
+            .map(|l| l.id.clone())
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RepositoryData {
+    repository: Option<RepositoryNode>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RepositoryNode {
+    #[serde(flatten)]
+    meta: RepoMeta,
+    #[serde(default)]
+    issue: Option<RemoteIssue>,
+    #[serde(default)]
+    issues: Option<Nodes<RemoteIssue>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateIssueData {
+    create_issue: IssuePayload,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateIssueData {
+    update_issue: IssuePayload,
+}
+
+#[derive(Debug, Deserialize)]
+struct IssuePayload {
+    issue: RemoteIssue,
+}
+
+/// What syncing one task did
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SyncOutcome {
+    UpToDate,
+    /// The task's changes were written to the issue
+    Pushed,
+    /// The issue's changes were written to the task
+    Pulled,
+}
+
+/// Which side wins when task and issue differ
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SyncDirection {
+    None,
+    Push,
+    Pull,
+}
+
+async fn fetch_repository(
+    client: &GraphQLClient,
+    owner: &str,
+    repo: &str,
+    select: &str,
+    variables: serde_json::Value,
+    params: &str,
+) -> Result<RepositoryNode, String> {
+    let query = format!(
+        "query($owner: String!, $name: String!{}) {{ repository(owner: $owner, name: $name) {{ id labels(first: 100) {{ nodes {{ id name }} }} {} }} }}",
+        params,
+        select.replace("ISSUE_FIELDS", ISSUE_FIELDS),
+    );
+    let mut vars = json!({ "owner": owner, "name": repo });
+    if let (Some(vars), Some(extra)) = (vars.as_object_mut(), variables.as_object()) {
+        vars.extend(extra.clone());
+    }
+
+    let data: RepositoryData = client.query(&query, vars).await.map_err(|e| e.to_string())?;
+    data.repository
+        .ok_or_else(|| format!("Repository {}/{} not found or not accessible", owner, repo))
+}
+
+/// Repository id and labels.
+pub async fn fetch_repo_meta(client: &GraphQLClient, owner: &str, repo: &str) -> Result<RepoMeta, String> {
+    Ok(fetch_repository(client, owner, repo, "", json!({}), "").await?.meta)
+}
+
+/// The linked issue, together with its repository's labels.
+pub async fn fetch_issue(client: &GraphQLClient, link: &IssueLink) -> Result<(RepoMeta, RemoteIssue), String> {
+    let node = fetch_repository(
+        client,
+        &link.owner,
+        &link.repo,
+        "issue(number: $number) { ISSUE_FIELDS }",
+        json!({ "number": link.number }),
+        ", $number: Int!",
+    )
+    .await?;
+    let issue = node.issue.ok_or_else(|| format!("Issue {} not found", link))?;
+    Ok((node.meta, issue))
+}
+
+/// Most recently updated issues of a repository.
+pub async fn fetch_repo_issues(
+    client: &GraphQLClient,
+    owner: &str,
+    repo: &str,
+    states: &[&str],
+    limit: i32,
+) -> Result<(RepoMeta, Vec<RemoteIssue>), String> {
+    let node = fetch_repository(
+        client,
+        owner,
+        repo,
+        "issues(states: $states, first: $limit, orderBy: {field: UPDATED_AT, direction: DESC}) { nodes { ISSUE_FIELDS } }",
+        json!({ "states": states, "limit": limit }),
+        ", $states: [IssueState!], $limit: Int!",
+    )
+    .await?;
+    Ok((node.meta, node.issues.map(|i| i.nodes).unwrap_or_default()))
+}
+
+fn body_of(task: &Task) -> String {
+    task.description.clone().unwrap_or_default()
+}
+
+fn state_of(task: &Task) -> &'static str {
+    if task.completed { "CLOSED" } else { "OPEN" }
+}
+
+/// Whether task and issue already agree on title, body, state and labels.
+pub fn matches_issue(task: &Task, issue: &RemoteIssue, meta: &RepoMeta) -> bool {
+    let mut local: Vec<String> = task.tags.iter().filter(|t| meta.has_label(t)).map(|t| t.to_lowercase()).collect();
+    let mut remote: Vec<String> = issue.labels.nodes.iter().map(|l| l.name.to_lowercase()).collect();
+    local.sort();
+    remote.sort();
+
+    task.title == issue.title
+        && body_of(task).trim() == issue.body.trim()
+        && state_of(task) == issue.state
+        && local == remote
+}
+
+/// Decide the direction from the edit times. Changes on only one side since
+/// the last sync go to the other; if both sides changed, the later edit wins.
+pub fn sync_direction(task: &Task, remote_updated: DateTime<Utc>) -> SyncDirection {
+    let synced = task.issue.as_ref().and_then(|l| l.synced_at);
+    let local_changed = synced.is_none_or(|s| task.updated_at > s);
+    let remote_changed = synced.is_none_or(|s| remote_updated > s);
+
+    match (local_changed, remote_changed) {
+        (false, false) => SyncDirection::None,
+        (true, false) => SyncDirection::Push,
+        (false, true) => SyncDirection::Pull,
+        (true, true) if task.updated_at > remote_updated => SyncDirection::Push,
+        (true, true) => SyncDirection::Pull,
+    }
+}
+
+/// Overwrite the task with the issue. Tags that are not labels of the
+/// repository are kept, since they cannot exist on the issue.
+pub fn apply_issue(task: &mut Task, issue: &RemoteIssue, meta: &RepoMeta) {
+    let mut tags: Vec<String> = issue.labels.nodes.iter().map(|l| l.name.clone()).collect();
+    tags.extend(task.tags.iter().filter(|t| !meta.has_label(t)).cloned());
+
+    task.title = issue.title.clone();
+    task.description = if issue.body.trim().is_empty() { None } else { Some(issue.body.clone()) };
+    task.tags = tags;
+    task.completed = issue.state == "CLOSED";
+    task.completed_at = if task.completed { Some(issue.closed_at.unwrap_or(issue.updated_at)) } else { None };
+    task.updated_at = issue.updated_at;
+    if let Some(link) = task.issue.as_mut() {
+        link.synced_at = Some(issue.updated_at);
+    }
+}
+
+fn mark_synced(task: &mut Task, issue: &RemoteIssue) {
+    let synced = task.updated_at.max(issue.updated_at);
+    if let Some(link) = task.issue.as_mut() {
+        link.synced_at = Some(synced);
+    }
+}
+
+async fn update_issue(client: &GraphQLClient, task: &Task, issue_id: &str, meta: &RepoMeta) -> Result<RemoteIssue, String> {
+    let query = "mutation($input: UpdateIssueInput!) { updateIssue(input: $input) { issue { ISSUE_FIELDS } } }"
+        .replace("ISSUE_FIELDS", ISSUE_FIELDS);
+    let input = json!({
+        "id": issue_id,
+        "title": task.title,
+        "body": body_of(task),
+        "state": state_of(task),
+        "labelIds": meta.label_ids(&task.tags),
+    });
+
+    let data: UpdateIssueData = client
+        .query(&query, json!({ "input": input }))
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(data.update_issue.issue)
+}
+
+/// Bring a linked task and its issue in line, in whichever direction
+/// `sync_direction` picks. Unlinked tasks are left alone.
+pub async fn sync_task(client: &GraphQLClient, task: &mut Task) -> Result<SyncOutcome, String> {
+    let link = match &task.issue {
+        Some(l) => l.clone(),
+        None => return Ok(SyncOutcome::UpToDate),
+    };
+    let (meta, issue) = fetch_issue(client, &link).await?;
+
+    if matches_issue(task, &issue, &meta) {
+        mark_synced(task, &issue);
+        return Ok(SyncOutcome::UpToDate);
+    }
+
+    match sync_direction(task, issue.updated_at) {
+        SyncDirection::None => Ok(SyncOutcome::UpToDate),
+        SyncDirection::Pull => {
+            apply_issue(task, &issue, &meta);
+            Ok(SyncOutcome::Pulled)
+        }
+        SyncDirection::Push => {
+            let updated = update_issue(client, task, &issue.id, &meta).await?;
+            mark_synced(task, &updated);
+            Ok(SyncOutcome::Pushed)
+        }
+    }
+}
+
+/// Sync every linked task. Results are (task index, outcome), in list order.
+pub async fn sync_all(client: &GraphQLClient, tasks: &mut [Task]) -> Vec<(usize, Result<SyncOutcome, String>)> {
+    let mut results = Vec::new();
+    for (i, task) in tasks.iter_mut().enumerate() {
+        if task.issue.is_some() {
+            results.push((i, sync_task(client, task).await));
+        }
+    }
+    results
+}
+
+/// Add a task for every issue of the repository that no task links to yet.
+/// Returns how many were imported and how many were already linked.
+pub async fn import_issues(
+    client: &GraphQLClient,
+    owner: &str,
+    repo: &str,
+    include_closed: bool,
+    tasks: &mut Vec<Task>,
+) -> Result<(usize, usize), String> {
+    let states: &[&str] = if include_closed { &["OPEN", "CLOSED"] } else { &["OPEN"] };
+    let (meta, issues) = fetch_repo_issues(client, owner, repo, states, 100).await?;
+
+    let (mut imported, mut linked) = (0, 0);
+    for issue in issues {
+        let known = tasks.iter().any(|t| {
+            t.issue.as_ref().is_some_and(|l| {
+                l.number == issue.number && l.owner.eq_ignore_ascii_case(owner) && l.repo.eq_ignore_ascii_case(repo)
+            })
+        });
+        if known {
+            linked += 1;
+            continue;
+        }
+
+        let mut task = Task::new(issue.title.clone());
+        task.issue = Some(IssueLink::new(owner, repo, issue.number));
+        apply_issue(&mut task, &issue, &meta);
+        tasks.push(task);
+        imported += 1;
+    }
+    Ok((imported, linked))
+}
+
+/// Open an issue in `owner/repo` from the task and link the two.
+pub async fn create_issue(client: &GraphQLClient, task: &mut Task, owner: &str, repo: &str) -> Result<IssueLink, String> {
+    if let Some(link) = &task.issue {
+        return Err(format!("Task is already linked to {}", link));
+    }
+    let meta = fetch_repo_meta(client, owner, repo).await?;
+
+    let query = "mutation($input: CreateIssueInput!) { createIssue(input: $input) { issue { ISSUE_FIELDS } } }"
+        .replace("ISSUE_FIELDS", ISSUE_FIELDS);
+    let input = json!({
+        "repositoryId": meta.id,
+        "title": task.title,
+        "body": body_of(task),
+        "labelIds": meta.label_ids(&task.tags),
+    });
+    let data: CreateIssueData = client
+        .query(&query, json!({ "input": input }))
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut issue = data.create_issue.issue;
+
+    // Issues are always created open
+    if task.completed {
+        issue = update_issue(client, task, &issue.id, &meta).await?;
+    }
+
+    let mut link = IssueLink::new(owner, repo, issue.number);
+    link.synced_at = Some(task.updated_at.max(issue.updated_at));
+    task.issue = Some(link.clone());
+    Ok(link)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    use chrono::{Duration, TimeZone};
+    use serde_json::Value;
+
+    use super::*;
+
+    /// GraphQL server on a local port answering every POST with `respond(request body)`
+    struct MockServer {
+        url: String,
+        requests: Arc<Mutex<Vec<Value>>>,
+    }
+
+    impl MockServer {
+        fn start(respond: impl Fn(&Value) -> Value + Send + 'static) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}/graphql", listener.local_addr().unwrap());
+            let requests = Arc::new(Mutex::new(Vec::new()));
+            let seen = Arc::clone(&requests);
+            std::thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let Ok(mut stream) = stream else { continue };
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut length = 0;
+                    loop {
+                        let mut line = String::new();
+                        if reader.read_line(&mut line).unwrap_or(0) == 0 || line.trim().is_empty() {
+                            break;
+                        }
+                        if let Some((name, value)) = line.split_once(':') {
+                            if name.eq_ignore_ascii_case("content-length") {
+                                length = value.trim().parse().unwrap_or(0);
+                            }
+                        }
+                    }
+                    let mut body = vec![0; length];
+                    reader.read_exact(&mut body).unwrap();
+                    let request: Value = serde_json::from_slice(&body).unwrap();
+                    let response = respond(&request).to_string();
+                    seen.lock().unwrap().push(request);
+                    let _ = write!(
+                        stream,
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        response.len(),
+                        response,
+                    );
+                }
+            });
+            Self { url, requests }
+        }
+
+        fn client(&self) -> GraphQLClient {
+            GraphQLClient::with_endpoint("token".to_string(), &self.url)
+        }
+
+        fn queries(&self) -> Vec<String> {
+            self.requests.lock().unwrap().iter().map(|r| r["query"].as_str().unwrap_or("").to_string()).collect()
+        }
+
+        fn request(&self, i: usize) -> Value {
+            self.requests.lock().unwrap()[i].clone()
+        }
+    }
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 1, hour, 0, 0).unwrap()
+    }
+
+    fn labels() -> Value {
+        json!({ "nodes": [{ "id": "L_bug", "name": "bug" }, { "id": "L_docs", "name": "docs" }] })
+    }
+
+    fn issue(number: u64, title: &str, state: &str, updated: DateTime<Utc>, labels: &[&str]) -> Value {
+        json!({
+            "id": format!("I_{}", number),
+            "number": number,
+            "title": title,
+            "body": "",
+            "state": state,
+            "updatedAt": updated.to_rfc3339(),
+            "closedAt": if state == "CLOSED" { Some(updated.to_rfc3339()) } else { None },
+            "labels": { "nodes": labels.iter().map(|l| json!({ "name": l })).collect::<Vec<_>>() },
+        })
+    }
+
+    fn repository(extra: Value) -> Value {
+        let mut repository = json!({ "id": "R_1", "labels": labels() });
+        if let (Some(repository), Some(extra)) = (repository.as_object_mut(), extra.as_object()) {
+            repository.extend(extra.clone());
+        }
+        json!({ "data": { "repository": repository } })
+    }
+
+    /// A task linked to owner/repo#7, last synced and edited at `synced`
+    fn linked_task(title: &str, synced: DateTime<Utc>) -> Task {
+        let mut task = Task::new(title.to_string());
+        task.updated_at = synced;
+        let mut link = IssueLink::new("owner", "repo", 7);
+        link.synced_at = Some(synced);
+        task.issue = Some(link);
+        task
+    }
+
+    #[tokio::test]
+    async fn sync_pulls_remote_changes() {
+        let server = MockServer::start(|_| repository(json!({ "issue": issue(7, "Renamed on GitHub", "CLOSED", at(12), &["bug"]) })));
+        let mut task = linked_task("Original", at(10));
+        task.tags = vec!["local-only".to_string()];
+
+        let outcome = sync_task(&server.client(), &mut task).await.unwrap();
+
+        assert_eq!(outcome, SyncOutcome::Pulled);
+        assert_eq!(task.title, "Renamed on GitHub");
+        assert!(task.completed);
+        assert_eq!(task.tags, vec!["bug", "local-only"]);
+        assert_eq!(task.issue.unwrap().synced_at, Some(at(12)));
+        assert_eq!(server.queries().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn sync_pushes_local_changes() {
+        let server = MockServer::start(|request| {
+            if request["query"].as_str().unwrap_or("").contains("updateIssue") {
+                json!({ "data": { "updateIssue": { "issue": issue(7, "Edited locally", "OPEN", at(13), &["docs"]) } } })
+            } else {
+                repository(json!({ "issue": issue(7, "Original", "OPEN", at(10), &[]) }))
+            }
+        });
+        let mut task = linked_task("Original", at(10));
+        task.title = "Edited locally".to_string();
+        task.tags = vec!["docs".to_string(), "local-only".to_string()];
+        task.updated_at = at(11);
+
+        let outcome = sync_task(&server.client(), &mut task).await.unwrap();
+
+        assert_eq!(outcome, SyncOutcome::Pushed);
+        let input = server.request(1)["variables"]["input"].clone();
+        assert_eq!(input["id"], "I_7");
+        assert_eq!(input["title"], "Edited locally");
+        assert_eq!(input["state"], "OPEN");
+        assert_eq!(input["labelIds"], json!(["L_docs"]));
+        assert_eq!(task.issue.unwrap().synced_at, Some(at(13)));
+    }
+
+    #[tokio::test]
+    async fn sync_conflict_takes_the_later_edit() {
+        // Both sides changed since the last sync; the issue was edited last
+        let server = MockServer::start(|_| repository(json!({ "issue": issue(7, "Remote edit", "OPEN", at(14), &[]) })));
+        let mut task = linked_task("Original", at(10));
+        task.title = "Local edit".to_string();
+        task.updated_at = at(12);
+
+        let outcome = sync_task(&server.client(), &mut task).await.unwrap();
+
+        assert_eq!(outcome, SyncOutcome::Pulled);
+        assert_eq!(task.title, "Remote edit");
+        assert_eq!(server.queries().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn sync_of_matching_task_only_records_the_time() {
+        let server = MockServer::start(|_| repository(json!({ "issue": issue(7, "Same", "OPEN", at(12), &[]) })));
+        let mut task = linked_task("Same", at(10));
+        task.updated_at = at(11);
+
+        let outcome = sync_task(&server.client(), &mut task).await.unwrap();
+
+        assert_eq!(outcome, SyncOutcome::UpToDate);
+        assert_eq!(task.issue.unwrap().synced_at, Some(at(12)));
+        assert_eq!(server.queries().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn import_skips_linked_issues() {
+        let server = MockServer::start(|_| {
+            repository(json!({ "issues": { "nodes": [
+                issue(7, "Already linked", "OPEN", at(10), &[]),
+                issue(8, "New issue", "OPEN", at(11), &["bug"]),
+            ] } }))
+        });
+        let mut tasks = vec![linked_task("Already linked", at(10))];
+
+        let counts = import_issues(&server.client(), "Owner", "Repo", false, &mut tasks).await.unwrap();
+
+        assert_eq!(counts, (1, 1));
+        assert_eq!(tasks.len(), 2);
+        let imported = &tasks[1];
+        assert_eq!(imported.title, "New issue");
+        assert_eq!(imported.tags, vec!["bug"]);
+        assert_eq!(imported.issue.as_ref().map(|l| l.number), Some(8));
+        assert_eq!(server.request(0)["variables"]["states"], json!(["OPEN"]));
+    }
+
+    #[tokio::test]
+    async fn create_closes_the_issue_of_a_completed_task() {
+        let server = MockServer::start(|request| {
+            let query = request["query"].as_str().unwrap_or("");
+            if query.contains("createIssue") {
+                json!({ "data": { "createIssue": { "issue": issue(9, "Done already", "OPEN", at(12), &[]) } } })
+            } else if query.contains("updateIssue") {
+                json!({ "data": { "updateIssue": { "issue": issue(9, "Done already", "CLOSED", at(12) + Duration::minutes(1), &[]) } } })
+            } else {
+                repository(json!({}))
+            }
+        });
+        let mut task = Task::new("Done already".to_string());
+        task.toggle();
+
+        let link = create_issue(&server.client(), &mut task, "owner", "repo").await.unwrap();
+
+        assert_eq!(link.number, 9);
+        let queries = server.queries();
+        assert_eq!(queries.len(), 3);
+        assert!(queries[1].contains("createIssue"));
+        assert_eq!(server.request(1)["variables"]["input"]["repositoryId"], "R_1");
+        assert_eq!(server.request(2)["variables"]["input"]["state"], "CLOSED");
+        assert!(create_issue(&server.client(), &mut task, "owner", "repo").await.is_err());
+    }
+}
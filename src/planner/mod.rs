@@ -1,4 +1,10 @@
 pub mod storage;
 pub mod ui;
 pub mod task;
-pub mod history;
\ No newline at end of file
+pub mod archive;
+pub mod board;
+pub mod history;
+pub mod issues;
+pub mod commits;
+pub mod schedule;
+pub mod tree;
\ No newline at end of file
@@ -0,0 +1,190 @@
+use chrono::{Datelike, Duration, NaiveDate, TimeDelta, Weekday};
+
+use super::task::Task;
+
+/// Minutes in a working day, for efforts entered as "1d"
+const DAY_MINUTES: u32 = 8 * 60;
+
+/// How the planner orders its list
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TaskSort {
+    Created,
+    Due,
+    Priority,
+}
+
+impl TaskSort {
+    pub fn next(self) -> Self {
+        match self {
+            TaskSort::Created => TaskSort::Due,
+            TaskSort::Due => TaskSort::Priority,
+            TaskSort::Priority => TaskSort::Created,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TaskSort::Created => "created",
+            TaskSort::Due => "due date",
+            TaskSort::Priority => "priority",
+        }
+    }
+}
+
+/// Sort in place. Due and priority orders put open tasks first and tasks
+/// without a due date last; ties keep their created order.
+pub fn sort_tasks(tasks: &mut [Task], sort: TaskSort) {
+    tasks.sort_by_key(|t| t.created_at);
+    match sort {
+        TaskSort::Created => {}
+        TaskSort::Due => tasks.sort_by_key(|t| (t.completed, t.due.is_none(), t.due, std::cmp::Reverse(t.priority))),
+        TaskSort::Priority => tasks.sort_by_key(|t| (t.completed, std::cmp::Reverse(t.priority), t.due.is_none(), t.due)),
+    }
+}
+
+fn parse_weekday(word: &str) -> Option<Weekday> {
+    let day = match word {
+        "mon" | "monday" => Weekday::Mon,
+        "tue" | "tues" | "tuesday" => Weekday::Tue,
+        "wed" | "wednesday" => Weekday::Wed,
+        "thu" | "thur" | "thurs" | "thursday" => Weekday::Thu,
+        "fri" | "friday" => Weekday::Fri,
+        "sat" | "saturday" => Weekday::Sat,
+        "sun" | "sunday" => Weekday::Sun,
+        _ => return None,
+    };
+    Some(day)
+}
+
+/// Parse a due date relative to `today`: "today", "tomorrow", a weekday
+/// ("fri" is the coming Friday, today included; "next fri" the one after),
+/// "+3d" / "+2w", "2026-10-24" or "10-24". An empty input or "none" clears it.
+pub fn parse_due(input: &str, today: NaiveDate) -> Result<Option<NaiveDate>, String> {
+    let text = input.trim().to_lowercase();
+    let invalid = || format!("Cannot read due date '{}' — try fri, +3d, tomorrow or 2026-10-24", input.trim());
+
+    let date = match text.as_str() {
+        "" | "none" | "-" => return Ok(None),
+        "today" | "tod" => today,
+        "tomorrow" | "tom" | "tmr" => today + Duration::days(1),
+        _ => {
+            if let Some(rel) = text.strip_prefix('+') {
+                let (count, unit) = match rel.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+                    Some((i, _)) => rel.split_at(i),
+                    None => (rel, "d"),
+                };
+                let count: i64 = count.parse().map_err(|_| invalid())?;
+                // Out-of-range counts are rejected rather than overflowing
+                let delta = match unit {
+                    "d" => TimeDelta::try_days(count),
+                    "w" => TimeDelta::try_weeks(count),
+                    _ => None,
+                };
+                delta.and_then(|d| today.checked_add_signed(d)).ok_or_else(invalid)?
+            } else if let Some(day) = parse_weekday(text.trim_start_matches("next ")) {
+                let ahead = (7 + day.num_days_from_monday() as i64 - today.weekday().num_days_from_monday() as i64) % 7;
+                let extra = if text.starts_with("next ") { 7 } else { 0 };
+                today + Duration::days(ahead + extra)
+            } else if let Ok(date) = NaiveDate::parse_from_str(&text, "%Y-%m-%d") {
+                date
+            } else {
+                // "10-24": this year, or next year once the day has passed
+                let date = NaiveDate::parse_from_str(&format!("{}-{}", today.year(), text), "%Y-%m-%d")
+                    .map_err(|_| invalid())?;
+                if date < today {
+                    date.with_year(today.year() + 1).unwrap_or(date)
+                } else {
+                    date
+                }
+            }
+        }
+    };
+    Ok(Some(date))
+}
+
+/// Short due label: "today", "tomorrow", "3d overdue", "Fri" within a week, else "Oct 24".
+pub fn format_due(due: NaiveDate, today: NaiveDate) -> String {
+    let days = (due - today).num_days();
+    match days {
+        0 => "today".to_string(),
+        1 => "tomorrow".to_string(),
+        d if d < 0 => format!("{}d overdue", -d),
+        2..=6 => due.format("%a").to_string(),
+        _ if due.year() == today.year() => due.format("%b %-d").to_string(),
+        _ => due.format("%b %-d %Y").to_string(),
+    }
+}
+
+/// Parse an effort estimate: "30m", "2h", "1.5h", "1d" (a working day of 8h)
+/// or combinations like "1h30m". A bare number means hours. Empty clears it.
+pub fn parse_effort(input: &str) -> Result<Option<u32>, String> {
+    let text = input.trim().to_lowercase().replace(' ', "");
+    if text.is_empty() || text == "none" || text == "-" {
+        return Ok(None);
+    }
+    let invalid = || format!("Cannot read effort '{}' — try 30m, 2h or 1d", input.trim());
+
+    let mut total = 0.0;
+    let mut number = String::new();
+    for c in text.chars() {
+        if c.is_ascii_digit() || c == '.' {
+            number.push(c);
+            continue;
+        }
+        let value: f64 = number.parse().map_err(|_| invalid())?;
+        total += match c {
+            'm' => value,
+            'h' => value * 60.0,
+            'd' => value * DAY_MINUTES as f64,
+            _ => return Err(invalid()),
+        };
+        number.clear();
+    }
+    if !number.is_empty() {
+        total += number.parse::<f64>().map_err(|_| invalid())? * 60.0;
+    }
+    if total <= 0.0 {
+        return Err(invalid());
+    }
+    Ok(Some(total.round() as u32))
+}
+
+/// "45m", "2h", "1h30m", "2d"
+pub fn format_effort(minutes: u32) -> String {
+    if minutes >= DAY_MINUTES && minutes.is_multiple_of(DAY_MINUTES) {
+        return format!("{}d", minutes / DAY_MINUTES);
+    }
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h{}m", h, m),
+    }
+}
+
+/// Start-up reminder listing open tasks that are overdue or due today, if any.
+pub fn agenda_banner(tasks: &[Task], today: NaiveDate) -> Option<String> {
+    let mut due: Vec<&Task> = tasks
+        .iter()
+        .filter(|t| t.is_overdue(today) || t.is_due_today(today))
+        .collect();
+    if due.is_empty() {
+        return None;
+    }
+    due.sort_by_key(|t| (t.due, std::cmp::Reverse(t.priority)));
+
+    let overdue = due.iter().filter(|t| t.is_overdue(today)).count();
+    let mut out = format!(
+        "📅 {} task(s) overdue, {} due today — /plan to review",
+        overdue,
+        due.len() - overdue
+    );
+    for task in due.iter().take(5) {
+        let marker = if task.is_overdue(today) { "⚠" } else { "•" };
+        let when = task.due.map(|d| format_due(d, today)).unwrap_or_default();
+        out.push_str(&format!("\n  {} {} ({})", marker, task.title, when));
+    }
+    if due.len() > 5 {
+        out.push_str(&format!("\n  … and {} more", due.len() - 5));
+    }
+    Some(out)
+}
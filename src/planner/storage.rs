@@ -10,11 +10,45 @@ const GITIGNORE_FILE: &str = ".gitignore";
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TaskList {
     pub tasks: Vec<Task>,
+    #[serde(default)]
+    pub settings: PlannerSettings,
 }
 
 impl TaskList {
     pub fn new() -> Self {
-        Self { tasks: Vec::new() }
+        Self { tasks: Vec::new(), settings: PlannerSettings::default() }
+    }
+}
+
+/// Per-directory planner preferences, stored alongside the tasks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlannerSettings {
+    /// Tasks completed more than this many days ago are archived when the planner opens; 0 never archives
+    #[serde(default = "default_archive_after_days")]
+    pub archive_after_days: u32,
+    /// Complete a task when a commit with a `Closes gl-xxxx` trailer lands on the default branch
+    #[serde(default)]
+    pub close_from_commits: bool,
+    /// Board columns, left to right; the last one holds completed tasks
+    #[serde(default = "default_columns")]
+    pub columns: Vec<String>,
+}
+
+fn default_archive_after_days() -> u32 {
+    14
+}
+
+fn default_columns() -> Vec<String> {
+    ["Backlog", "In progress", "Review", "Done"].iter().map(|c| c.to_string()).collect()
+}
+
+impl Default for PlannerSettings {
+    fn default() -> Self {
+        Self {
+            archive_after_days: default_archive_after_days(),
+            close_from_commits: false,
+            columns: default_columns(),
+        }
     }
 }
 
@@ -48,6 +82,11 @@ pub fn ensure_gitignore() {
     }
 }
 
+/// Whether this directory has a task list, without touching `.gitignore`.
+pub fn tasks_exist() -> bool {
+    PathBuf::from(TASKS_FILE).exists()
+}
+
 pub fn load_tasks() -> TaskList {
     ensure_gitignore();
 
@@ -1,5 +1,85 @@
 use serde::{Deserialize, Serialize};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::fmt;
+
+/// How urgent a task is
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    High,
+    Urgent,
+}
+
+impl Priority {
+    /// The next level, wrapping from Urgent back to Low
+    pub fn next(self) -> Self {
+        match self {
+            Priority::Low => Priority::Normal,
+            Priority::Normal => Priority::High,
+            Priority::High => Priority::Urgent,
+            Priority::Urgent => Priority::Low,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Priority::Low => "Low",
+            Priority::Normal => "Normal",
+            Priority::High => "High",
+            Priority::Urgent => "Urgent",
+        }
+    }
+}
+
+/// GitHub issue a task is kept in sync with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IssueLink {
+    pub owner: String,
+    pub repo: String,
+    pub number: u64,
+    /// When task and issue last matched; changes after this are pushed or pulled
+    pub synced_at: Option<DateTime<Utc>>,
+}
+
+impl IssueLink {
+    pub fn new(owner: &str, repo: &str, number: u64) -> Self {
+        Self { owner: owner.to_string(), repo: repo.to_string(), number, synced_at: None }
+    }
+
+    /// Parse "owner/repo#12"
+    pub fn parse(s: &str) -> Option<Self> {
+        let (slug, number) = s.trim().split_once('#')?;
+        let (owner, repo) = slug.split_once('/')?;
+        if owner.is_empty() || repo.is_empty() {
+            return None;
+        }
+        Some(Self::new(owner, repo, number.parse().ok()?))
+    }
+}
+
+impl fmt::Display for IssueLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}#{}", self.owner, self.repo, self.number)
+    }
+}
+
+/// Commit that mentions a task in its message or was made on a branch named after it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommitLink {
+    /// Name of the repository the commit is in
+    pub repo: String,
+    pub sha: String,
+    /// First line of the commit message
+    pub summary: String,
+    /// Branch named after the task that the commit was found on
+    pub branch: Option<String>,
+    /// Has a `Closes` trailer for the task and is on the default branch
+    #[serde(default)]
+    pub closes: bool,
+    pub committed_at: DateTime<Utc>,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Task {
@@ -11,6 +91,28 @@ pub struct Task {
     pub created_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
     pub updated_at: DateTime<Utc>,
+    #[serde(default)]
+    pub due: Option<NaiveDate>,
+    #[serde(default)]
+    pub priority: Priority,
+    /// Estimated effort in minutes
+    #[serde(default)]
+    pub effort_minutes: Option<u32>,
+    /// Id of the task this is a subtask of
+    #[serde(default)]
+    pub parent: Option<String>,
+    /// Ids of tasks that must be completed before this one
+    #[serde(default)]
+    pub blocked_by: Vec<String>,
+    #[serde(default)]
+    pub issue: Option<IssueLink>,
+    /// Workflow column on the board. None is the first column; completed
+    /// tasks are always in the last one and keep this for when reopened
+    #[serde(default)]
+    pub column: Option<String>,
+    /// Newest first
+    #[serde(default)]
+    pub commits: Vec<CommitLink>,
 }
 
 impl Task {
@@ -25,9 +127,49 @@ impl Task {
             created_at: now,
             completed_at: None,
             updated_at: now,
+            due: None,
+            priority: Priority::Normal,
+            effort_minutes: None,
+            parent: None,
+            blocked_by: Vec::new(),
+            issue: None,
+            column: None,
+            commits: Vec::new(),
         }
     }
 
+    /// The id without its dashes, which task references are prefixes of
+    pub fn ref_hex(&self) -> String {
+        self.id.replace('-', "")
+    }
+
+    /// Reference to put in commit messages and branch names, e.g. "gl-3f2a":
+    /// the shortest prefix of at least four characters no other task in `tasks` shares
+    pub fn short_ref(&self, tasks: &[Task]) -> String {
+        let hex = self.ref_hex();
+        let others: Vec<String> = tasks.iter().filter(|t| t.id != self.id).map(Task::ref_hex).collect();
+        let mut len = 4.min(hex.len());
+        while len < hex.len() && others.iter().any(|o| o.starts_with(&hex[..len])) {
+            len += 1;
+        }
+        format!("gl-{}", &hex[..len])
+    }
+
+    /// Record a commit, or fill in the branch of one already recorded.
+    /// Returns false when nothing changed.
+    pub fn add_commit(&mut self, link: CommitLink) -> bool {
+        if let Some(existing) = self.commits.iter_mut().find(|c| c.repo == link.repo && c.sha == link.sha) {
+            if existing.branch.is_some() || link.branch.is_none() {
+                return false;
+            }
+            existing.branch = link.branch;
+        } else {
+            self.commits.push(link);
+            self.commits.sort_by_key(|c| std::cmp::Reverse(c.committed_at));
+        }
+        true
+    }
+
     pub fn toggle(&mut self) {
         self.completed = !self.completed; // Simple boolean flip
         self.updated_at = Utc::now();
@@ -60,4 +202,71 @@ impl Task {
         self.tags = tags;
         self.updated_at = Utc::now();
     }
-}
\ No newline at end of file
+
+    pub fn set_due(&mut self, due: Option<NaiveDate>) {
+        self.due = due;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn set_priority(&mut self, priority: Priority) {
+        self.priority = priority;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn set_effort(&mut self, effort_minutes: Option<u32>) {
+        self.effort_minutes = effort_minutes;
+        self.updated_at = Utc::now();
+    }
+
+    /// Move to a board column, completing or reopening the task as needed
+    pub fn set_column(&mut self, column: Option<String>, completed: bool) {
+        self.column = column;
+        if self.completed != completed {
+            self.toggle();
+        } else {
+            self.updated_at = Utc::now();
+        }
+    }
+
+    pub fn set_parent(&mut self, parent: Option<String>) {
+        self.parent = parent;
+        self.updated_at = Utc::now();
+    }
+
+    pub fn add_blocker(&mut self, id: String) {
+        if !self.blocked_by.contains(&id) {
+            self.blocked_by.push(id);
+            self.updated_at = Utc::now();
+        }
+    }
+
+    pub fn remove_blocker(&mut self, id: &str) {
+        self.blocked_by.retain(|b| b != id);
+        self.updated_at = Utc::now();
+    }
+
+    /// Still open and due before `today`
+    pub fn is_overdue(&self, today: NaiveDate) -> bool {
+        !self.completed && self.due.is_some_and(|d| d < today)
+    }
+
+    /// Still open and due exactly `today`
+    pub fn is_due_today(&self, today: NaiveDate) -> bool {
+        !self.completed && self.due == Some(today)
+    }
+}
+
+/// The task whose id starts with `prefix`, as shown in the planner's detail panel.
+pub fn find_by_prefix<'a>(tasks: &'a mut [Task], prefix: &str) -> Result<&'a mut Task, String> {
+    let matching: Vec<usize> = tasks
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| !prefix.is_empty() && t.id.starts_with(prefix))
+        .map(|(i, _)| i)
+        .collect();
+    match matching.as_slice() {
+        [i] => Ok(&mut tasks[*i]),
+        [] => Err(format!("No task with id '{}'", prefix)),
+        _ => Err(format!("Task id '{}' is ambiguous; use more characters", prefix)),
+    }
+}
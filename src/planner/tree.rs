@@ -0,0 +1,128 @@
+use std::collections::{HashMap, HashSet};
+
+use super::task::Task;
+
+// Parent id of `task` if that parent is still in the list.
+fn live_parent<'a>(ids: &HashSet<&str>, task: &'a Task) -> Option<&'a str> {
+    task.parent.as_deref().filter(|p| ids.contains(p))
+}
+
+/// Reorder so every subtask directly follows its parent (depth first),
+/// keeping siblings in their current order. Subtasks whose parent is gone
+/// are shown at the top level.
+pub fn arrange(tasks: &mut Vec<Task>) {
+    let order = {
+        let ids: HashSet<&str> = tasks.iter().map(|t| t.id.as_str()).collect();
+        let mut children: HashMap<Option<&str>, Vec<usize>> = HashMap::new();
+        for (i, task) in tasks.iter().enumerate() {
+            children.entry(live_parent(&ids, task)).or_default().push(i);
+        }
+
+        let mut order = Vec::with_capacity(tasks.len());
+        let mut seen = vec![false; tasks.len()];
+        let mut stack: Vec<usize> = children.get(&None).map(|c| c.iter().rev().copied().collect()).unwrap_or_default();
+        while let Some(i) = stack.pop() {
+            if std::mem::replace(&mut seen[i], true) {
+                continue;
+            }
+            order.push(i);
+            if let Some(c) = children.get(&Some(tasks[i].id.as_str())) {
+                stack.extend(c.iter().rev());
+            }
+        }
+        // A parent cycle (only possible from a hand-edited file) has no root; keep those tasks anyway
+        order.extend((0..tasks.len()).filter(|i| !seen[*i]));
+        order
+    };
+
+    let mut slots: Vec<Option<Task>> = std::mem::take(tasks).into_iter().map(Some).collect();
+    tasks.extend(order.into_iter().filter_map(|i| slots[i].take()));
+}
+
+/// Nesting depth of every task, in list order.
+pub fn depths(tasks: &[Task]) -> Vec<usize> {
+    let by_id: HashMap<&str, &Task> = tasks.iter().map(|t| (t.id.as_str(), t)).collect();
+    tasks
+        .iter()
+        .map(|task| {
+            let mut depth = 0;
+            let mut current = task;
+            while let Some(parent) = current.parent.as_deref().and_then(|p| by_id.get(p)) {
+                depth += 1;
+                current = parent;
+                if depth > tasks.len() {
+                    break;
+                }
+            }
+            depth
+        })
+        .collect()
+}
+
+/// Ids of all subtasks below `id`, at any depth.
+pub fn descendant_ids(tasks: &[Task], id: &str) -> Vec<String> {
+    let mut found: Vec<String> = Vec::new();
+    let mut frontier = vec![id.to_string()];
+    while let Some(current) = frontier.pop() {
+        for task in tasks.iter().filter(|t| t.parent.as_deref() == Some(current.as_str())) {
+            if task.id != id && !found.contains(&task.id) {
+                found.push(task.id.clone());
+                frontier.push(task.id.clone());
+            }
+        }
+    }
+    found
+}
+
+/// (completed, total) over all subtasks of `id`, or None if it has none.
+pub fn progress(tasks: &[Task], id: &str) -> Option<(usize, usize)> {
+    let below = descendant_ids(tasks, id);
+    if below.is_empty() {
+        return None;
+    }
+    let done = tasks.iter().filter(|t| t.completed && below.contains(&t.id)).count();
+    Some((done, below.len()))
+}
+
+/// Blockers of `task` that are still open. Blockers that were deleted no longer count.
+pub fn open_blockers<'a>(tasks: &'a [Task], task: &Task) -> Vec<&'a Task> {
+    tasks
+        .iter()
+        .filter(|t| !t.completed && task.blocked_by.contains(&t.id))
+        .collect()
+}
+
+/// Tasks that `id` blocks.
+pub fn blocked_by_this<'a>(tasks: &'a [Task], id: &str) -> Vec<&'a Task> {
+    tasks.iter().filter(|t| t.blocked_by.iter().any(|b| b == id)).collect()
+}
+
+/// Whether `blocker` may block `id`: not itself, and not something that
+/// (directly or indirectly) waits for `id` already.
+pub fn can_block(tasks: &[Task], id: &str, blocker: &str) -> Result<(), String> {
+    if id == blocker {
+        return Err("A task cannot block itself".to_string());
+    }
+    let mut frontier = vec![blocker];
+    let mut seen = HashSet::new();
+    while let Some(current) = frontier.pop() {
+        if current == id {
+            return Err("That would make the two tasks wait for each other".to_string());
+        }
+        if !seen.insert(current) {
+            continue;
+        }
+        if let Some(task) = tasks.iter().find(|t| t.id == current) {
+            frontier.extend(task.blocked_by.iter().map(String::as_str));
+        }
+    }
+    Ok(())
+}
+
+/// Whether `id` may become a subtask of `parent`: not of itself or its own subtasks.
+pub fn can_nest(tasks: &[Task], id: &str, parent: &str) -> Result<(), String> {
+    if id == parent || descendant_ids(tasks, id).iter().any(|d| d == parent) {
+        return Err("A task cannot be nested under its own subtasks".to_string());
+    }
+    Ok(())
+}
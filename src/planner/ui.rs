@@ -1,5 +1,6 @@
 use super::history::{Action, History};
-use super::storage::{load_tasks, save_tasks};
+use super::archive::{due_for_archive, subtree_ids, take_tasks};
+use super::storage::{load_archive, load_tasks, save_archive, save_tasks};
 use super::task::Task;
 use chrono::Local;
 use crossterm::{
@@ -16,7 +17,15 @@ use std::io::{self, Write};
 
 pub fn run_planner() -> Result<(), Box<dyn std::error::Error>> {
     let mut task_list = load_tasks();
+    let mut archive = load_archive();
     let mut history = History::new();
+
+    let stale = due_for_archive(&task_list.tasks, task_list.settings.archive_after_days, chrono::Utc::now());
+    if !stale.is_empty() {
+        archive.tasks.extend(take_tasks(&mut task_list.tasks, &stale).into_iter().map(|(_, t)| t));
+        save_tasks(&task_list)?;
+        save_archive(&archive)?;
+    }
     let mut selected_index = 0;
 
     let mut stdout = io::stdout();
@@ -83,6 +92,23 @@ pub fn run_planner() -> Result<(), Box<dyn std::error::Error>> {
                     }
                 }
 
+                KeyCode::Char('x') if selected_index < task_list.tasks.len() => {
+                    let ids = subtree_ids(&task_list.tasks, &task_list.tasks[selected_index].id);
+                    let taken = take_tasks(&mut task_list.tasks, &ids);
+                    archive.tasks.extend(taken.iter().map(|(_, t)| t.clone()));
+                    history.push(Action::Archive { tasks: taken });
+
+                    if selected_index >= task_list.tasks.len()
+                        && !task_list.tasks.is_empty()
+                    {
+                        selected_index = task_list.tasks.len() - 1;
+                    }
+
+                    save_tasks(&task_list)?;
+                    save_archive(&archive)?;
+                    state_changed = true;
+                }
+
                 KeyCode::Char('a') => {
                     disable_raw_mode()?;
                     execute!(stdout, cursor::Show)?;
@@ -122,24 +148,22 @@ pub fn run_planner() -> Result<(), Box<dyn std::error::Error>> {
                     }
                 }
 
-                KeyCode::Char('u') => {
-                    if history.undo(&mut task_list.tasks) {
-                        save_tasks(&task_list)?;
-                        if selected_index >= task_list.tasks.len()
-                            && !task_list.tasks.is_empty()
-                        {
-                            selected_index =
-                                task_list.tasks.len().saturating_sub(1);
-                        }
-                        state_changed = true;
+                KeyCode::Char('u') if history.undo(&mut task_list.tasks, &mut archive.tasks) => {
+                    save_tasks(&task_list)?;
+                    save_archive(&archive)?;
+                    if selected_index >= task_list.tasks.len()
+                        && !task_list.tasks.is_empty()
+                    {
+                        selected_index =
+                            task_list.tasks.len().saturating_sub(1);
                     }
+                    state_changed = true;
                 }
 
-                KeyCode::Char('r') => {
-                    if history.redo(&mut task_list.tasks) {
-                        save_tasks(&task_list)?;
-                        state_changed = true;
-                    }
+                KeyCode::Char('r') if history.redo(&mut task_list.tasks, &mut archive.tasks) => {
+                    save_tasks(&task_list)?;
+                    save_archive(&archive)?;
+                    state_changed = true;
                 }
 
                 _ => {}
@@ -239,7 +263,7 @@ fn render_tasks(
     output.push_str(&format!("\n{}\n", "─".repeat(width)));
     output.push_str("💡 Controls:\n");
     output.push_str("   ↑/↓: Navigate | Space: Toggle | a: Add | e: Edit | d: Delete\n");
-    output.push_str("   u: Undo | r: Redo | q: Quit\n");
+    output.push_str("   x: Archive | u: Undo | r: Redo | q: Quit\n");
 
     write!(stdout, "{}", output)?;
     Ok(())
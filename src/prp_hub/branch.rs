@@ -0,0 +1,363 @@
+use std::process::Command;
+
+use git2::{build::CheckoutBuilder, BranchType, Repository, RepositoryState, StatusOptions};
+
+use crate::prp_hub::config::load_config;
+use crate::prp_hub::errors::PrpError;
+use crate::prp_hub::types::{RepoOutcomes, RepositoryInfo};
+
+/// Name of the branch HEAD points to, or None when detached / unreadable.
+pub fn current_branch(path: &std::path::Path) -> Option<String> {
+    let repo = Repository::open(path).ok()?;
+    if repo.head_detached().unwrap_or(true) {
+        return None;
+    }
+    let head = repo.head().ok()?;
+    head.shorthand().map(|s| s.to_string())
+}
+
+/// True when the repositories are not all on the same branch.
+pub fn branches_diverge(branches: &[Option<String>]) -> bool {
+    branches.windows(2).any(|w| w[0] != w[1])
+}
+
+/// Whether the local branch `name` has an upstream configured.
+pub fn has_upstream(info: &RepositoryInfo, name: &str) -> bool {
+    Repository::open(&info.path)
+        .ok()
+        .and_then(|r| r.find_branch(name, BranchType::Local).ok().map(|b| b.upstream().is_ok()))
+        .unwrap_or(false)
+}
+
+/// Resolve a repository's default branch: `origin/HEAD` if the remote
+/// advertised one, otherwise the first of `main` / `master` that exists.
+pub fn default_branch(repo: &Repository) -> Option<String> {
+    if let Ok(r) = repo.find_reference("refs/remotes/origin/HEAD") {
+        if let Some(target) = r.symbolic_target() {
+            if let Some(name) = target.strip_prefix("refs/remotes/origin/") {
+                return Some(name.to_string());
+            }
+        }
+    }
+
+    ["main", "master"]
+        .iter()
+        .find(|name| {
+            repo.find_branch(name, BranchType::Local).is_ok()
+                || repo.find_branch(&format!("origin/{}", name), BranchType::Remote).is_ok()
+        })
+        .map(|name| name.to_string())
+}
+
+fn open(info: &RepositoryInfo) -> Result<Repository, PrpError> {
+    Repository::open(&info.path).map_err(|e| PrpError::InvalidState {
+        repo: info.name.clone(),
+        reason: format!("Cannot open repository: {}", e),
+        fix: "Ensure the path is a valid git repository".to_string(),
+    })
+}
+
+/// A repository is clean enough to change branches when no operation is in
+/// progress and no tracked file is modified or staged. Untracked files are
+/// left alone; checkout refuses to overwrite them anyway.
+pub fn ensure_clean(info: &RepositoryInfo) -> Result<(), PrpError> {
+    let repo = open(info)?;
+
+    if repo.state() != RepositoryState::Clean {
+        return Err(PrpError::InvalidState {
+            repo: info.name.clone(),
+            reason: format!("Operation in progress: {:?}", repo.state()),
+            fix: "Finish or abort it first (see `git status`)".to_string(),
+        });
+    }
+
+    let mut opts = StatusOptions::new();
+    opts.include_untracked(false).include_ignored(false);
+    let statuses = repo.statuses(Some(&mut opts)).map_err(|e| PrpError::InvalidState {
+        repo: info.name.clone(),
+        reason: format!("Cannot read status: {}", e),
+        fix: "Run `git status` to inspect".to_string(),
+    })?;
+
+    if !statuses.is_empty() {
+        return Err(PrpError::InvalidState {
+            repo: info.name.clone(),
+            reason: format!("{} uncommitted change(s)", statuses.len()),
+            fix: "Commit or stash your changes first".to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+// Check out an existing local branch, refusing to overwrite anything.
+fn checkout_local(repo: &Repository, info: &RepositoryInfo, name: &str) -> Result<(), PrpError> {
+    let fail = |reason: String| PrpError::InvalidState {
+        repo: info.name.clone(),
+        reason,
+        fix: "Run `git status` to inspect".to_string(),
+    };
+
+    let refname = format!("refs/heads/{}", name);
+    let target = repo
+        .revparse_single(&refname)
+        .map_err(|e| fail(format!("Cannot resolve '{}': {}", name, e)))?;
+
+    repo.checkout_tree(&target, Some(CheckoutBuilder::new().safe()))
+        .map_err(|e| fail(format!("Checkout of '{}' failed: {}", name, e)))?;
+    repo.set_head(&refname)
+        .map_err(|e| fail(format!("Cannot move HEAD to '{}': {}", name, e)))?;
+
+    Ok(())
+}
+
+fn plan_create(info: &RepositoryInfo, name: &str) -> Result<String, PrpError> {
+    ensure_clean(info)?;
+    let repo = open(info)?;
+
+    if repo.find_branch(name, BranchType::Local).is_ok() {
+        return Err(PrpError::InvalidState {
+            repo: info.name.clone(),
+            reason: format!("Branch '{}' already exists", name),
+            fix: format!("Use /prp switch {} instead", name),
+        });
+    }
+
+    default_branch(&repo).ok_or_else(|| PrpError::InvalidState {
+        repo: info.name.clone(),
+        reason: "Cannot determine the default branch".to_string(),
+        fix: "Run `git remote set-head origin --auto`".to_string(),
+    })
+}
+
+fn create_one(info: &RepositoryInfo, name: &str, base: &str) -> Result<(), PrpError> {
+    let repo = open(info)?;
+    let fail = |reason: String| PrpError::InvalidState {
+        repo: info.name.clone(),
+        reason,
+        fix: "Run `git status` to inspect".to_string(),
+    };
+
+    // Prefer the remote copy of the default branch: it is what the feature
+    // will eventually merge into, even when the local one lags behind.
+    let base_commit = repo
+        .revparse_single(&format!("refs/remotes/origin/{}", base))
+        .or_else(|_| repo.revparse_single(&format!("refs/heads/{}", base)))
+        .and_then(|o| o.peel_to_commit())
+        .map_err(|e| fail(format!("Cannot resolve default branch '{}': {}", base, e)))?;
+
+    repo.branch(name, &base_commit, false)
+        .map_err(|e| fail(format!("Cannot create branch '{}': {}", name, e)))?;
+
+    checkout_local(&repo, info, name)
+}
+
+/// Publish a branch and set its upstream (`git push --set-upstream <remote> <name>`),
+/// using the repository's remote override or `origin`.
+pub fn publish_branch(info: &RepositoryInfo, name: &str) -> Result<(), PrpError> {
+    let config = load_config();
+    let remote = config.remote_for(&info.path).0.unwrap_or("origin");
+    let output = Command::new("git")
+        .args(["push", "--set-upstream", remote, name])
+        .current_dir(&info.path)
+        .output()
+        .map_err(|e| PrpError::PushFailed {
+            repo: info.name.clone(),
+            stderr: e.to_string(),
+        })?;
+
+    if !output.status.success() {
+        return Err(PrpError::PushFailed {
+            repo: info.name.clone(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Create `name` from each repository's default branch and check it out.
+/// Every repository is checked first; if any is dirty or already has the
+/// branch, nothing is touched and all problems are returned.
+pub fn create_branch_all(
+    repos: &[RepositoryInfo],
+    name: &str,
+) -> Result<RepoOutcomes, Vec<PrpError>> {
+    let mut bases = Vec::new();
+    let mut errors = Vec::new();
+    for info in repos {
+        match plan_create(info, name) {
+            Ok(base) => bases.push(base),
+            Err(e) => errors.push(e),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(repos
+        .iter()
+        .zip(bases)
+        .map(|(info, base)| {
+            let outcome = create_one(info, name, &base).map(|()| format!("created from {}", base));
+            (info.name.clone(), outcome)
+        })
+        .collect())
+}
+
+fn plan_switch(info: &RepositoryInfo, name: &str) -> Result<(), PrpError> {
+    ensure_clean(info)?;
+    let repo = open(info)?;
+
+    if repo.find_branch(name, BranchType::Local).is_ok()
+        || repo.find_branch(&format!("origin/{}", name), BranchType::Remote).is_ok()
+    {
+        return Ok(());
+    }
+
+    Err(PrpError::InvalidState {
+        repo: info.name.clone(),
+        reason: format!("Branch '{}' does not exist locally or on origin", name),
+        fix: format!("Create it everywhere with /prp branch {}", name),
+    })
+}
+
+fn switch_one(info: &RepositoryInfo, name: &str) -> Result<String, PrpError> {
+    let repo = open(info)?;
+
+    if repo.find_branch(name, BranchType::Local).is_ok() {
+        checkout_local(&repo, info, name)?;
+        return Ok("switched".to_string());
+    }
+
+    // Only on the remote: create a local tracking branch first
+    let fail = |reason: String| PrpError::InvalidState {
+        repo: info.name.clone(),
+        reason,
+        fix: "Run `git fetch` and retry".to_string(),
+    };
+    let remote_name = format!("origin/{}", name);
+    let commit = repo
+        .find_branch(&remote_name, BranchType::Remote)
+        .and_then(|b| b.get().peel_to_commit())
+        .map_err(|e| fail(format!("Cannot resolve '{}': {}", remote_name, e)))?;
+    let mut branch = repo
+        .branch(name, &commit, false)
+        .map_err(|e| fail(format!("Cannot create branch '{}': {}", name, e)))?;
+    branch
+        .set_upstream(Some(&remote_name))
+        .map_err(|e| fail(format!("Cannot set upstream: {}", e)))?;
+
+    checkout_local(&repo, info, name)?;
+    Ok(format!("switched (tracking {})", remote_name))
+}
+
+/// Check out `name` in every repository. Like `create_branch_all`, all
+/// repositories are checked before any of them is switched.
+pub fn switch_branch_all(
+    repos: &[RepositoryInfo],
+    name: &str,
+) -> Result<RepoOutcomes, Vec<PrpError>> {
+    let errors: Vec<PrpError> = repos.iter().filter_map(|r| plan_switch(r, name).err()).collect();
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(repos
+        .iter()
+        .map(|info| (info.name.clone(), switch_one(info, name)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prp_hub::test_support::{bare_remote, clone, commit_file, git, TempDir};
+
+    #[test]
+    fn default_branch_follows_origin_head() {
+        let tmp = TempDir::new();
+        let remote = bare_remote(tmp.path(), "api");
+        let seed = clone(&remote, tmp.path(), "seed");
+        git(&seed.path, &["push", "-q", "origin", "main:develop"]);
+        git(&remote, &["symbolic-ref", "HEAD", "refs/heads/develop"]);
+
+        let api = clone(&remote, tmp.path(), "api");
+
+        assert_eq!(default_branch(&Repository::open(&api.path).unwrap()).as_deref(), Some("develop"));
+    }
+
+    #[test]
+    fn default_branch_falls_back_to_main_or_master() {
+        let tmp = TempDir::new();
+        git(tmp.path(), &["init", "-q", "-b", "master", "legacy"]);
+        commit_file(&tmp.path().join("legacy"), "a.txt", "1");
+        git(tmp.path(), &["init", "-q", "-b", "trunk", "odd"]);
+        commit_file(&tmp.path().join("odd"), "a.txt", "1");
+
+        let legacy = Repository::open(tmp.path().join("legacy")).unwrap();
+        let odd = Repository::open(tmp.path().join("odd")).unwrap();
+        assert_eq!(default_branch(&legacy).as_deref(), Some("master"));
+        assert_eq!(default_branch(&odd), None);
+    }
+
+    #[test]
+    fn create_touches_nothing_when_one_repo_is_dirty() {
+        let tmp = TempDir::new();
+        let repos: Vec<_> = ["api", "web"]
+            .iter()
+            .map(|name| clone(&bare_remote(tmp.path(), name), tmp.path(), name))
+            .collect();
+        std::fs::write(repos[1].path.join("README.md"), "edited").unwrap();
+
+        let errors = create_branch_all(&repos, "feature").unwrap_err();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].repo(), Some("web"));
+        for repo in &repos {
+            assert_eq!(current_branch(&repo.path).as_deref(), Some("main"));
+            assert!(git(&repo.path, &["branch", "--list", "feature"]).is_empty());
+        }
+    }
+
+    #[test]
+    fn create_branches_from_the_remote_default_branch() {
+        let tmp = TempDir::new();
+        let remote = bare_remote(tmp.path(), "api");
+        let api = clone(&remote, tmp.path(), "api");
+        let other = clone(&remote, tmp.path(), "other");
+        commit_file(&other.path, "upstream.txt", "new");
+        git(&other.path, &["push", "-q", "origin", "main"]);
+        git(&api.path, &["fetch", "-q", "origin"]);
+
+        let outcomes = create_branch_all(std::slice::from_ref(&api), "feature").unwrap();
+
+        assert_eq!(outcomes[0].1.as_deref().unwrap(), "created from main");
+        assert_eq!(current_branch(&api.path).as_deref(), Some("feature"));
+        // Local main lags behind; the branch starts from origin/main
+        assert_eq!(git(&api.path, &["rev-parse", "HEAD"]), git(&api.path, &["rev-parse", "origin/main"]));
+        assert!(create_branch_all(std::slice::from_ref(&api), "feature").is_err());
+    }
+
+    #[test]
+    fn switch_creates_a_tracking_branch_from_origin() {
+        let tmp = TempDir::new();
+        let remote = bare_remote(tmp.path(), "api");
+        let api = clone(&remote, tmp.path(), "api");
+        let other = clone(&remote, tmp.path(), "other");
+        git(&other.path, &["checkout", "-q", "-b", "feature"]);
+        commit_file(&other.path, "feature.txt", "work");
+        git(&other.path, &["push", "-q", "origin", "feature"]);
+        git(&api.path, &["fetch", "-q", "origin"]);
+
+        let outcomes = switch_branch_all(std::slice::from_ref(&api), "feature").unwrap();
+
+        assert_eq!(outcomes[0].1.as_deref().unwrap(), "switched (tracking origin/feature)");
+        assert_eq!(current_branch(&api.path).as_deref(), Some("feature"));
+        assert!(has_upstream(&api, "feature"));
+        assert!(api.path.join("feature.txt").exists());
+
+        let missing = switch_branch_all(std::slice::from_ref(&api), "nowhere").unwrap_err();
+        assert!(matches!(&missing[0], PrpError::InvalidState { reason, .. } if reason.contains("does not exist")));
+    }
+}
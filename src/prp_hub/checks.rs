@@ -0,0 +1,137 @@
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+
+use crate::prp_hub::config::{PrpConfig, PreCommitCheck};
+use crate::prp_hub::errors::PrpError;
+use crate::prp_hub::exec::shell;
+use crate::prp_hub::types::RepositoryInfo;
+
+/// Check command that runs the built-in secret scanner
+pub const SECRET_SCAN: &str = "builtin:secrets";
+
+/// Lines of output kept from a failing check
+const OUTPUT_TAIL: usize = 12;
+
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub name: String,
+    pub passed: bool,
+    /// Last lines of output (or the findings), kept for the report
+    pub output: Vec<String>,
+    pub elapsed: Duration,
+}
+
+/// Every check of one repository, in configured order
+#[derive(Debug, Clone)]
+pub struct RepoChecks {
+    pub path: PathBuf,
+    pub name: String,
+    pub results: Vec<CheckResult>,
+}
+
+impl RepoChecks {
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(|r| r.passed)
+    }
+
+    /// One `CheckFailed` per failing check
+    pub fn failures(&self) -> Vec<PrpError> {
+        self.results
+            .iter()
+            .filter(|r| !r.passed)
+            .map(|r| PrpError::CheckFailed {
+                repo: self.name.clone(),
+                check: r.name.clone(),
+                output: if r.output.is_empty() {
+                    "(no output)".to_string()
+                } else {
+                    r.output.join("\n           ")
+                },
+            })
+            .collect()
+    }
+}
+
+fn tail(text: &str) -> Vec<String> {
+    let lines: Vec<String> = text.lines().filter(|l| !l.trim().is_empty()).map(|l| l.to_string()).collect();
+    lines[lines.len().saturating_sub(OUTPUT_TAIL)..].to_vec()
+}
+
+// Secret scan of the work tree; findings already ignored via /scan do not count.
+fn secret_scan(info: &RepositoryInfo) -> (bool, Vec<String>) {
+    let db = crate::scanner::ignore::load_ignore_db();
+    let findings: Vec<String> = crate::scanner::engine::scan_directory(&info.path.to_string_lossy())
+        .into_iter()
+        .filter(|f| !db.ignored.iter().any(|i| i.fingerprint == f.fingerprint))
+        .map(|f| {
+            let file = std::path::Path::new(&f.file)
+                .strip_prefix(&info.path)
+                .map(|p| p.display().to_string())
+                .unwrap_or(f.file);
+            format!("{}:{}  {}", file, f.line, f.secret_type)
+        })
+        .collect();
+    let passed = findings.is_empty();
+    let mut output = findings;
+    if output.len() > OUTPUT_TAIL {
+        let more = output.len() - OUTPUT_TAIL;
+        output.truncate(OUTPUT_TAIL);
+        output.push(format!("… and {} more", more));
+    }
+    (passed, output)
+}
+
+pub fn run_check(info: &RepositoryInfo, check: &PreCommitCheck) -> CheckResult {
+    let start = Instant::now();
+    let (passed, output) = if check.command.trim() == SECRET_SCAN {
+        secret_scan(info)
+    } else {
+        match shell(&check.command)
+            .current_dir(&info.path)
+            .stdin(Stdio::null())
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .output()
+        {
+            Ok(out) => {
+                let mut text = String::from_utf8_lossy(&out.stdout).to_string();
+                text.push('\n');
+                text.push_str(&String::from_utf8_lossy(&out.stderr));
+                (out.status.success(), tail(&text))
+            }
+            Err(e) => (false, vec![format!("Cannot start command: {}", e)]),
+        }
+    };
+    CheckResult {
+        name: check.name.clone(),
+        passed,
+        output,
+        elapsed: start.elapsed(),
+    }
+}
+
+/// Run a repository's configured checks one after another.
+pub fn run_repo_checks(info: &RepositoryInfo, config: &PrpConfig) -> RepoChecks {
+    RepoChecks {
+        path: info.path.clone(),
+        name: info.name.clone(),
+        results: config.checks_for(&info.path).iter().map(|c| run_check(info, c)).collect(),
+    }
+}
+
+/// Run the checks of all `repos`, repositories in parallel. Results keep the input order.
+pub fn run_all_checks(repos: &[RepositoryInfo], config: &PrpConfig) -> Vec<RepoChecks> {
+    repos.par_iter().map(|info| run_repo_checks(info, config)).collect()
+}
+
+/// "✔ fmt (0.4s)" / "✖ test (12.1s)"
+pub fn describe_result(result: &CheckResult) -> String {
+    format!(
+        "{} {} ({:.1}s)",
+        if result.passed { "✔" } else { "✖" },
+        result.name,
+        result.elapsed.as_secs_f32()
+    )
+}
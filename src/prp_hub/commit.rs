@@ -1,14 +1,30 @@
-use git2::{Repository, Signature, StatusOptions};
+use std::path::Path;
 
+use git2::{Repository, StatusOptions};
+
+use crate::prp_hub::branch::current_branch;
+use crate::prp_hub::config::load_config;
 use crate::prp_hub::errors::PrpError;
+use crate::prp_hub::message::trailer_block;
+use crate::prp_hub::signing::{write_commit, WriteCommitError};
+use crate::prp_hub::staging::{stage_selection, RepoSelection};
+use crate::prp_hub::submodule::{stage_gitlinks, SubmoduleLink};
 use crate::prp_hub::types::{CommitSession, RepoCommitResult, RepositoryInfo};
 
-/// Stage all changes and commit in a single repository.
-/// Returns None if there is nothing to commit (clean working tree).
+/// Stage changes and commit in a single repository.
+/// With a `selection`, only the chosen files and hunks are staged; without one,
+/// everything is (`git add .`).
+/// `trailers` are appended after the Group-ID trailer (see `trailer_block`), and
+/// the commit is signed when the repository has `commit.gpgsign` set.
+/// `gitlinks` are submodule paths whose new HEAD is recorded in this commit.
+/// Returns None if there is nothing to commit (clean working tree or empty selection).
 pub fn commit_repo(
     info: &RepositoryInfo,
     message: &str,
     group_id: &str,
+    selection: Option<&RepoSelection>,
+    trailers: &[String],
+    gitlinks: &[&Path],
 ) -> Result<Option<RepoCommitResult>, PrpError> {
     let repo = Repository::open(&info.path).map_err(|e| PrpError::CommitFailed {
         repo: info.name.clone(),
@@ -30,27 +46,60 @@ pub fn commit_repo(
         st != git2::Status::CURRENT && !st.contains(git2::Status::IGNORED)
     });
 
-    if !has_changes {
+    let nothing_selected = selection.map(|s| s.included_count() == 0).unwrap_or(false);
+    if !has_changes || (nothing_selected && gitlinks.is_empty()) {
         return Ok(None);
     }
 
-    // Stage all changes (git add .)
-    let mut index = repo.index().map_err(|e| PrpError::CommitFailed {
-        repo: info.name.clone(),
-        reason: format!("Cannot open index: {}", e),
-    })?;
+    let mut index = match selection {
+        Some(selection) => {
+            // Stage only the chosen paths and hunks
+            stage_selection(&repo, selection).map_err(|e| PrpError::CommitFailed {
+                repo: info.name.clone(),
+                reason: format!("Staging selected changes failed: {}", e),
+            })?;
+            let mut index = repo.index().map_err(|e| PrpError::CommitFailed {
+                repo: info.name.clone(),
+                reason: format!("Cannot open index: {}", e),
+            })?;
+            index.read(false).map_err(|e| PrpError::CommitFailed {
+                repo: info.name.clone(),
+                reason: format!("Cannot reload index: {}", e),
+            })?;
+            index
+        }
+        None => {
+            // Stage all changes (git add .)
+            let mut index = repo.index().map_err(|e| PrpError::CommitFailed {
+                repo: info.name.clone(),
+                reason: format!("Cannot open index: {}", e),
+            })?;
+
+            index
+                .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+                .map_err(|e| PrpError::CommitFailed {
+                    repo: info.name.clone(),
+                    reason: format!("git add failed: {}", e),
+                })?;
+
+            index.write().map_err(|e| PrpError::CommitFailed {
+                repo: info.name.clone(),
+                reason: format!("Cannot write index: {}", e),
+            })?;
+            index
+        }
+    };
 
-    index
-        .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
-        .map_err(|e| PrpError::CommitFailed {
+    if !gitlinks.is_empty() {
+        stage_gitlinks(&repo, gitlinks).map_err(|e| PrpError::CommitFailed {
             repo: info.name.clone(),
-            reason: format!("git add failed: {}", e),
+            reason: format!("Cannot stage submodule pointers: {}", e),
         })?;
-
-    index.write().map_err(|e| PrpError::CommitFailed {
-        repo: info.name.clone(),
-        reason: format!("Cannot write index: {}", e),
-    })?;
+        index.read(false).map_err(|e| PrpError::CommitFailed {
+            repo: info.name.clone(),
+            reason: format!("Cannot reload index: {}", e),
+        })?;
+    }
 
     let tree_oid = index.write_tree().map_err(|e| PrpError::CommitFailed {
         repo: info.name.clone(),
@@ -62,26 +111,39 @@ pub fn commit_repo(
         reason: format!("Cannot find tree: {}", e),
     })?;
 
-    // Build commit message with Group-ID trailer
-    let full_message = format!("{}\n\nGroup-ID: {}", message, group_id);
-
     // Get signature from repo config (falls back to a placeholder)
     let sig = repo.signature().map_err(|e| PrpError::CommitFailed {
         repo: info.name.clone(),
         reason: format!("Cannot read git config signature: {}", e),
     })?;
 
+    // Build commit message with Group-ID and configured trailers
+    let branch = current_branch(&info.path).unwrap_or_else(|| "HEAD".to_string());
+    let trailers = trailer_block(
+        group_id,
+        trailers,
+        sig.name().unwrap_or(""),
+        sig.email().unwrap_or(""),
+        &info.name,
+        &branch,
+    );
+    let full_message = format!("{}\n\n{}", message, trailers);
+
     // Get parent commit (HEAD)
     let parent_commit = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
 
     let parents: Vec<&git2::Commit> = parent_commit.as_ref().map(|c| vec![c]).unwrap_or_default();
 
-    let commit_oid = repo
-        .commit(Some("HEAD"), &sig, &sig, &full_message, &tree, &parents)
-        .map_err(|e| PrpError::CommitFailed {
+    let commit_oid = write_commit(&repo, &sig, &full_message, &tree, &parents).map_err(|e| match e {
+        WriteCommitError::Signing(reason) => PrpError::SigningFailed {
+            repo: info.name.clone(),
+            reason,
+        },
+        WriteCommitError::Git(e) => PrpError::CommitFailed {
             repo: info.name.clone(),
             reason: e.to_string(),
-        })?;
+        },
+    })?;
 
     Ok(Some(RepoCommitResult {
         path: info.path.clone(),
@@ -90,24 +152,55 @@ pub fn commit_repo(
 }
 
 /// Commit across all repositories in a session.
-/// On any failure, stops immediately and returns the error
-/// (caller is responsible for rollback of `session.committed`).
-pub fn commit_all(
+/// `on_repo` is called after each repository with its commit, or `None` if it
+/// had nothing to commit. On any failure, stops immediately and returns the
+/// error (caller is responsible for rollback of `session.committed`).
+pub fn commit_each<F>(
     session: &mut CommitSession,
     message: &str,
-) -> Result<(), PrpError> {
+    mut on_repo: F,
+) -> Result<(), PrpError>
+where
+    F: FnMut(&RepositoryInfo, Option<&RepoCommitResult>),
+{
+    let config = load_config();
     for info in session.repositories.clone().iter() {
-        print!("  📝 {} ... ", info.name);
-
-        match commit_repo(info, message, &session.group_id)? {
-            Some(result) => {
-                println!("✅ committed {}", &result.commit_oid.to_string()[..8]);
-                session.committed.push(result);
-            }
-            None => {
-                println!("⏭  nothing to commit, skipped");
-            }
+        let selection = session.selections.get(&info.path);
+        let message = session.messages.get(&info.path).map(String::as_str).unwrap_or(message);
+        let trailers = config.trailers_for(&info.path);
+
+        // Submodules are committed first, so their new commits are known here
+        let bumps: Vec<SubmoduleLink> = session
+            .submodules
+            .iter()
+            .filter(|l| l.parent == info.path && session.committed.iter().any(|c| c.path == l.child))
+            .cloned()
+            .collect();
+        let gitlinks: Vec<&Path> = bumps.iter().map(|l| l.rel_path.as_path()).collect();
+
+        let result = commit_repo(info, message, &session.group_id, selection, &trailers, &gitlinks)?;
+        on_repo(info, result.as_ref());
+        if let Some(result) = result {
+            session.committed.push(result);
+            session.gitlink_bumps.extend(bumps);
         }
     }
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Commit across all repositories in a session, printing progress.
+/// On any failure, stops immediately and returns the error
+/// (caller is responsible for rollback of `session.committed`).
+pub fn commit_all(
+    session: &mut CommitSession,
+    message: &str,
+) -> Result<(), PrpError> {
+    commit_each(session, message, |info, result| match result {
+        Some(result) => println!(
+            "  📝 {} ... ✅ committed {}",
+            info.name,
+            &result.commit_oid.to_string()[..8]
+        ),
+        None => println!("  📝 {} ... ⏭  nothing to commit, skipped", info.name),
+    })
+}
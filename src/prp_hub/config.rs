@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
@@ -7,6 +8,170 @@ const CONFIG_PATH: &str = ".gitlink/prp_config.json";
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct PrpConfig {
     pub excluded_repos: Vec<PathBuf>,
+    /// Commit message template, e.g. "{message}\n\n{summary}".
+    /// Placeholders: {message}, {repo}, {branch}, {summary}
+    #[serde(default)]
+    pub message_template: Option<String>,
+    #[serde(default)]
+    pub conventional: ConventionalConfig,
+    /// Per-repo template / commit-lint overrides
+    #[serde(default)]
+    pub repo_rules: Vec<RepoMessageRules>,
+    /// Extra trailers written after Group-ID, e.g. "Refs: ENG-123".
+    /// Placeholders: {name}, {email}, {repo}, {branch}
+    #[serde(default)]
+    pub trailers: Vec<String>,
+    /// Add `Signed-off-by` with the committer identity, like `git commit -s`
+    #[serde(default)]
+    pub signoff: bool,
+    /// Keep searching inside discovered repositories for nested ones.
+    /// Submodules are found either way.
+    #[serde(default)]
+    pub nested_repos: bool,
+    /// Repository → repositories it must be committed and pushed after, by
+    /// name or path. Added to the ones inferred from Cargo, npm and Go manifests.
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, Vec<String>>,
+    /// Checks run in every repository before a PRP session commits
+    #[serde(default)]
+    pub checks: Vec<PreCommitCheck>,
+    /// Fetch and update repositories before a session commits
+    #[serde(default)]
+    pub pull: PullConfig,
+}
+
+/// Settings for `/prp pull` and the pull run before sessions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PullConfig {
+    /// Pull every repository before a PRP session commits
+    pub before_commit: bool,
+    /// Rebase local commits onto the upstream instead of refusing diverged branches
+    pub rebase: bool,
+    /// Stash uncommitted changes around the update and restore them afterwards
+    pub autostash: bool,
+}
+
+impl Default for PullConfig {
+    fn default() -> Self {
+        Self {
+            before_commit: false,
+            rebase: false,
+            autostash: true,
+        }
+    }
+}
+
+/// A command that must succeed before a repository is committed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreCommitCheck {
+    pub name: String,
+    /// Run through the shell in the repository root; exit 0 passes.
+    /// `builtin:secrets` runs the secret scanner instead.
+    pub command: String,
+}
+
+/// Conventional Commits rules checked before any PRP commit is written
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConventionalConfig {
+    pub enabled: bool,
+    pub types: Vec<String>,
+    pub require_scope: bool,
+    /// Allowed scopes; empty means any scope
+    pub scopes: Vec<String>,
+    /// A `!` header must be explained by a `BREAKING CHANGE:` footer
+    pub require_breaking_footer: bool,
+}
+
+impl Default for ConventionalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            types: [
+                "feat", "fix", "docs", "style", "refactor", "perf", "test", "build", "ci", "chore", "revert",
+            ]
+            .iter()
+            .map(|t| t.to_string())
+            .collect(),
+            require_scope: false,
+            scopes: Vec::new(),
+            require_breaking_footer: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoMessageRules {
+    pub path: PathBuf,
+    #[serde(default)]
+    pub template: Option<String>,
+    #[serde(default)]
+    pub conventional: Option<ConventionalConfig>,
+    /// Added to the global trailers for this repository only
+    #[serde(default)]
+    pub trailers: Vec<String>,
+    /// Added to the global checks for this repository only
+    #[serde(default)]
+    pub checks: Vec<PreCommitCheck>,
+    /// Remote to push to and compare against, instead of the branch's upstream
+    #[serde(default)]
+    pub remote: Option<String>,
+    /// Push refspec, e.g. "{branch}:refs/heads/release/{branch}".
+    /// Placeholder: {branch} (the local branch)
+    #[serde(default)]
+    pub refspec: Option<String>,
+}
+
+impl PrpConfig {
+    /// Template for a repository: its own override, else the global one.
+    pub fn template_for(&self, path: &Path) -> Option<&str> {
+        self.repo_rules
+            .iter()
+            .find(|r| r.path == path)
+            .and_then(|r| r.template.as_deref())
+            .or(self.message_template.as_deref())
+    }
+
+    /// Commit-lint rules for a repository: its own override, else the global ones.
+    pub fn conventional_for(&self, path: &Path) -> &ConventionalConfig {
+        self.repo_rules
+            .iter()
+            .find(|r| r.path == path)
+            .and_then(|r| r.conventional.as_ref())
+            .unwrap_or(&self.conventional)
+    }
+
+    /// Trailer templates for a repository: `Signed-off-by` (if enabled), the
+    /// global trailers, then the repository's own.
+    pub fn trailers_for(&self, path: &Path) -> Vec<String> {
+        let mut trailers = Vec::new();
+        if self.signoff {
+            trailers.push("Signed-off-by: {name} <{email}>".to_string());
+        }
+        trailers.extend(self.trailers.iter().cloned());
+        if let Some(rules) = self.repo_rules.iter().find(|r| r.path == path) {
+            trailers.extend(rules.trailers.iter().cloned());
+        }
+        trailers
+    }
+
+    /// Per-repo remote and refspec overrides, either of which may be unset.
+    pub fn remote_for(&self, path: &Path) -> (Option<&str>, Option<&str>) {
+        match self.repo_rules.iter().find(|r| r.path == path) {
+            Some(rules) => (rules.remote.as_deref(), rules.refspec.as_deref()),
+            None => (None, None),
+        }
+    }
+
+    /// Pre-commit checks for a repository: the global ones, then its own.
+    pub fn checks_for(&self, path: &Path) -> Vec<PreCommitCheck> {
+        let mut checks = self.checks.clone();
+        if let Some(rules) = self.repo_rules.iter().find(|r| r.path == path) {
+            checks.extend(rules.checks.iter().cloned());
+        }
+        checks
+    }
 }
 
 fn config_path() -> PathBuf {
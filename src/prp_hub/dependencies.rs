@@ -0,0 +1,450 @@
+use std::path::{Path, PathBuf};
+
+use git2::Repository;
+use regex::Regex;
+use walkdir::WalkDir;
+
+use crate::prp_hub::config::PrpConfig;
+use crate::prp_hub::errors::PrpError;
+use crate::prp_hub::submodule::submodule_links;
+use crate::prp_hub::types::RepositoryInfo;
+use crate::scanner::filters::IGNORED_DIRS;
+
+// How deep below a repository root manifests are looked for (workspace members)
+const MANIFEST_DEPTH: usize = 3;
+
+/// `repo` must be committed and pushed after `depends_on`
+#[derive(Debug, Clone)]
+pub struct Dependency {
+    pub repo: PathBuf,
+    pub depends_on: PathBuf,
+    /// Where the dependency was found, e.g. "Cargo.toml path"
+    pub reason: String,
+}
+
+/// Session repositories in dependency order, with the edges that produced it
+#[derive(Debug, Clone)]
+pub struct DependencyPlan {
+    pub order: Vec<RepositoryInfo>,
+    pub dependencies: Vec<Dependency>,
+}
+
+impl DependencyPlan {
+    /// "a → b → c"
+    pub fn order_line(&self) -> String {
+        self.order.iter().map(|r| r.name.as_str()).collect::<Vec<_>>().join(" → ")
+    }
+
+    /// One line per dependency: "api needs shared (Cargo.toml path)".
+    pub fn describe(&self) -> Vec<String> {
+        self.dependencies
+            .iter()
+            .map(|d| {
+                format!(
+                    "{} needs {} ({})",
+                    name_of(&self.order, &d.repo),
+                    name_of(&self.order, &d.depends_on),
+                    d.reason
+                )
+            })
+            .collect()
+    }
+}
+
+fn name_of(repos: &[RepositoryInfo], path: &Path) -> String {
+    repos
+        .iter()
+        .find(|r| r.path == path)
+        .map(|r| r.name.clone())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+/// Reduce a remote URL to "host/owner/repo" so https, ssh and scp-style
+/// forms of the same repository compare equal.
+fn normalize_url(url: &str) -> String {
+    let mut u = url.trim().to_lowercase();
+    u = u.split('#').next().unwrap_or("").to_string();
+    if let Some(rest) = u.strip_prefix("github:") {
+        u = format!("github.com/{}", rest);
+    }
+    u = u.trim_start_matches("git+").to_string();
+    if let Some(i) = u.find("://") {
+        u = u[i + 3..].to_string();
+    }
+    if let Some(i) = u.find('@') {
+        u = u[i + 1..].to_string();
+    }
+    // scp-style "host:owner/repo"
+    if let Some(i) = u.find(':') {
+        if !u[..i].contains('/') {
+            u.replace_range(i..i + 1, "/");
+        }
+    }
+    u.trim_end_matches('/').trim_end_matches(".git").to_string()
+}
+
+fn origin_url(path: &Path) -> Option<String> {
+    let repo = Repository::open(path).ok()?;
+    let remote = repo.find_remote("origin").ok()?;
+    remote.url().map(normalize_url)
+}
+
+/// A reference found in one repository's manifests
+enum Reference {
+    Path(PathBuf),
+    Url(String),
+    GoModule(String),
+}
+
+fn manifests(repo: &Path) -> Vec<PathBuf> {
+    WalkDir::new(repo)
+        .max_depth(MANIFEST_DEPTH)
+        .into_iter()
+        .filter_entry(|e| {
+            let name = e.file_name().to_string_lossy();
+            e.depth() == 0 || !IGNORED_DIRS.iter().any(|d| *d == name)
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            e.file_type().is_file()
+                && matches!(e.file_name().to_str(), Some("Cargo.toml" | "package.json" | "go.mod"))
+        })
+        .map(|e| e.into_path())
+        .collect()
+}
+
+// `path = ".."` and `git = ".."` in dependency and patch tables.
+fn cargo_references(manifest: &Path, raw: &str) -> Vec<(Reference, &'static str)> {
+    let path_re = Regex::new(r#"(?:^|[\s{,])path\s*=\s*"([^"]+)""#).expect("valid regex");
+    let git_re = Regex::new(r#"(?:^|[\s{,])git\s*=\s*"([^"]+)""#).expect("valid regex");
+    let dir = manifest.parent().unwrap_or(Path::new("."));
+
+    let mut refs = Vec::new();
+    let mut in_deps = false;
+    for line in raw.lines() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.starts_with('[') {
+            let header = line.trim_matches(|c| c == '[' || c == ']');
+            in_deps = header.contains("dependencies") || header.starts_with("patch.");
+            continue;
+        }
+        if !in_deps {
+            continue;
+        }
+        if let Some(c) = path_re.captures(line) {
+            refs.push((Reference::Path(dir.join(&c[1])), "Cargo.toml path"));
+        }
+        if let Some(c) = git_re.captures(line) {
+            refs.push((Reference::Url(c[1].to_string()), "Cargo.toml git"));
+        }
+    }
+    refs
+}
+
+// `file:`/`link:` paths and git URLs (including `owner/repo` shorthand) in any dependency map.
+fn npm_references(manifest: &Path, raw: &str) -> Vec<(Reference, &'static str)> {
+    let json: serde_json::Value = match serde_json::from_str(raw) {
+        Ok(v) => v,
+        Err(_) => return Vec::new(),
+    };
+    let shorthand = Regex::new(r"^[\w.-]+/[\w.-]+(#.*)?$").expect("valid regex");
+    let dir = manifest.parent().unwrap_or(Path::new("."));
+
+    let mut refs = Vec::new();
+    for table in ["dependencies", "devDependencies", "peerDependencies", "optionalDependencies"] {
+        let deps = match json.get(table).and_then(|d| d.as_object()) {
+            Some(d) => d,
+            None => continue,
+        };
+        for spec in deps.values().filter_map(|v| v.as_str()) {
+            if let Some(p) = ["file:", "link:", "portal:"].iter().find_map(|pre| spec.strip_prefix(pre)) {
+                refs.push((Reference::Path(dir.join(p)), "package.json path"));
+            } else if spec.starts_with("git") || spec.contains("://") || spec.starts_with("github:") {
+                refs.push((Reference::Url(spec.to_string()), "package.json git"));
+            } else if shorthand.is_match(spec) {
+                refs.push((Reference::Url(format!("github:{}", spec)), "package.json git"));
+            }
+        }
+    }
+    refs
+}
+
+// `require` module paths and `replace ... => ../path` targets.
+fn go_references(manifest: &Path, raw: &str) -> Vec<(Reference, &'static str)> {
+    let dir = manifest.parent().unwrap_or(Path::new("."));
+    let mut refs = Vec::new();
+    let mut block: Option<&str> = None;
+
+    for line in raw.lines() {
+        let line = line.split("//").next().unwrap_or("").trim();
+        if line == ")" {
+            block = None;
+            continue;
+        }
+        let (kind, body) = match block {
+            Some(kind) => (kind, line),
+            None => match line.split_once(char::is_whitespace) {
+                Some((kind @ ("require" | "replace"), rest)) if rest.trim() == "(" => {
+                    block = Some(kind);
+                    continue;
+                }
+                Some((kind @ ("require" | "replace"), rest)) => (kind, rest.trim()),
+                _ => continue,
+            },
+        };
+
+        if kind == "require" {
+            if let Some(module) = body.split_whitespace().next() {
+                refs.push((Reference::GoModule(module.to_string()), "go.mod require"));
+            }
+        } else if let Some((_, target)) = body.split_once("=>") {
+            let target = target.split_whitespace().next().unwrap_or("");
+            if target.starts_with('.') || target.starts_with('/') {
+                refs.push((Reference::Path(dir.join(target)), "go.mod replace"));
+            }
+        }
+    }
+    refs
+}
+
+fn go_module_of(repo: &Path) -> Option<String> {
+    let raw = std::fs::read_to_string(repo.join("go.mod")).ok()?;
+    raw.lines()
+        .find_map(|l| l.trim().strip_prefix("module "))
+        .map(|m| m.trim().to_string())
+}
+
+/// Dependencies between `repos`: declared in the PRP config, inferred from
+/// Cargo, npm and Go manifests, and submodule relationships.
+pub fn find_dependencies(repos: &[RepositoryInfo], config: &PrpConfig) -> Vec<Dependency> {
+    let urls: Vec<Option<String>> = repos.iter().map(|r| origin_url(&r.path)).collect();
+    let modules: Vec<Option<String>> = repos.iter().map(|r| go_module_of(&r.path)).collect();
+
+    // Repository containing a path: the deepest one, so submodules win over parents
+    let containing = |path: &Path| -> Option<usize> {
+        let path = std::fs::canonicalize(path).ok()?;
+        repos
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| path.starts_with(&r.path))
+            .max_by_key(|(_, r)| r.path.components().count())
+            .map(|(i, _)| i)
+    };
+    let by_name = |key: &str| -> Option<usize> {
+        repos.iter().position(|r| r.name == key || r.path == Path::new(key))
+    };
+
+    let mut deps: Vec<Dependency> = Vec::new();
+    let mut add = |repo: usize, dep: usize, reason: &str| {
+        if repo != dep && !deps.iter().any(|d| d.repo == repos[repo].path && d.depends_on == repos[dep].path) {
+            deps.push(Dependency {
+                repo: repos[repo].path.clone(),
+                depends_on: repos[dep].path.clone(),
+                reason: reason.to_string(),
+            });
+        }
+    };
+
+    for (repo, needs) in &config.dependencies {
+        if let Some(r) = by_name(repo) {
+            for dep in needs.iter().filter_map(|n| by_name(n)) {
+                add(r, dep, "declared");
+            }
+        }
+    }
+
+    for link in submodule_links(repos) {
+        let parent = repos.iter().position(|r| r.path == link.parent);
+        let child = repos.iter().position(|r| r.path == link.child);
+        if let (Some(p), Some(c)) = (parent, child) {
+            add(p, c, "submodule");
+        }
+    }
+
+    for (i, info) in repos.iter().enumerate() {
+        for manifest in manifests(&info.path) {
+            let raw = match std::fs::read_to_string(&manifest) {
+                Ok(r) => r,
+                Err(_) => continue,
+            };
+            let refs = match manifest.file_name().and_then(|n| n.to_str()) {
+                Some("Cargo.toml") => cargo_references(&manifest, &raw),
+                Some("package.json") => npm_references(&manifest, &raw),
+                Some("go.mod") => go_references(&manifest, &raw),
+                _ => continue,
+            };
+
+            for (reference, reason) in refs {
+                let target = match reference {
+                    Reference::Path(p) => containing(&p),
+                    Reference::Url(u) => {
+                        let u = normalize_url(&u);
+                        urls.iter().position(|o| o.as_deref() == Some(u.as_str()))
+                    }
+                    Reference::GoModule(m) => modules
+                        .iter()
+                        .position(|own| own.as_deref().map(|own| m == own || m.starts_with(&format!("{}/", own))).unwrap_or(false))
+                        .or_else(|| urls.iter().position(|o| o.as_deref() == Some(m.to_lowercase().as_str()))),
+                };
+                if let Some(dep) = target {
+                    add(i, dep, reason);
+                }
+            }
+        }
+    }
+
+    deps
+}
+
+// A cycle among `remaining` (indices still blocked after the sort), as names.
+fn find_cycle(repos: &[RepositoryInfo], deps: &[Dependency], remaining: &[usize]) -> Vec<String> {
+    let index = |p: &Path| repos.iter().position(|r| r.path == p);
+    let mut path: Vec<usize> = vec![remaining[0]];
+    loop {
+        let current = *path.last().unwrap_or(&remaining[0]);
+        let next = deps
+            .iter()
+            .filter(|d| d.repo == repos[current].path)
+            .filter_map(|d| index(&d.depends_on))
+            .find(|i| remaining.contains(i));
+        let next = match next {
+            Some(n) => n,
+            None => return path.iter().map(|&i| repos[i].name.clone()).collect(),
+        };
+        if let Some(start) = path.iter().position(|&i| i == next) {
+            let mut cycle: Vec<String> = path[start..].iter().map(|&i| repos[i].name.clone()).collect();
+            cycle.push(repos[next].name.clone());
+            return cycle;
+        }
+        path.push(next);
+    }
+}
+
+/// Topological order of `repos`: every repository comes after the ones it
+/// depends on; otherwise the original order is kept. Fails on a cycle.
+pub fn dependency_order(repos: &[RepositoryInfo], deps: &[Dependency]) -> Result<Vec<RepositoryInfo>, PrpError> {
+    let mut placed = vec![false; repos.len()];
+    let mut order: Vec<RepositoryInfo> = Vec::with_capacity(repos.len());
+
+    while order.len() < repos.len() {
+        let ready = (0..repos.len()).find(|&i| {
+            !placed[i]
+                && deps
+                    .iter()
+                    .filter(|d| d.repo == repos[i].path)
+                    .all(|d| repos.iter().position(|r| r.path == d.depends_on).map(|j| placed[j]).unwrap_or(true))
+        });
+        match ready {
+            Some(i) => {
+                placed[i] = true;
+                order.push(repos[i].clone());
+            }
+            None => {
+                let remaining: Vec<usize> = (0..repos.len()).filter(|&i| !placed[i]).collect();
+                return Err(PrpError::DependencyCycle(find_cycle(repos, deps, &remaining)));
+            }
+        }
+    }
+
+    Ok(order)
+}
+
+/// Find the dependencies between `repos` and put them in commit/push order.
+pub fn plan_order(repos: &[RepositoryInfo], config: &PrpConfig) -> Result<DependencyPlan, PrpError> {
+    let dependencies = find_dependencies(repos, config);
+    let order = dependency_order(repos, &dependencies)?;
+    Ok(DependencyPlan { order, dependencies })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prp_hub::test_support::TempDir;
+
+    fn repo(root: &Path, name: &str, manifest: Option<(&str, &str)>) -> RepositoryInfo {
+        let path = root.join(name);
+        std::fs::create_dir_all(&path).unwrap();
+        if let Some((file, content)) = manifest {
+            std::fs::write(path.join(file), content).unwrap();
+        }
+        RepositoryInfo { name: name.to_string(), path }
+    }
+
+    fn names(repos: &[RepositoryInfo]) -> Vec<&str> {
+        repos.iter().map(|r| r.name.as_str()).collect()
+    }
+
+    #[test]
+    fn linear_chain_is_ordered_dependencies_first() {
+        let root = TempDir::new();
+        let app = repo(root.path(), "app", Some(("Cargo.toml", "[dependencies]\napi = { path = \"../api\" }\n")));
+        let api = repo(root.path(), "api", Some(("Cargo.toml", "[dependencies]\ncore = { path = \"../core\" }\n")));
+        let core = repo(root.path(), "core", None);
+
+        let plan = plan_order(&[app, api, core], &PrpConfig::default()).unwrap();
+
+        assert_eq!(names(&plan.order), ["core", "api", "app"]);
+        assert_eq!(plan.dependencies.len(), 2);
+        assert!(plan.dependencies.iter().all(|d| d.reason == "Cargo.toml path"));
+    }
+
+    #[test]
+    fn cycle_is_reported_by_name() {
+        let root = TempDir::new();
+        let repos = [repo(root.path(), "a", None), repo(root.path(), "b", None), repo(root.path(), "c", None)];
+        let mut config = PrpConfig::default();
+        config.dependencies.insert("a".into(), vec!["b".into()]);
+        config.dependencies.insert("b".into(), vec!["a".into()]);
+
+        match plan_order(&repos, &config) {
+            Err(PrpError::DependencyCycle(cycle)) => assert_eq!(cycle, ["a", "b", "a"]),
+            Err(e) => panic!("expected a dependency cycle, got {}", e),
+            Ok(plan) => panic!("expected a dependency cycle, got {:?}", names(&plan.order)),
+        }
+    }
+
+    #[test]
+    fn cargo_paths_and_git_urls_in_dependency_tables() {
+        let raw = r#"
+[package]
+name = "app"
+path = "ignored"
+
+[dependencies]
+api = { path = "../api" }
+core = { git = "https://github.com/acme/core" } # pinned
+
+[patch.crates-io]
+serde = { path = "../serde" }
+"#;
+        let refs = cargo_references(Path::new("/src/app/Cargo.toml"), raw);
+
+        assert_eq!(refs.len(), 3);
+        assert!(matches!(&refs[0], (Reference::Path(p), "Cargo.toml path") if p == Path::new("/src/app/../api")));
+        assert!(matches!(&refs[1], (Reference::Url(u), "Cargo.toml git") if u == "https://github.com/acme/core"));
+        assert!(matches!(&refs[2], (Reference::Path(p), "Cargo.toml path") if p == Path::new("/src/app/../serde")));
+    }
+
+    #[test]
+    fn npm_file_links_and_git_specs() {
+        let raw = r#"{
+  "dependencies": { "ui": "file:../ui", "left-pad": "^1.3.0" },
+  "devDependencies": { "tools": "acme/tools#main" }
+}"#;
+        let refs = npm_references(Path::new("/src/web/package.json"), raw);
+
+        assert_eq!(refs.len(), 2);
+        assert!(matches!(&refs[0], (Reference::Path(p), "package.json path") if p == Path::new("/src/web/../ui")));
+        assert!(matches!(&refs[1], (Reference::Url(u), "package.json git") if u == "github:acme/tools#main"));
+    }
+
+    #[test]
+    fn go_requires_and_local_replaces() {
+        let raw = "module example.com/app\n\nrequire (\n\texample.com/lib v1.2.0 // indirect\n)\n\nreplace example.com/lib => ../lib\n";
+        let refs = go_references(Path::new("/src/app/go.mod"), raw);
+
+        assert_eq!(refs.len(), 2);
+        assert!(matches!(&refs[0], (Reference::GoModule(m), "go.mod require") if m == "example.com/lib"));
+        assert!(matches!(&refs[1], (Reference::Path(p), "go.mod replace") if p == Path::new("/src/app/../lib")));
+    }
+}
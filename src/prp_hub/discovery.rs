@@ -1,55 +1,237 @@
-use std::path::PathBuf;
 use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
-use walkdir::WalkDir;
+use git2::Repository;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 
+use crate::prp_hub::config::{is_excluded, load_config};
 use crate::prp_hub::errors::PrpError;
+use crate::prp_hub::manifest::{find_manifest, load_manifest, manifest_repositories};
 use crate::prp_hub::types::RepositoryInfo;
+use crate::prp_hub::workspace::workspace_repositories;
+use crate::scanner::filters::IGNORED_DIRS;
 
-/// Recursively discover all git repositories under `root`, including root itself.
-/// Nested repositories are treated independently.
+/// Repositories under `root`. When `root` has a workspace manifest, its
+/// cloned repositories are used; otherwise the directory tree is walked.
 pub fn discover_repositories(root: &str) -> Result<Vec<RepositoryInfo>, PrpError> {
     let root_path = std::fs::canonicalize(root)
         .map_err(|e| PrpError::DiscoveryError(e.to_string()))?;
 
-    let mut seen: HashSet<PathBuf> = HashSet::new();
-    let mut repos: Vec<RepositoryInfo> = Vec::new();
+    match find_manifest(&root_path) {
+        Some(path) => manifest_repositories(&load_manifest(&path)?),
+        None => walk_repositories(root),
+    }
+}
 
-    // WalkDir will traverse all subdirectories; we look for .git entries.
-    // When we find a `.git` directory, its parent is a repo root.
-    for entry in WalkDir::new(&root_path)
-        .follow_links(false)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        let path = entry.path();
-
-        // We're looking for a `.git` file or directory
-        if path.file_name().map(|n| n == ".git").unwrap_or(false) {
-            if let Some(repo_root) = path.parent() {
-                let canonical = match std::fs::canonicalize(repo_root) {
-                    Ok(p) => p,
-                    Err(_) => continue,
-                };
-
-                if seen.contains(&canonical) {
-                    continue;
-                }
-                seen.insert(canonical.clone());
-
-                let name = canonical
-                    .file_name()
-                    .map(|n| n.to_string_lossy().into_owned())
-                    .unwrap_or_else(|| canonical.to_string_lossy().into_owned());
-
-                repos.push(RepositoryInfo {
-                    name,
-                    path: canonical,
-                });
-            }
+/// How a directory holds a repository
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RepoLayout {
+    /// `.git` directory
+    WorkTree,
+    /// `.git` file pointing elsewhere: a linked worktree or a submodule checkout
+    Linked,
+    /// No work tree; the directory is the git directory itself
+    Bare,
+}
+
+fn repo_layout(dir: &Path) -> Option<RepoLayout> {
+    let dot_git = dir.join(".git");
+    match std::fs::symlink_metadata(&dot_git) {
+        Ok(m) if m.is_dir() => return Some(RepoLayout::WorkTree),
+        Ok(m) if m.is_file() => {
+            let pointer = std::fs::read_to_string(&dot_git).unwrap_or_default();
+            return pointer.starts_with("gitdir:").then_some(RepoLayout::Linked);
+        }
+        _ => {}
+    }
+
+    let bare = dir.join("HEAD").is_file() && dir.join("objects").is_dir() && dir.join("refs").is_dir();
+    bare.then_some(RepoLayout::Bare)
+}
+
+/// Repositories and the directories that were read to find them
+#[derive(Debug, Default)]
+struct Scan {
+    repos: Vec<PathBuf>,
+    dirs: Vec<(PathBuf, SystemTime)>,
+}
+
+impl Scan {
+    fn merge(mut self, other: Scan) -> Scan {
+        self.repos.extend(other.repos);
+        self.dirs.extend(other.dirs);
+        self
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+// Checked-out submodules of a repository, recursively. Their directories are
+// recorded even when empty so that a later `git submodule update` is noticed.
+fn scan_submodules(repo: &Path, scan: &mut Scan) {
+    let paths: Vec<PathBuf> = match Repository::open(repo) {
+        Ok(r) => r
+            .submodules()
+            .map(|subs| subs.iter().map(|s| repo.join(s.path())).collect())
+            .unwrap_or_default(),
+        Err(_) => return,
+    };
+    for path in paths {
+        if let Some(time) = modified(&path) {
+            scan.dirs.push((path.clone(), time));
+        }
+        if matches!(repo_layout(&path), Some(RepoLayout::Linked | RepoLayout::WorkTree)) {
+            scan.repos.push(path.clone());
+            scan_submodules(&path, scan);
+        }
+    }
+}
+
+// Search one directory and, in parallel, its subdirectories. Descent stops at
+// a repository (below the root) unless `nested` is set, and always at a bare
+// one. Bare repositories have no work tree to commit from, so they are not listed.
+fn scan_dir(dir: &Path, is_root: bool, nested: bool) -> Scan {
+    let mut scan = Scan::default();
+    if let Some(time) = modified(dir) {
+        scan.dirs.push((dir.to_path_buf(), time));
+    }
+
+    let layout = repo_layout(dir);
+    if let Some(layout) = layout {
+        if layout == RepoLayout::Bare {
+            return scan;
+        }
+        scan.repos.push(dir.to_path_buf());
+        scan_submodules(dir, &mut scan);
+        if !nested && !is_root {
+            return scan;
         }
     }
 
+    let subdirs: Vec<PathBuf> = match std::fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            // file_type() does not follow symlinks, so linked directories are skipped
+            .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
+            .filter(|e| {
+                let name = e.file_name();
+                let name = name.to_string_lossy();
+                !IGNORED_DIRS.iter().any(|d| *d == name)
+            })
+            .map(|e| e.path())
+            .collect(),
+        Err(_) => return scan,
+    };
+
+    subdirs
+        .par_iter()
+        .map(|d| scan_dir(d, false, nested))
+        .reduce(Scan::default, Scan::merge)
+        .merge(scan)
+}
+
+const DISCOVERY_CACHE_FILE: &str = "discovery.json";
+// Roots remembered at once; the oldest is dropped first
+const DISCOVERY_CACHE_ROOTS: usize = 20;
+
+/// Result of an earlier walk, valid while none of the directories it read has changed
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedScan {
+    root: PathBuf,
+    nested: bool,
+    repos: Vec<PathBuf>,
+    dirs: Vec<(PathBuf, SystemTime)>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DiscoveryCache {
+    scans: Vec<CachedScan>,
+}
+
+fn discovery_cache_path() -> Option<PathBuf> {
+    // Tests walk throwaway directories; keep them out of the user's cache
+    if cfg!(test) {
+        return None;
+    }
+    dirs::cache_dir().map(|d| d.join("gitlink").join(DISCOVERY_CACHE_FILE))
+}
+
+fn load_discovery_cache() -> DiscoveryCache {
+    discovery_cache_path()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save_discovery_cache(cache: &DiscoveryCache) {
+    let path = match discovery_cache_path() {
+        Some(p) => p,
+        None => return,
+    };
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Ok(json) = serde_json::to_string(cache) {
+        let _ = std::fs::write(&path, json);
+    }
+}
+
+// A directory gains or loses entries → its mtime changes → the walk is redone.
+fn cache_is_fresh(scan: &CachedScan) -> bool {
+    scan.dirs
+        .par_iter()
+        .all(|(dir, time)| modified(dir).as_ref() == Some(time))
+}
+
+/// Find all git repositories under `root`, including root itself: work trees,
+/// linked worktrees and submodules. Bare repositories are left out. Build and tool
+/// directories (the scanner's ignore list) are skipped, and the search does not
+/// look inside a repository unless `nested_repos` is set in the PRP config.
+/// Results are cached per root and reused until a directory they came from changes.
+pub fn walk_repositories(root: &str) -> Result<Vec<RepositoryInfo>, PrpError> {
+    let root_path = std::fs::canonicalize(root)
+        .map_err(|e| PrpError::DiscoveryError(e.to_string()))?;
+    let nested = load_config().nested_repos;
+
+    let mut cache = load_discovery_cache();
+    let cached = cache
+        .scans
+        .iter()
+        .find(|s| s.root == root_path && s.nested == nested)
+        .filter(|s| cache_is_fresh(s))
+        .map(|s| s.repos.clone());
+
+    let found = match cached {
+        Some(repos) => repos,
+        None => {
+            let scan = scan_dir(&root_path, true, nested);
+            cache.scans.retain(|s| !(s.root == root_path && s.nested == nested));
+            cache.scans.push(CachedScan {
+                root: root_path.clone(),
+                nested,
+                repos: scan.repos.clone(),
+                dirs: scan.dirs,
+            });
+            if cache.scans.len() > DISCOVERY_CACHE_ROOTS {
+                cache.scans.remove(0);
+            }
+            save_discovery_cache(&cache);
+            scan.repos
+        }
+    };
+
+    let mut seen: HashSet<PathBuf> = HashSet::new();
+    let mut repos: Vec<RepositoryInfo> = found
+        .into_iter()
+        .filter_map(|p| std::fs::canonicalize(p).ok())
+        .filter(|p| seen.insert(p.clone()))
+        .map(RepositoryInfo::from_path)
+        .collect();
+
     if repos.is_empty() {
         return Err(PrpError::NoRepositoriesFound);
     }
@@ -58,4 +240,27 @@ pub fn discover_repositories(root: &str) -> Result<Vec<RepositoryInfo>, PrpError
     repos.sort_by(|a, b| a.path.cmp(&b.path));
 
     Ok(repos)
-}
\ No newline at end of file
+}
+
+/// Repositories under `root` that are not excluded in the PRP config.
+pub fn active_repositories(root: &str) -> Result<Vec<RepositoryInfo>, PrpError> {
+    let config = load_config();
+    let repos: Vec<RepositoryInfo> = discover_repositories(root)?
+        .into_iter()
+        .filter(|r| !is_excluded(&config, &r.path))
+        .collect();
+
+    if repos.is_empty() {
+        return Err(PrpError::NoRepositoriesFound);
+    }
+    Ok(repos)
+}
+
+/// Repositories for a PRP command: the named workspace when `set` is given,
+/// otherwise the active repositories under the current directory.
+pub fn resolve_repositories(set: Option<&str>) -> Result<Vec<RepositoryInfo>, PrpError> {
+    match set {
+        Some(name) => workspace_repositories(name),
+        None => active_repositories("."),
+    }
+}
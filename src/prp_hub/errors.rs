@@ -1,6 +1,10 @@
 use std::fmt;
 
-#[derive(Debug)]
+use serde::Serialize;
+
+/// Serialized as `{"kind": "<variant>", "detail": ...}` for headless output
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", content = "detail")]
 pub enum PrpError {
     DiscoveryError(String),
     InvalidState { repo: String, reason: String, fix: String },
@@ -9,7 +13,17 @@ pub enum PrpError {
     UnmergedPaths(String),
     CommitFailed { repo: String, reason: String },
     PushFailed { repo: String, stderr: String },
+    PreflightFailed { repo: String, reason: String, fix: String },
     RollbackFailed { repo: String, reason: String },
+    PullRequestFailed { repo: String, reason: String },
+    InvalidMessage { repo: String, reason: String },
+    SigningFailed { repo: String, reason: String },
+    PullFailed { repo: String, reason: String, fix: String },
+    TagFailed { repo: String, reason: String, fix: String },
+    ReleaseFailed { repo: String, reason: String },
+    CheckFailed { repo: String, check: String, output: String },
+    /// Repository names along the cycle, first repeated at the end
+    DependencyCycle(Vec<String>),
     NoRepositoriesFound,
 }
 
@@ -49,11 +63,56 @@ impl fmt::Display for PrpError {
                 "\n❌ Repository: {}\n   Error: Push failed\n   Output: {}\n   Suggested Fix: Check remote connection or run `git pull` first",
                 repo, stderr
             ),
+            PrpError::PreflightFailed { repo, reason, fix } => write!(
+                f,
+                "\n❌ Repository: {}\n   Error: Remote check failed — {}\n   Suggested Fix: {}",
+                repo, reason, fix
+            ),
             PrpError::RollbackFailed { repo, reason } => write!(
                 f,
                 "\n❌ Repository: {} — Rollback failed: {}",
                 repo, reason
             ),
+            PrpError::PullRequestFailed { repo, reason } => write!(
+                f,
+                "\n❌ Repository: {}\n   Error: Pull request failed — {}\n   Suggested Fix: Check the branch is pushed and run /auth status",
+                repo, reason
+            ),
+            PrpError::InvalidMessage { repo, reason } => write!(
+                f,
+                "\n❌ Repository: {}\n   Error: Commit message rejected — {}\n   Suggested Fix: Reword the message or adjust the rules in .gitlink/prp_config.json",
+                repo, reason
+            ),
+            PrpError::SigningFailed { repo, reason } => write!(
+                f,
+                "\n❌ Repository: {}\n   Error: Commit signing failed — {}\n   Suggested Fix: Check user.signingkey and gpg.format, then try `git commit -S` in that repository",
+                repo, reason
+            ),
+            PrpError::PullFailed { repo, reason, fix } => write!(
+                f,
+                "\n❌ Repository: {}\n   Error: Pull failed — {}\n   Suggested Fix: {}",
+                repo, reason, fix
+            ),
+            PrpError::TagFailed { repo, reason, fix } => write!(
+                f,
+                "\n❌ Repository: {}\n   Error: Tag failed — {}\n   Suggested Fix: {}",
+                repo, reason, fix
+            ),
+            PrpError::ReleaseFailed { repo, reason } => write!(
+                f,
+                "\n❌ Repository: {}\n   Error: Release failed — {}\n   Suggested Fix: Check the tag is pushed and run /auth status",
+                repo, reason
+            ),
+            PrpError::CheckFailed { repo, check, output } => write!(
+                f,
+                "\n❌ Repository: {}\n   Error: Pre-commit check '{}' failed\n   Output: {}\n   Suggested Fix: Fix the reported problems, or leave this repository out of the session",
+                repo, check, output
+            ),
+            PrpError::DependencyCycle(cycle) => write!(
+                f,
+                "\n❌ Dependency cycle: {}\n   Error: These repositories depend on each other, so no commit/push order exists\n   Suggested Fix: Break one of the dependencies, or leave one of these repositories out of the session",
+                cycle.join(" → ")
+            ),
             PrpError::NoRepositoriesFound => write!(
                 f,
                 "❌ No git repositories found in the current directory."
@@ -62,4 +121,26 @@ impl fmt::Display for PrpError {
     }
 }
 
+impl PrpError {
+    /// Repository the error is about, if it concerns a single one
+    pub fn repo(&self) -> Option<&str> {
+        match self {
+            PrpError::DetachedHead(repo) | PrpError::MergeConflict(repo) | PrpError::UnmergedPaths(repo) => Some(repo),
+            PrpError::InvalidState { repo, .. }
+            | PrpError::CommitFailed { repo, .. }
+            | PrpError::PushFailed { repo, .. }
+            | PrpError::PreflightFailed { repo, .. }
+            | PrpError::RollbackFailed { repo, .. }
+            | PrpError::PullRequestFailed { repo, .. }
+            | PrpError::InvalidMessage { repo, .. }
+            | PrpError::SigningFailed { repo, .. }
+            | PrpError::PullFailed { repo, .. }
+            | PrpError::TagFailed { repo, .. }
+            | PrpError::ReleaseFailed { repo, .. }
+            | PrpError::CheckFailed { repo, .. } => Some(repo),
+            PrpError::DiscoveryError(_) | PrpError::DependencyCycle(_) | PrpError::NoRepositoriesFound => None,
+        }
+    }
+}
+
 impl std::error::Error for PrpError {}
\ No newline at end of file
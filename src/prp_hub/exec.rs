@@ -0,0 +1,288 @@
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::prp_hub::discovery::resolve_repositories;
+use crate::prp_hub::errors::PrpError;
+use crate::prp_hub::status::collect_status;
+use crate::prp_hub::types::RepositoryInfo;
+
+/// Default number of repositories a command runs in at once
+pub const DEFAULT_JOBS: usize = 4;
+
+/// A parsed `/prp exec [options] <command>`
+#[derive(Debug, Clone)]
+pub struct ExecRequest {
+    pub command: String,
+    pub changed_only: bool,
+    pub jobs: usize,
+    pub set: Option<String>,
+}
+
+/// Parse the text after `exec`. Options are only recognised before the
+/// command, so flags meant for the command itself are passed through untouched.
+pub fn parse_exec_args(rest: &str) -> Result<ExecRequest, String> {
+    let usage = "Usage: /prp exec [--changed-only] [-j N] [--set <workspace>] <command>";
+    let mut request = ExecRequest {
+        command: String::new(),
+        changed_only: false,
+        jobs: DEFAULT_JOBS,
+        set: None,
+    };
+
+    let mut rest = rest.trim_start();
+    loop {
+        let (word, tail) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        match word {
+            "--changed-only" => request.changed_only = true,
+            "-j" | "--jobs" | "--set" => {
+                let (value, after) = tail.trim_start().split_once(char::is_whitespace).unwrap_or((tail.trim(), ""));
+                if value.is_empty() {
+                    return Err(usage.to_string());
+                }
+                if word == "--set" {
+                    request.set = Some(value.to_string());
+                } else {
+                    request.jobs = value
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|n| *n > 0)
+                        .ok_or_else(|| format!("Invalid job count '{}'", value))?;
+                }
+                rest = after.trim_start();
+                continue;
+            }
+            "--" => {
+                rest = tail.trim_start();
+                break;
+            }
+            _ => break,
+        }
+        rest = tail.trim_start();
+    }
+
+    if rest.trim().is_empty() {
+        return Err(usage.to_string());
+    }
+    request.command = rest.trim().to_string();
+    Ok(request)
+}
+
+/// The repositories a request runs in, in discovery order.
+pub fn exec_targets(request: &ExecRequest) -> Result<Vec<RepositoryInfo>, PrpError> {
+    let repos = resolve_repositories(request.set.as_deref())?;
+    if !request.changed_only {
+        return Ok(repos);
+    }
+
+    let dirty: Vec<RepositoryInfo> = repos.into_iter().filter(|r| !collect_status(r).is_empty()).collect();
+    if dirty.is_empty() {
+        return Err(PrpError::DiscoveryError("No repository has uncommitted changes".to_string()));
+    }
+    Ok(dirty)
+}
+
+/// Progress of a parallel run, tagged with the repository's index in the target list
+#[derive(Debug)]
+pub enum ExecEvent {
+    Started(usize),
+    Line { repo: usize, text: String, stderr: bool },
+    /// `code` is None when the process was killed or could not start
+    Finished { repo: usize, code: Option<i32>, elapsed: Duration },
+}
+
+/// `command` run through `sh -c` (`cmd /C` on Windows), in its own process group.
+pub fn shell(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", command]);
+        // Own process group, so cancelling also stops whatever the shell started
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut cmd, 0);
+        cmd
+    }
+}
+
+fn kill_tree(child: &mut Child) {
+    #[cfg(unix)]
+    {
+        let _ = Command::new("kill")
+            .args(["-TERM", "--", &format!("-{}", child.id())])
+            .stderr(Stdio::null())
+            .status();
+    }
+    let _ = child.kill();
+}
+
+fn forward_lines<R: Read + Send + 'static>(
+    reader: R,
+    repo: usize,
+    stderr: bool,
+    tx: Sender<ExecEvent>,
+) -> std::thread::JoinHandle<()> {
+    std::thread::spawn(move || {
+        for line in BufReader::new(reader).lines().map_while(Result::ok) {
+            let _ = tx.send(ExecEvent::Line { repo, text: line, stderr });
+        }
+    })
+}
+
+// Wait for the child, killing it if the run is cancelled.
+fn wait_or_cancel(child: &mut Child, cancel: &AtomicBool) -> Option<i32> {
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return status.code(),
+            Ok(None) if cancel.load(Ordering::Relaxed) => {
+                kill_tree(child);
+                let _ = child.wait();
+                return None;
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(_) => return None,
+        }
+    }
+}
+
+fn run_one(info: &RepositoryInfo, repo: usize, command: &str, tx: &Sender<ExecEvent>, cancel: &AtomicBool) {
+    let start = Instant::now();
+    let _ = tx.send(ExecEvent::Started(repo));
+
+    let spawned = shell(command)
+        .current_dir(&info.path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        // Prompts would hang a headless child; fail fast instead
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .spawn();
+
+    let code = match spawned {
+        Ok(mut child) => {
+            let readers: Vec<_> = [
+                child.stdout.take().map(|o| forward_lines(o, repo, false, tx.clone())),
+                child.stderr.take().map(|e| forward_lines(e, repo, true, tx.clone())),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+            let code = wait_or_cancel(&mut child, cancel);
+            // After a cancel a surviving grandchild may hold the pipes open;
+            // leave its reader behind rather than wait for it
+            if !cancel.load(Ordering::Relaxed) {
+                for r in readers {
+                    let _ = r.join();
+                }
+            }
+            code
+        }
+        Err(e) => {
+            let _ = tx.send(ExecEvent::Line {
+                repo,
+                text: format!("Cannot start command: {}", e),
+                stderr: true,
+            });
+            None
+        }
+    };
+
+    let _ = tx.send(ExecEvent::Finished { repo, code, elapsed: start.elapsed() });
+}
+
+/// Run `command` through the shell in every repository, at most `jobs` at a
+/// time, streaming events to `tx`. Blocks until all repositories finished or
+/// `cancel` is set (running commands are then killed and the rest skipped).
+pub fn run_parallel(
+    repos: &[RepositoryInfo],
+    command: &str,
+    jobs: usize,
+    tx: Sender<ExecEvent>,
+    cancel: Arc<AtomicBool>,
+) {
+    let next = AtomicUsize::new(0);
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, repos.len().max(1)) {
+            let tx = tx.clone();
+            let next = &next;
+            let cancel = &cancel;
+            scope.spawn(move || loop {
+                let i = next.fetch_add(1, Ordering::SeqCst);
+                if i >= repos.len() || cancel.load(Ordering::Relaxed) {
+                    break;
+                }
+                run_one(&repos[i], i, command, &tx, cancel);
+            });
+        }
+    });
+}
+
+/// Final state of one repository after a run
+#[derive(Debug, Clone)]
+pub struct ExecResult {
+    pub name: String,
+    pub code: Option<i32>,
+    pub elapsed: Duration,
+}
+
+/// Exit-code summary: one line per repository, then the totals.
+pub fn format_exec_summary(results: &[ExecResult]) -> String {
+    let mut out = String::new();
+    for r in results {
+        let status = match r.code {
+            Some(0) => "✅ exit 0".to_string(),
+            Some(code) => format!("❌ exit {}", code),
+            None => "❌ killed / not started".to_string(),
+        };
+        out.push_str(&format!("   {}:  {}  ({:.1}s)\n", r.name, status, r.elapsed.as_secs_f32()));
+    }
+    let failed = results.iter().filter(|r| r.code != Some(0)).count();
+    out.push_str(&format!(
+        "\n{} {} succeeded, {} failed\n",
+        if failed == 0 { "✅" } else { "⚠️ " },
+        results.len() - failed,
+        failed
+    ));
+    out
+}
+
+/// Run a request to completion and return the grouped output plus summary.
+/// Used where streaming is not available (plain command output).
+pub fn run_collected(request: &ExecRequest) -> Result<(String, bool), PrpError> {
+    let repos = exec_targets(request)?;
+    let (tx, rx) = std::sync::mpsc::channel();
+    run_parallel(&repos, &request.command, request.jobs, tx, Arc::new(AtomicBool::new(false)));
+
+    let mut output: Vec<Vec<String>> = vec![Vec::new(); repos.len()];
+    let mut results: Vec<ExecResult> = repos
+        .iter()
+        .map(|r| ExecResult { name: r.name.clone(), code: None, elapsed: Duration::ZERO })
+        .collect();
+    for event in rx.try_iter() {
+        match event {
+            ExecEvent::Line { repo, text, .. } => output[repo].push(text),
+            ExecEvent::Finished { repo, code, elapsed } => {
+                results[repo].code = code;
+                results[repo].elapsed = elapsed;
+            }
+            ExecEvent::Started(_) => {}
+        }
+    }
+
+    let mut out = String::new();
+    for (info, lines) in repos.iter().zip(&output) {
+        out.push_str(&format!("▣ {}\n", info.name));
+        for l in lines {
+            out.push_str(&format!("   {}\n", l));
+        }
+        out.push('\n');
+    }
+    out.push_str(&format_exec_summary(&results));
+    let ok = results.iter().all(|r| r.code == Some(0));
+    Ok((out, ok))
+}
@@ -1,86 +1,231 @@
 use std::collections::HashMap;
-use std::process::Command;
+use std::path::PathBuf;
 
-use dialoguer::{theme::ColorfulTheme, Select};
+use dialoguer::{theme::ColorfulTheme, Input, Select};
+use git2::{DiffFormat, Oid, Repository};
 
+use crate::github::pulls_client::{PullsClient, Review};
 use crate::prp_hub::config::{exclude_repo, include_repo, is_excluded, load_config, save_config};
 use crate::prp_hub::discovery::discover_repositories;
-
-#[derive(Debug)]
+use crate::prp_hub::journal::{load_journal, SessionJournal};
+use crate::prp_hub::types::RepositoryInfo;
+use crate::prp_hub::workspace::{
+    format_workspaces, is_valid_name, load_workspaces, normalize_entry, save_workspaces,
+};
+
+/// One commit carrying a Group-ID trailer
+#[derive(Debug, Clone)]
 pub struct CommitInfo {
     pub repo_name: String,
+    pub repo_path: PathBuf,
+    pub oid: Oid,
     pub short_sha: String,
     pub first_line: String,
+    /// Full message, trailers included
+    pub message: String,
+    /// "Name <email>"
+    pub author: String,
+    /// Commit time, seconds since the epoch
+    pub time: i64,
 }
 
-fn extract_groups_from_repo(
-    repo_name: &str,
-    repo_path: &std::path::Path,
-) -> HashMap<String, Vec<CommitInfo>> {
-    let mut map: HashMap<String, Vec<CommitInfo>> = HashMap::new();
+/// Commits from every repository that share one Group-ID
+#[derive(Debug, Clone)]
+pub struct CommitGroup {
+    pub group_id: String,
+    pub commits: Vec<CommitInfo>,
+}
 
-    let output = Command::new("git")
-        .args(["log", "--format=%H%n%B%n---COMMIT_END---"])
-        .current_dir(repo_path)
-        .output();
+impl CommitGroup {
+    /// Time of the newest commit in the group.
+    pub fn latest(&self) -> i64 {
+        self.commits.iter().map(|c| c.time).max().unwrap_or(0)
+    }
 
-    let output = match output {
-        Ok(o) if o.status.success() => o,
-        _ => return map,
-    };
+    pub fn repo_count(&self) -> usize {
+        let mut repos: Vec<&PathBuf> = self.commits.iter().map(|c| &c.repo_path).collect();
+        repos.sort();
+        repos.dedup();
+        repos.len()
+    }
+}
 
-    let raw = String::from_utf8_lossy(&output.stdout);
+fn group_id_of(message: &str) -> Option<String> {
+    message.lines().find_map(|l| {
+        l.trim()
+            .strip_prefix("Group-ID:")
+            .map(|g| g.trim().to_string())
+            .filter(|g| !g.is_empty())
+    })
+}
 
-    for block in raw.split("---COMMIT_END---") {
-        let block = block.trim();
-        if block.is_empty() {
-            continue;
-        }
+fn extract_groups_from_repo(info: &RepositoryInfo) -> HashMap<String, Vec<CommitInfo>> {
+    let mut map: HashMap<String, Vec<CommitInfo>> = HashMap::new();
 
-        let mut lines = block.lines();
+    let repo = match Repository::open(&info.path) {
+        Ok(r) => r,
+        Err(_) => return map,
+    };
+    let mut walk = match repo.revwalk() {
+        Ok(w) => w,
+        Err(_) => return map,
+    };
+    if walk.push_head().is_err() {
+        return map;
+    }
 
-        let sha = match lines.next() {
-            Some(s) => s.trim().to_string(),
+    for oid in walk.filter_map(|o| o.ok()) {
+        let commit = match repo.find_commit(oid) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+        let message = String::from_utf8_lossy(commit.message_bytes()).into_owned();
+        let group_id = match group_id_of(&message) {
+            Some(g) => g,
             None => continue,
         };
 
-        if sha.len() < 8 {
-            continue;
+        let author = commit.author();
+        map.entry(group_id).or_default().push(CommitInfo {
+            repo_name: info.name.clone(),
+            repo_path: info.path.clone(),
+            oid,
+            short_sha: oid.to_string()[..8].to_string(),
+            first_line: message
+                .lines()
+                .find(|l| !l.trim().is_empty())
+                .unwrap_or("(no message)")
+                .to_string(),
+            message,
+            author: format!("{} <{}>", author.name().unwrap_or(""), author.email().unwrap_or("")),
+            time: commit.time().seconds(),
+        });
+    }
+
+    map
+}
+
+/// Every Group-ID found in the history of `repos`, newest group first.
+pub fn collect_groups(repos: &[RepositoryInfo]) -> Vec<CommitGroup> {
+    let mut global: HashMap<String, Vec<CommitInfo>> = HashMap::new();
+    for repo in repos {
+        for (gid, commits) in extract_groups_from_repo(repo) {
+            global.entry(gid).or_default().extend(commits);
         }
+    }
 
-        let rest: Vec<&str> = lines.collect();
-        let body = rest.join("\n");
+    let mut groups: Vec<CommitGroup> = global
+        .into_iter()
+        .map(|(group_id, mut commits)| {
+            commits.sort_by(|a, b| a.time.cmp(&b.time).then_with(|| a.repo_name.cmp(&b.repo_name)));
+            CommitGroup { group_id, commits }
+        })
+        .collect();
+    groups.sort_by(|a, b| b.latest().cmp(&a.latest()).then_with(|| a.group_id.cmp(&b.group_id)));
+    groups
+}
 
-        let group_id = body.lines().find_map(|l| {
-            let l = l.trim();
-            if l.starts_with("Group-ID:") {
-                Some(l.trim_start_matches("Group-ID:").trim().to_string())
-            } else {
-                None
-            }
-        });
+/// Local date and time of a commit, e.g. "2024-05-01 14:03".
+pub fn format_commit_time(time: i64) -> String {
+    chrono::DateTime::from_timestamp(time, 0)
+        .map(|t| t.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
 
-        let group_id = match group_id {
-            Some(g) => g,
-            None => continue,
-        };
+/// One line of a group's combined diff
+#[derive(Debug, Clone)]
+pub enum PatchLine {
+    /// Start of a member commit: "repo  sha  summary"
+    Commit(String),
+    File(String),
+    Hunk(String),
+    Added(String),
+    Removed(String),
+    Context(String),
+    Note(String),
+}
 
-        let first_line = body
-            .lines()
-            .find(|l| !l.trim().is_empty())
-            .unwrap_or("(no message)")
-            .to_string();
+/// Diff of one commit against its first parent.
+pub fn commit_patch(commit: &CommitInfo) -> Result<Vec<PatchLine>, git2::Error> {
+    let repo = Repository::open(&commit.repo_path)?;
+    let c = repo.find_commit(commit.oid)?;
+    let tree = c.tree()?;
+    let parent_tree = match c.parent(0) {
+        Ok(p) => Some(p.tree()?),
+        Err(_) => None,
+    };
+    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+
+    let mut lines = Vec::new();
+    diff.print(DiffFormat::Patch, |delta, _hunk, line| {
+        let text = String::from_utf8_lossy(line.content()).trim_end_matches('\n').to_string();
+        match line.origin() {
+            'F' => {
+                let path = delta
+                    .new_file()
+                    .path()
+                    .or_else(|| delta.old_file().path())
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_default();
+                lines.push(PatchLine::File(path));
+            }
+            'H' => lines.push(PatchLine::Hunk(text)),
+            '+' => lines.push(PatchLine::Added(text)),
+            '-' => lines.push(PatchLine::Removed(text)),
+            ' ' => lines.push(PatchLine::Context(text)),
+            'B' => lines.push(PatchLine::Note("Binary file differs".to_string())),
+            _ => {}
+        }
+        true
+    })?;
+    Ok(lines)
+}
 
-        let short_sha = sha[..8].to_string();
+/// Diffs of every member commit of a group, one after another.
+pub fn group_patch(group: &CommitGroup) -> Vec<PatchLine> {
+    let mut lines = Vec::new();
+    for commit in &group.commits {
+        lines.push(PatchLine::Commit(format!(
+            "{}  {}  {}",
+            commit.repo_name, commit.short_sha, commit.first_line
+        )));
+        match commit_patch(commit) {
+            Ok(patch) if patch.is_empty() => lines.push(PatchLine::Note("No file changes".to_string())),
+            Ok(patch) => lines.extend(patch),
+            Err(e) => lines.push(PatchLine::Note(format!("Cannot diff: {}", e))),
+        }
+    }
+    lines
+}
 
-        map.entry(group_id).or_default().push(CommitInfo {
-            repo_name: repo_name.to_string(),
-            short_sha,
-            first_line,
-        });
+/// Plain-text listing of groups: id, date, repos and each member commit.
+pub fn format_groups(groups: &[CommitGroup]) -> String {
+    if groups.is_empty() {
+        return "ℹ️  No commits with Group-ID trailers found.\n".to_string();
     }
 
-    map
+    let mut out = String::new();
+    for group in groups {
+        out.push_str(&format!(
+            "📦 {}  —  {}, {} repo(s)\n",
+            group.group_id,
+            format_commit_time(group.latest()),
+            group.repo_count()
+        ));
+        for c in &group.commits {
+            out.push_str(&format!("   [{}]  {}  {}  ({})\n", c.repo_name, c.short_sha, c.first_line, c.author));
+        }
+        if let Ok(journal) = load_journal(&group.group_id) {
+            for r in &journal.repos {
+                if let Some(pr) = &r.pull_request {
+                    out.push_str(&format!("   🔀 [{}]  #{}  {}\n", r.name, pr.number, pr.url));
+                }
+            }
+        }
+        out.push('\n');
+    }
+    out.push_str(&format!("Total groups found: {}\n", groups.len()));
+    out
 }
 
 /// `gitlink prp list` — shows commit groups and lets user manage repo inclusion.
@@ -117,6 +262,7 @@ pub fn list_groups() -> Result<(), Box<dyn std::error::Error>> {
 
         let mut menu_items: Vec<String> = Vec::new();
         menu_items.push("📋 Show commit groups".to_string());
+        menu_items.push("🗂  Manage workspaces".to_string());
 
         for r in &active {
             menu_items.push(format!("🚫 Exclude '{}' from sync", r.name));
@@ -136,21 +282,25 @@ pub fn list_groups() -> Result<(), Box<dyn std::error::Error>> {
             show_commit_groups(&active)?;
             continue;
         }
+        if choice == 1 {
+            manage_workspaces()?;
+            continue;
+        }
 
         let back_index = menu_items.len() - 1;
         if choice == back_index {
             break;
         }
 
-        if choice >= 1 && choice <= active.len() {
-            let repo = active[choice - 1];
+        if choice >= 2 && choice < active.len() + 2 {
+            let repo = active[choice - 2];
             exclude_repo(&mut config, repo.path.clone());
             save_config(&config);
             println!("\n🚫 '{}' excluded from PRP sync.", repo.name);
             continue;
         }
 
-        let readd_start = active.len() + 1;
+        let readd_start = active.len() + 2;
         if choice >= readd_start && choice < back_index {
             let repo = excluded[choice - readd_start];
             include_repo(&mut config, &repo.path);
@@ -162,52 +312,200 @@ pub fn list_groups() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-fn show_commit_groups(
-    repos: &[&crate::prp_hub::types::RepositoryInfo],
-) -> Result<(), Box<dyn std::error::Error>> {
+/// Create, extend and prune the named workspaces used by `/prp --set`.
+fn manage_workspaces() -> Result<(), Box<dyn std::error::Error>> {
+    loop {
+        let mut store = load_workspaces();
+        println!("\n🗂  Workspaces\n");
+        print!("{}", format_workspaces(&store));
+
+        let mut items: Vec<String> = vec!["➕ New workspace".to_string()];
+        items.extend(store.workspaces.iter().map(|w| format!("✏️  Edit '{}'", w.name)));
+        items.push("← Back".to_string());
+
+        let choice = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Workspaces")
+            .items(&items)
+            .default(0)
+            .interact()?;
+
+        if choice == items.len() - 1 {
+            return Ok(());
+        }
+
+        let name = if choice == 0 {
+            let name: String = Input::with_theme(&ColorfulTheme::default())
+                .with_prompt("Workspace name (e.g. backend)")
+                .validate_with(|n: &String| {
+                    if is_valid_name(n.trim()) { Ok(()) } else { Err("Use letters, digits, '-', '_' or '.'") }
+                })
+                .interact_text()?;
+            name.trim().to_string()
+        } else {
+            store.workspaces[choice - 1].name.clone()
+        };
+
+        let ws_entries = store.find(&name).map(|w| w.entries.clone()).unwrap_or_default();
+        let mut actions: Vec<String> = vec!["➕ Add path or glob".to_string()];
+        actions.extend(ws_entries.iter().map(|e| format!("🗑  Remove '{}'", e)));
+        if !ws_entries.is_empty() {
+            actions.push("❌ Delete workspace".to_string());
+        }
+        actions.push("← Back".to_string());
+
+        let action = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Workspace '{}'", name))
+            .items(&actions)
+            .default(0)
+            .interact()?;
+
+        if action == 0 {
+            let entry: String = Input::with_theme(&ColorfulTheme::default())
+                .with_prompt("Path or glob (e.g. ~/work/services/*)")
+                .interact_text()?;
+            store.add_entries(&name, &[normalize_entry(entry.trim())]);
+        } else if action <= ws_entries.len() {
+            store.remove_entry(&name, &ws_entries[action - 1])?;
+        } else if action == ws_entries.len() + 1 && !ws_entries.is_empty() {
+            store.remove(&name);
+            println!("\n🗑  Workspace '{}' deleted.", name);
+        } else {
+            continue;
+        }
+        save_workspaces(&store)?;
+    }
+}
+
+fn show_commit_groups(repos: &[&RepositoryInfo]) -> Result<(), Box<dyn std::error::Error>> {
     println!("\n🔍 Scanning {} active repositories for linked commit groups...\n", repos.len());
 
-    let mut global: HashMap<String, Vec<CommitInfo>> = HashMap::new();
+    let repos: Vec<RepositoryInfo> = repos.iter().map(|r| (*r).clone()).collect();
+    println!("{}", "=".repeat(80));
+    print!("{}", format_groups(&collect_groups(&repos)));
+    println!("{}", "=".repeat(80));
 
-    for repo in repos {
-        let local = extract_groups_from_repo(&repo.name, &repo.path);
-        for (gid, commits) in local {
-            global.entry(gid).or_default().extend(commits);
+    Ok(())
+}
+
+/// Live review/merge state of one pull request in a group
+#[derive(Debug)]
+pub struct PullRequestStatus {
+    pub repo_name: String,
+    pub number: u64,
+    pub url: String,
+    /// "open", "closed" or "merged"
+    pub state: String,
+    /// "approved", "changes requested" or "awaiting review"
+    pub review: String,
+}
+
+// Only the latest review of each reviewer counts, as on GitHub.
+fn review_decision(reviews: &[Review]) -> String {
+    let mut latest: HashMap<String, &str> = HashMap::new();
+    for r in reviews {
+        if matches!(r.state.as_str(), "APPROVED" | "CHANGES_REQUESTED" | "DISMISSED") {
+            let who = r.user.as_ref().map(|u| u.login.clone()).unwrap_or_default();
+            latest.insert(who, r.state.as_str());
         }
     }
 
-    if global.is_empty() {
-        println!("ℹ️  No commits with Group-ID trailers found.");
-        return Ok(());
+    if latest.values().any(|s| *s == "CHANGES_REQUESTED") {
+        "changes requested".to_string()
+    } else if latest.values().any(|s| *s == "APPROVED") {
+        "approved".to_string()
+    } else {
+        "awaiting review".to_string()
     }
+}
 
-    let mut group_ids: Vec<String> = global.keys().cloned().collect();
-    group_ids.sort();
+/// Fetch the status of every pull request recorded for a group.
+pub async fn fetch_group_pr_status(
+    client: &PullsClient,
+    journal: &SessionJournal,
+) -> Vec<Result<PullRequestStatus, String>> {
+    let mut statuses = Vec::new();
 
-    for gid in &group_ids {
-        let commits = &global[gid];
-        let mut seen_repos: Vec<&str> = commits.iter().map(|c| c.repo_name.as_str()).collect();
-        seen_repos.dedup();
-        let repo_count = seen_repos.len();
+    for r in &journal.repos {
+        let pr = match &r.pull_request {
+            Some(pr) => pr,
+            None => continue,
+        };
 
-        println!("{}", "=".repeat(80));
-        println!("📦 Group-ID: {}", gid);
-        println!("   Spans {} repo(s)", repo_count);
-        println!("{}", "-".repeat(80));
-
-        for c in commits {
-            println!(
-                "   [{repo}]  {sha}  {msg}",
-                repo = c.repo_name,
-                sha = c.short_sha,
-                msg = c.first_line
-            );
-        }
-        println!();
+        let status = async {
+            let pull = client.fetch_pull(&pr.owner, &pr.repo, pr.number).await?;
+            let reviews = client.fetch_reviews(&pr.owner, &pr.repo, pr.number).await?;
+            let state = if pull.merged { "merged".to_string() } else { pull.state };
+            Ok::<_, Box<dyn std::error::Error>>(PullRequestStatus {
+                repo_name: r.name.clone(),
+                number: pr.number,
+                url: pr.url.clone(),
+                state,
+                review: review_decision(&reviews),
+            })
+        }
+        .await
+        .map_err(|e| format!("{} #{}: {}", r.name, pr.number, e));
+
+        statuses.push(status);
     }
 
-    println!("{}", "=".repeat(80));
-    println!("Total groups found: {}", group_ids.len());
+    statuses
+}
 
-    Ok(())
-}
\ No newline at end of file
+/// Render a group's combined pull request status as plain text.
+pub fn format_group_pr_status(
+    journal: &SessionJournal,
+    statuses: &[Result<PullRequestStatus, String>],
+) -> String {
+    let mut out = format!(
+        "PRP Pull Requests — {}\n{}\n\n   Message:  {}\n\n",
+        journal.group_id,
+        "─".repeat(60),
+        journal.message.lines().next().unwrap_or(""),
+    );
+
+    let ok: Vec<&PullRequestStatus> = statuses.iter().filter_map(|s| s.as_ref().ok()).collect();
+
+    for s in &ok {
+        let icon = match (s.state.as_str(), s.review.as_str()) {
+            ("merged", _) => "🟣",
+            ("closed", _) => "⛔",
+            (_, "approved") => "✅",
+            (_, "changes requested") => "❌",
+            _ => "⏳",
+        };
+        out.push_str(&format!(
+            "   {} {}:  #{} {} · {}\n      {}\n",
+            icon, s.repo_name, s.number, s.state, s.review, s.url
+        ));
+    }
+    for e in statuses.iter().filter_map(|s| s.as_ref().err()) {
+        out.push_str(&format!("   ⚠️  {}\n", e));
+    }
+
+    let without_pr = journal.repos.iter().filter(|r| r.pull_request.is_none()).count();
+    let merged = ok.iter().filter(|s| s.state == "merged").count();
+    let approved = ok.iter().filter(|s| s.state == "open" && s.review == "approved").count();
+    let changes = ok.iter().filter(|s| s.state == "open" && s.review == "changes requested").count();
+
+    out.push_str(&format!(
+        "\n📊 Group\n\n   Merged:             {}/{}\n   Approved (open):    {}\n   Changes requested:  {}\n   Without PR:         {}\n",
+        merged,
+        journal.repos.len(),
+        approved,
+        changes,
+        without_pr,
+    ));
+
+    let verdict = if merged == journal.repos.len() {
+        "✅ Fully merged"
+    } else if changes > 0 {
+        "❌ Blocked by requested changes"
+    } else if merged + approved == journal.repos.len() {
+        "✅ Ready to merge"
+    } else {
+        "⏳ In review"
+    };
+    out.push_str(&format!("\n{}\n", verdict));
+    out
+}
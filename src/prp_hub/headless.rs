@@ -0,0 +1,387 @@
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::prp_hub::{
+    branch::current_branch,
+    checks::run_all_checks,
+    commit::commit_each,
+    config::load_config,
+    dependencies::plan_order,
+    discovery::resolve_repositories,
+    errors::PrpError,
+    journal::{push_recorded, record_reverted, SessionJournal, SessionPhase},
+    message::{prepare_messages, MessageInput},
+    pull::pull_all,
+    push::preflight_all,
+    rollback::{revert_partial_push, rollback_each},
+    staging::{collect_changes, RepoSelection},
+    state::validate_repo,
+    types::{new_group_id, CommitSession, RepositoryInfo},
+};
+
+/// Flags of `gitlink prp`
+#[derive(Debug, Clone, Default)]
+pub struct HeadlessOptions {
+    pub message: String,
+    pub set: Option<String>,
+    pub push: bool,
+    /// Commit untracked files too (they are left out by default, as in the TUI)
+    pub include_untracked: bool,
+    /// Pull from upstream first, even if `pull.before_commit` is off
+    pub pull: bool,
+    pub skip_checks: bool,
+    /// After a partial push, revert what was pushed instead of recording it for `/prp resume`
+    pub revert_on_failure: bool,
+}
+
+pub const HEADLESS_USAGE: &str = "Usage: gitlink prp -m <message> [--set <workspace>] [--push] [--pull] \
+[--include-untracked] [--skip-checks] [--revert-on-failure]";
+
+pub fn parse_headless_args(args: &[String]) -> Result<HeadlessOptions, String> {
+    let mut options = HeadlessOptions::default();
+    let mut words = args.iter();
+    while let Some(word) = words.next() {
+        match word.as_str() {
+            "-m" | "--message" => options.message = words.next().ok_or(HEADLESS_USAGE)?.clone(),
+            "--set" => options.set = Some(words.next().ok_or(HEADLESS_USAGE)?.clone()),
+            "--push" => options.push = true,
+            "--pull" => options.pull = true,
+            "--include-untracked" => options.include_untracked = true,
+            "--skip-checks" => options.skip_checks = true,
+            "--revert-on-failure" => options.revert_on_failure = true,
+            other => return Err(format!("Unknown argument '{}'. {}", other, HEADLESS_USAGE)),
+        }
+    }
+    if options.message.trim().is_empty() {
+        return Err(HEADLESS_USAGE.to_string());
+    }
+    Ok(options)
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorReport {
+    /// `PrpError` variant, e.g. "DetachedHead"
+    pub kind: String,
+    pub repo: Option<String>,
+    /// The same text the interactive modes print
+    pub message: String,
+    /// Variant fields
+    pub detail: serde_json::Value,
+}
+
+impl From<&PrpError> for ErrorReport {
+    fn from(e: &PrpError) -> Self {
+        let value = serde_json::to_value(e).unwrap_or_default();
+        Self {
+            kind: value["kind"].as_str().unwrap_or_default().to_string(),
+            repo: e.repo().map(str::to_string),
+            message: e.to_string().trim().to_string(),
+            detail: value["detail"].clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct RepoReport {
+    pub name: String,
+    pub path: String,
+    pub branch: Option<String>,
+    /// Changed files in the working tree
+    pub changes: usize,
+    pub commit: Option<String>,
+    pub pushed: bool,
+}
+
+/// Everything `gitlink prp` prints, as one JSON document
+#[derive(Debug, Serialize)]
+pub struct HeadlessReport {
+    pub ok: bool,
+    /// Last stage reached: discovery, validation, pull, checks, message,
+    /// order, commit, preflight, push or done
+    pub stage: &'static str,
+    pub group_id: Option<String>,
+    /// Commit and push order
+    pub order: Vec<String>,
+    pub repositories: Vec<RepoReport>,
+    pub errors: Vec<ErrorReport>,
+    /// What happened to already-made commits after a failure:
+    /// "rolled_back", "reverted" or "recorded" (resumable with `/prp resume`)
+    pub recovery: Option<&'static str>,
+}
+
+impl HeadlessReport {
+    fn new() -> Self {
+        Self {
+            ok: false,
+            stage: "discovery",
+            group_id: None,
+            order: Vec::new(),
+            repositories: Vec::new(),
+            errors: Vec::new(),
+            recovery: None,
+        }
+    }
+
+    fn fail<'a>(mut self, errors: impl IntoIterator<Item = &'a PrpError>) -> Self {
+        self.errors.extend(errors.into_iter().map(ErrorReport::from));
+        self.ok = false;
+        self
+    }
+
+    fn repo_mut(&mut self, info: &RepositoryInfo) -> Option<&mut RepoReport> {
+        let path = info.path.display().to_string();
+        self.repositories.iter_mut().find(|r| r.path == path)
+    }
+}
+
+/// Run a whole PRP session without prompts. Every stage that the interactive
+/// flow asks about is decided by `options` instead; failures stop the session
+/// and undo commits the same way the interactive flow does.
+pub fn run_headless(options: &HeadlessOptions) -> HeadlessReport {
+    match resolve_repositories(options.set.as_deref()) {
+        Ok(repos) => run_session(options, &repos),
+        Err(e) => HeadlessReport::new().fail([&e]),
+    }
+}
+
+// The session itself, over already discovered repositories.
+fn run_session(options: &HeadlessOptions, repos: &[RepositoryInfo]) -> HeadlessReport {
+    let mut report = HeadlessReport::new();
+    let config = load_config();
+
+    let report_repos = |report: &mut HeadlessReport| {
+        report.repositories = repos
+            .iter()
+            .map(|info| RepoReport {
+                name: info.name.clone(),
+                path: info.path.display().to_string(),
+                branch: current_branch(&info.path),
+                changes: collect_changes(info).len(),
+                commit: None,
+                pushed: false,
+            })
+            .collect();
+    };
+    report_repos(&mut report);
+
+    report.stage = "validation";
+    let errors: Vec<PrpError> = repos.iter().filter_map(|r| validate_repo(r).err()).collect();
+    if !errors.is_empty() {
+        return report.fail(&errors);
+    }
+
+    if options.pull || config.pull.before_commit {
+        report.stage = "pull";
+        let errors: Vec<PrpError> = pull_all(repos, &config.pull, false)
+            .into_iter()
+            .filter_map(|r| r.outcome.err())
+            .collect();
+        if !errors.is_empty() {
+            return report.fail(&errors);
+        }
+        report_repos(&mut report);
+    }
+
+    let mut selections = HashMap::new();
+    for info in repos {
+        let changes = collect_changes(info);
+        if changes.is_empty() {
+            continue;
+        }
+        let mut selection = RepoSelection::default_for(&changes);
+        if options.include_untracked {
+            selection.files.iter_mut().for_each(|f| f.included = true);
+        }
+        selections.insert(info.path.clone(), selection);
+    }
+    if selections.is_empty() {
+        report.stage = "done";
+        report.ok = true;
+        return report;
+    }
+
+    if !options.skip_checks {
+        report.stage = "checks";
+        let changed: Vec<RepositoryInfo> = repos.iter().filter(|r| selections.contains_key(&r.path)).cloned().collect();
+        let errors: Vec<PrpError> = run_all_checks(&changed, &config).iter().flat_map(|c| c.failures()).collect();
+        if !errors.is_empty() {
+            return report.fail(&errors);
+        }
+    }
+
+    report.stage = "message";
+    let message = options.message.trim();
+    let inputs: Vec<MessageInput> = repos
+        .iter()
+        .map(|info| MessageInput { info, override_message: None, selection: selections.get(&info.path) })
+        .collect();
+    let messages = match prepare_messages(&inputs, message, &config) {
+        Ok(m) => m,
+        Err(errors) => return report.fail(&errors),
+    };
+
+    report.stage = "order";
+    let plan = match plan_order(repos, &config) {
+        Ok(p) => p,
+        Err(e) => return report.fail([&e]),
+    };
+
+    report.stage = "commit";
+    let group_id = new_group_id();
+    report.group_id = Some(group_id.clone());
+    let mut session = CommitSession::new(group_id, plan.order);
+    report.order = session.repositories.iter().map(|r| r.name.clone()).collect();
+    session.selections = selections;
+    session.messages = messages;
+
+    if let Err(e) = commit_each(&mut session, message, |_, _| {}) {
+        let undo: Vec<PrpError> = rollback_each(&session.committed).into_iter().filter_map(|(_, r)| r.err()).collect();
+        if !session.committed.is_empty() {
+            let _ = SessionJournal::from_session(&session, message, SessionPhase::RolledBack).save();
+        }
+        report.recovery = Some("rolled_back");
+        return report.fail(std::iter::once(&e).chain(&undo));
+    }
+    for c in &session.committed {
+        if let Some(info) = session.repositories.iter().find(|r| r.path == c.path) {
+            if let Some(r) = report.repo_mut(info) {
+                r.commit = Some(c.commit_oid.to_string());
+            }
+        }
+    }
+
+    let mut journal = SessionJournal::from_session(&session, message, SessionPhase::Committed);
+    let _ = journal.save();
+    if !options.push || session.committed.is_empty() {
+        report.stage = "done";
+        report.ok = true;
+        return report;
+    }
+
+    report.stage = "preflight";
+    let committed = session.committed_repos();
+    let (_, errors) = preflight_all(&committed);
+    if !errors.is_empty() {
+        let undo: Vec<PrpError> = rollback_each(&session.committed).into_iter().filter_map(|(_, r)| r.err()).collect();
+        journal.phase = SessionPhase::RolledBack;
+        let _ = journal.save();
+        report.recovery = Some("rolled_back");
+        for r in report.repositories.iter_mut() {
+            r.commit = None;
+        }
+        return report.fail(errors.iter().chain(&undo));
+    }
+
+    report.stage = "push";
+    let push = push_recorded(&mut journal, &committed, |_, _| {});
+    for info in &push.pushed {
+        if let Some(r) = report.repo_mut(info) {
+            r.pushed = true;
+        }
+    }
+    if let Some((_, e)) = &push.failed {
+        if options.revert_on_failure {
+            let outcomes = revert_partial_push(&session, &push);
+            record_reverted(&mut journal, &outcomes);
+            report.recovery = Some("reverted");
+            let undo: Vec<&PrpError> = outcomes.iter().filter_map(|(_, o)| o.as_ref().err()).collect();
+            return report.fail(std::iter::once(e).chain(undo));
+        }
+        report.recovery = Some("recorded");
+        return report.fail([e]);
+    }
+
+    report.stage = "done";
+    report.ok = true;
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prp_hub::test_support::{bare_remote, clone, git, TempDir};
+
+    fn args(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    fn commit_only(message: &str) -> HeadlessOptions {
+        HeadlessOptions { message: message.to_string(), ..Default::default() }
+    }
+
+    #[test]
+    fn parse_requires_a_message() {
+        assert_eq!(parse_headless_args(&args(&["--push"])).unwrap_err(), HEADLESS_USAGE);
+        assert_eq!(parse_headless_args(&args(&["-m"])).unwrap_err(), HEADLESS_USAGE);
+        assert_eq!(parse_headless_args(&args(&["-m", "  "])).unwrap_err(), HEADLESS_USAGE);
+
+        let options = parse_headless_args(&args(&["-m", "Bump", "--set", "core", "--push", "--skip-checks"])).unwrap();
+        assert_eq!(options.message, "Bump");
+        assert_eq!(options.set.as_deref(), Some("core"));
+        assert!(options.push && options.skip_checks && !options.pull);
+    }
+
+    #[test]
+    fn parse_rejects_unknown_flags_with_usage() {
+        let err = parse_headless_args(&args(&["-m", "Bump", "--force"])).unwrap_err();
+        assert!(err.starts_with("Unknown argument '--force'."));
+        assert!(err.ends_with(HEADLESS_USAGE));
+    }
+
+    #[test]
+    fn detached_repo_stops_at_validation() {
+        let tmp = TempDir::new();
+        let api = clone(&bare_remote(tmp.path(), "api"), tmp.path(), "api");
+        let web = clone(&bare_remote(tmp.path(), "web"), tmp.path(), "web");
+        git(&web.path, &["checkout", "-q", "--detach"]);
+        std::fs::write(api.path.join("README.md"), "edited").unwrap();
+
+        let report = run_session(&commit_only("Bump"), &[api.clone(), web]);
+
+        assert!(!report.ok);
+        assert_eq!(report.stage, "validation");
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].kind, "DetachedHead");
+        assert_eq!(report.errors[0].repo.as_deref(), Some("web"));
+        assert!(report.repositories.iter().all(|r| r.commit.is_none()));
+        // Nothing was committed
+        assert_eq!(git(&api.path, &["rev-parse", "HEAD"]), git(&api.path, &["rev-parse", "origin/main"]));
+    }
+
+    #[test]
+    fn commit_only_run_reports_commits_and_group() {
+        let tmp = TempDir::new();
+        let repos: Vec<_> = ["api", "web", "docs"]
+            .iter()
+            .map(|name| clone(&bare_remote(tmp.path(), name), tmp.path(), name))
+            .collect();
+        std::fs::write(repos[0].path.join("README.md"), "api change").unwrap();
+        std::fs::write(repos[1].path.join("README.md"), "web change").unwrap();
+        // Untracked only: left out unless --include-untracked
+        std::fs::write(repos[2].path.join("new.txt"), "draft").unwrap();
+
+        let report = run_session(&commit_only("Bump shared version"), &repos);
+
+        assert!(report.ok, "{:?}", report.errors);
+        assert_eq!(report.stage, "done");
+        assert!(report.recovery.is_none());
+        let group_id = report.group_id.clone().unwrap();
+        for (repo, entry) in repos.iter().zip(&report.repositories) {
+            let head = git(&repo.path, &["rev-parse", "HEAD"]);
+            if repo.name == "docs" {
+                assert_eq!(entry.commit, None);
+                continue;
+            }
+            assert_eq!(entry.commit.as_deref(), Some(head.as_str()));
+            assert!(!entry.pushed);
+            let message = git(&repo.path, &["log", "-1", "--format=%B"]);
+            assert!(message.starts_with("Bump shared version"));
+            assert!(message.contains(&group_id));
+        }
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["stage"], "done");
+        assert_eq!(json["group_id"], group_id.as_str());
+    }
+}
@@ -0,0 +1,342 @@
+use std::path::PathBuf;
+
+use chrono::{DateTime, Utc};
+use git2::{Oid, Repository};
+use serde::{Deserialize, Serialize};
+
+use crate::prp_hub::branch::current_branch;
+use crate::prp_hub::errors::PrpError;
+use crate::prp_hub::push::{preflight_all, push_repos};
+use crate::prp_hub::rollback::{revert_pushed, rollback_one};
+use crate::prp_hub::types::{CommitSession, PushReport, RepoCommitResult, RepoOutcomes, RepositoryInfo};
+
+const JOURNAL_DIR: &str = ".gitlink/prp_sessions";
+
+/// How far a session got when its journal was last written
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SessionPhase {
+    /// Commits exist locally, nothing pushed yet
+    Committed,
+    /// Some repositories pushed, some not
+    PartiallyPushed,
+    Pushed,
+    /// Commits were soft-reset before anything reached a remote
+    RolledBack,
+    /// Undone after the fact (soft-reset and/or revert commits)
+    Undone,
+}
+
+impl SessionPhase {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SessionPhase::Committed => "committed",
+            SessionPhase::PartiallyPushed => "partially pushed",
+            SessionPhase::Pushed => "pushed",
+            SessionPhase::RolledBack => "rolled back",
+            SessionPhase::Undone => "undone",
+        }
+    }
+
+    /// Whether `/prp resume` has anything left to push
+    pub fn is_resumable(&self) -> bool {
+        matches!(self, SessionPhase::Committed | SessionPhase::PartiallyPushed)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalRepo {
+    pub name: String,
+    pub path: PathBuf,
+    pub commit_oid: String,
+    pub pushed: bool,
+    #[serde(default)]
+    pub undone: bool,
+    /// Branch the session commit was made on
+    #[serde(default)]
+    pub branch: String,
+    #[serde(default)]
+    pub pull_request: Option<PullRequestLink>,
+}
+
+/// A pull request opened for one repository of a group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PullRequestLink {
+    pub owner: String,
+    pub repo: String,
+    pub number: u64,
+    pub url: String,
+}
+
+impl JournalRepo {
+    pub fn info(&self) -> RepositoryInfo {
+        RepositoryInfo {
+            name: self.name.clone(),
+            path: self.path.clone(),
+        }
+    }
+
+    fn commit_result(&self) -> Result<RepoCommitResult, PrpError> {
+        let commit_oid = Oid::from_str(&self.commit_oid).map_err(|e| PrpError::InvalidState {
+            repo: self.name.clone(),
+            reason: format!("Journal holds an invalid commit id '{}': {}", self.commit_oid, e),
+            fix: "Inspect the journal file under .gitlink/prp_sessions".to_string(),
+        })?;
+        Ok(RepoCommitResult {
+            path: self.path.clone(),
+            commit_oid,
+        })
+    }
+}
+
+/// On-disk record of a PRP session, one JSON file per Group-ID
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionJournal {
+    pub group_id: String,
+    pub message: String,
+    pub phase: SessionPhase,
+    pub repos: Vec<JournalRepo>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SessionJournal {
+    pub fn from_session(session: &CommitSession, message: &str, phase: SessionPhase) -> Self {
+        let repos = session
+            .committed
+            .iter()
+            .map(|c| {
+                let name = session
+                    .repositories
+                    .iter()
+                    .find(|r| r.path == c.path)
+                    .map(|r| r.name.clone())
+                    .unwrap_or_else(|| c.path.display().to_string());
+                JournalRepo {
+                    name,
+                    path: c.path.clone(),
+                    commit_oid: c.commit_oid.to_string(),
+                    pushed: false,
+                    undone: false,
+                    branch: current_branch(&c.path).unwrap_or_default(),
+                    pull_request: None,
+                }
+            })
+            .collect();
+
+        Self {
+            group_id: session.group_id.clone(),
+            message: message.to_string(),
+            phase,
+            repos,
+            updated_at: Utc::now(),
+        }
+    }
+
+    pub fn mark_pushed(&mut self, info: &RepositoryInfo) {
+        if let Some(repo) = self.repos.iter_mut().find(|r| r.path == info.path) {
+            repo.pushed = true;
+        }
+    }
+
+    /// Take pushed flags and phase from a push report.
+    pub fn apply_report(&mut self, report: &PushReport) {
+        for info in &report.pushed {
+            self.mark_pushed(info);
+        }
+        self.phase = if self.repos.iter().all(|r| r.pushed) {
+            SessionPhase::Pushed
+        } else {
+            SessionPhase::PartiallyPushed
+        };
+    }
+
+    pub fn unpushed(&self) -> Vec<RepositoryInfo> {
+        self.repos.iter().filter(|r| !r.pushed).map(|r| r.info()).collect()
+    }
+
+    /// Stamp and write the journal.
+    pub fn save(&mut self) -> std::io::Result<PathBuf> {
+        self.updated_at = Utc::now();
+        save_journal(self)
+    }
+}
+
+fn journal_dir() -> PathBuf {
+    // Tests commit in throwaway repositories; keep their journals out of the checkout
+    if cfg!(test) {
+        return std::env::temp_dir().join("gitlink-test-sessions");
+    }
+    PathBuf::from(JOURNAL_DIR)
+}
+
+fn journal_path(group_id: &str) -> PathBuf {
+    journal_dir().join(format!("{}.json", group_id))
+}
+
+pub fn save_journal(journal: &SessionJournal) -> std::io::Result<PathBuf> {
+    let path = journal_path(&journal.group_id);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(journal)?;
+    std::fs::write(&path, json)?;
+    Ok(path)
+}
+
+pub fn load_journal(group_id: &str) -> std::io::Result<SessionJournal> {
+    let content = std::fs::read_to_string(journal_path(group_id))?;
+    let journal = serde_json::from_str(&content)?;
+    Ok(journal)
+}
+
+/// All recorded sessions, newest first. Unreadable files are skipped.
+pub fn list_journals() -> Vec<SessionJournal> {
+    let entries = match std::fs::read_dir(journal_dir()) {
+        Ok(e) => e,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut journals: Vec<SessionJournal> = entries
+        .flatten()
+        .filter(|e| e.path().extension().map(|x| x == "json").unwrap_or(false))
+        .filter_map(|e| std::fs::read_to_string(e.path()).ok())
+        .filter_map(|s| serde_json::from_str(&s).ok())
+        .collect();
+
+    journals.sort_by_key(|j| std::cmp::Reverse(j.updated_at));
+    journals
+}
+
+/// The most recent session that still has commits waiting to be pushed.
+pub fn latest_resumable() -> Option<SessionJournal> {
+    list_journals().into_iter().find(|j| j.phase.is_resumable())
+}
+
+/// Push repositories in order like `push_repos`, writing the journal after
+/// every successful push so a crash mid-way leaves an accurate record.
+pub fn push_recorded<F>(
+    journal: &mut SessionJournal,
+    repos: &[RepositoryInfo],
+    mut on_repo: F,
+) -> PushReport
+where
+    F: FnMut(&RepositoryInfo, &Result<(), PrpError>),
+{
+    let report = push_repos(repos, |info, result| {
+        on_repo(info, result);
+        if result.is_ok() {
+            journal.mark_pushed(info);
+            let _ = journal.save();
+        }
+    });
+    journal.apply_report(&report);
+    let _ = journal.save();
+    report
+}
+
+/// Mark the repositories undone by `revert_partial_push` and close the session
+/// if all of them succeeded.
+pub fn record_reverted(journal: &mut SessionJournal, outcomes: &[(String, Result<String, PrpError>)]) {
+    for repo in &mut journal.repos {
+        repo.undone = outcomes.iter().any(|(name, o)| *name == repo.name && o.is_ok());
+    }
+    if journal.repos.iter().all(|r| r.undone) {
+        journal.phase = SessionPhase::Undone;
+    }
+    let _ = journal.save();
+}
+
+// The session commit must still be on the branch: either HEAD itself or an
+// ancestor of it. Anything else means the history was rewritten since.
+fn check_commit_on_head(repo: &JournalRepo) -> Result<(), PrpError> {
+    let fail = |reason: String| PrpError::InvalidState {
+        repo: repo.name.clone(),
+        reason,
+        fix: "Inspect the branch with `git log`; the session commit is no longer on it".to_string(),
+    };
+
+    let commit = repo.commit_result()?;
+    let git = Repository::open(&repo.path).map_err(|e| fail(format!("Cannot open repository: {}", e)))?;
+    let head = git
+        .head()
+        .ok()
+        .and_then(|h| h.target())
+        .ok_or_else(|| fail("HEAD does not point to a commit".to_string()))?;
+
+    let contained = head == commit.commit_oid
+        || git.graph_descendant_of(head, commit.commit_oid).unwrap_or(false);
+    if !contained {
+        return Err(fail(format!(
+            "Session commit {} is not on the current branch",
+            &repo.commit_oid[..8.min(repo.commit_oid.len())]
+        )));
+    }
+    Ok(())
+}
+
+/// Finish an interrupted push: check and push every repository that the journal
+/// has not marked as pushed, updating the journal as each one lands.
+/// Nothing is pushed if any repository fails its checks.
+pub fn resume_session<F>(journal: &mut SessionJournal, mut on_repo: F) -> Result<(), Vec<PrpError>>
+where
+    F: FnMut(&RepositoryInfo, &Result<(), PrpError>),
+{
+    let pending: Vec<JournalRepo> = journal.repos.iter().filter(|r| !r.pushed).cloned().collect();
+
+    let mut errors: Vec<PrpError> = pending
+        .iter()
+        .filter_map(|r| check_commit_on_head(r).err())
+        .collect();
+    if errors.is_empty() {
+        let infos: Vec<RepositoryInfo> = pending.iter().map(|r| r.info()).collect();
+        errors = preflight_all(&infos).1;
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let infos = journal.unpushed();
+    let report = push_recorded(journal, &infos, &mut on_repo);
+
+    match report.failed {
+        Some((_, e)) => Err(vec![e]),
+        None => Ok(()),
+    }
+}
+
+/// Undo every commit of a recorded session, newest first. Unpushed commits are
+/// soft-reset, pushed ones get a pushed revert commit. A repository is only
+/// touched while its HEAD is still the session commit. Repositories already
+/// undone by an earlier, partly failed run are skipped.
+pub fn undo_session(journal: &mut SessionJournal) -> RepoOutcomes {
+    let mut outcomes = Vec::new();
+    let group_id = journal.group_id.clone();
+
+    for repo in journal.repos.iter_mut().rev().filter(|r| !r.undone) {
+        let outcome = repo.commit_result().and_then(|commit| {
+            if repo.pushed {
+                let head = Repository::open(&repo.path)
+                    .ok()
+                    .and_then(|r| r.head().ok().and_then(|h| h.target()));
+                if head != Some(commit.commit_oid) {
+                    return Err(PrpError::RollbackFailed {
+                        repo: repo.name.clone(),
+                        reason: "HEAD has moved past the session commit; skipping undo for safety".to_string(),
+                    });
+                }
+                revert_pushed(&repo.info(), &commit, &group_id)
+                    .map(|oid| format!("reverted by {}", &oid.to_string()[..8]))
+            } else {
+                rollback_one(&commit).map(|()| "rolled back".to_string())
+            }
+        });
+        repo.undone = outcome.is_ok();
+        outcomes.push((repo.name.clone(), outcome));
+    }
+
+    if journal.repos.iter().all(|r| r.undone) {
+        journal.phase = SessionPhase::Undone;
+    }
+    let _ = journal.save();
+
+    outcomes
+}
@@ -0,0 +1,396 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use git2::{BranchType, Repository};
+use serde::{Deserialize, Serialize};
+
+use crate::prp_hub::branch::current_branch;
+use crate::prp_hub::discovery::walk_repositories;
+use crate::prp_hub::errors::PrpError;
+use crate::prp_hub::types::RepositoryInfo;
+
+/// Default manifest location, relative to the workspace root
+pub const MANIFEST_PATH: &str = ".gitlink/manifest.json";
+
+/// One repository of a workspace manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestRepo {
+    /// Defaults to the last component of `path`
+    #[serde(default)]
+    pub name: Option<String>,
+    pub url: String,
+    /// Checkout location, relative to the workspace root
+    pub path: PathBuf,
+    /// Branch to clone and track; the remote's default branch when omitted
+    #[serde(default)]
+    pub branch: Option<String>,
+}
+
+/// List of repositories that make up a workspace, like the Android `repo` tool's manifest
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Manifest {
+    pub repos: Vec<ManifestRepo>,
+    /// Directory the repository paths are relative to; not stored in the file
+    #[serde(skip)]
+    pub root: PathBuf,
+}
+
+impl Manifest {
+    pub fn repo_name(&self, repo: &ManifestRepo) -> String {
+        repo.name.clone().unwrap_or_else(|| {
+            repo.path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| repo.path.to_string_lossy().into_owned())
+        })
+    }
+
+    pub fn checkout_path(&self, repo: &ManifestRepo) -> PathBuf {
+        self.root.join(&repo.path)
+    }
+}
+
+/// Read a manifest. Paths inside it are relative to the directory holding the
+/// `.gitlink` folder, or to the manifest's own directory when it lives elsewhere.
+pub fn load_manifest(path: &Path) -> Result<Manifest, PrpError> {
+    let raw = std::fs::read_to_string(path)
+        .map_err(|e| PrpError::DiscoveryError(format!("Cannot read manifest {}: {}", path.display(), e)))?;
+    let mut manifest: Manifest = serde_json::from_str(&raw)
+        .map_err(|e| PrpError::DiscoveryError(format!("Invalid manifest {}: {}", path.display(), e)))?;
+
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+    manifest.root = if dir.file_name().map(|n| n == ".gitlink").unwrap_or(false) {
+        dir.parent().map(Path::to_path_buf).unwrap_or(dir)
+    } else {
+        dir
+    };
+    Ok(manifest)
+}
+
+/// The manifest governing `root`, if it has one.
+pub fn find_manifest(root: &Path) -> Option<PathBuf> {
+    let path = root.join(MANIFEST_PATH);
+    path.is_file().then_some(path)
+}
+
+/// Repositories listed in the manifest that are cloned. Missing ones are left
+/// out; `gitlink workspace sync` brings them in.
+pub fn manifest_repositories(manifest: &Manifest) -> Result<Vec<RepositoryInfo>, PrpError> {
+    let mut repos: Vec<RepositoryInfo> = manifest
+        .repos
+        .iter()
+        .filter_map(|r| {
+            let path = std::fs::canonicalize(manifest.checkout_path(r)).ok()?;
+            path.join(".git").exists().then(|| RepositoryInfo {
+                name: manifest.repo_name(r),
+                path,
+            })
+        })
+        .collect();
+
+    if repos.is_empty() {
+        return Err(PrpError::NoRepositoriesFound);
+    }
+
+    repos.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(repos)
+}
+
+/// What `sync` did with one repository
+#[derive(Debug)]
+pub enum SyncAction {
+    Cloned,
+    Fetched,
+    /// Found under the root but not listed in the manifest; left untouched
+    Unlisted,
+}
+
+/// Per-repository result of a sync, with any drift from the manifest
+#[derive(Debug)]
+pub struct SyncReport {
+    pub name: String,
+    pub outcome: Result<SyncAction, String>,
+    pub drift: Vec<String>,
+}
+
+fn git(args: &[&str], dir: &Path) -> Result<(), String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        // Never stop for a password prompt; report the failure instead
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .map_err(|e| format!("Cannot run git: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(())
+}
+
+fn clone_repo(manifest: &Manifest, repo: &ManifestRepo) -> Result<SyncAction, String> {
+    let target = manifest.checkout_path(repo);
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Cannot create {}: {}", parent.display(), e))?;
+    }
+
+    let target_str = target.to_string_lossy().into_owned();
+    let mut args = vec!["clone"];
+    if let Some(branch) = &repo.branch {
+        args.extend(["--branch", branch.as_str()]);
+    }
+    args.extend([repo.url.as_str(), target_str.as_str()]);
+
+    git(&args, &manifest.root)?;
+    Ok(SyncAction::Cloned)
+}
+
+// Ways an existing checkout differs from what the manifest describes.
+fn drift_of(path: &Path, repo: &ManifestRepo) -> Vec<String> {
+    let mut drift = Vec::new();
+    let r = match Repository::open(path) {
+        Ok(r) => r,
+        Err(e) => return vec![format!("cannot open repository: {}", e)],
+    };
+
+    match r.find_remote("origin").ok().and_then(|o| o.url().map(str::to_string)) {
+        Some(url) if url != repo.url => drift.push(format!("origin is {} (manifest: {})", url, repo.url)),
+        None => drift.push("no 'origin' remote".to_string()),
+        _ => {}
+    }
+
+    let head = current_branch(path);
+    if let Some(want) = &repo.branch {
+        if head.as_deref() != Some(want.as_str()) {
+            drift.push(format!(
+                "on {} (manifest: {})",
+                head.as_deref().unwrap_or("detached HEAD"),
+                want
+            ));
+        }
+    }
+
+    let branch = repo.branch.clone().or(head);
+    if let Some(branch) = branch {
+        let local = r.find_branch(&branch, BranchType::Local).ok().and_then(|b| b.get().target());
+        let remote = r
+            .find_branch(&format!("origin/{}", branch), BranchType::Remote)
+            .ok()
+            .and_then(|b| b.get().target());
+        if let (Some(local), Some(remote)) = (local, remote) {
+            if let Ok((ahead, behind)) = r.graph_ahead_behind(local, remote) {
+                if ahead > 0 || behind > 0 {
+                    drift.push(format!("{} is {} ahead, {} behind origin/{}", branch, ahead, behind, branch));
+                }
+            }
+        }
+    }
+
+    let dirty = r
+        .statuses(None)
+        .map(|s| s.iter().any(|e| !e.status().is_ignored()))
+        .unwrap_or(false);
+    if dirty {
+        drift.push("uncommitted changes".to_string());
+    }
+
+    drift
+}
+
+/// Clone repositories that are missing, fetch the ones that exist, and
+/// report how each checkout differs from the manifest. Repositories found
+/// under the root but not listed in the manifest are reported too.
+pub fn sync_workspace<F>(manifest: &Manifest, mut on_repo: F) -> Vec<SyncReport>
+where
+    F: FnMut(&SyncReport),
+{
+    let mut reports = Vec::new();
+
+    for repo in &manifest.repos {
+        let name = manifest.repo_name(repo);
+        let path = manifest.checkout_path(repo);
+
+        let report = if path.join(".git").exists() {
+            let outcome = git(&["fetch", "--prune", "origin"], &path).map(|()| SyncAction::Fetched);
+            SyncReport { name, outcome, drift: drift_of(&path, repo) }
+        } else if path.exists() && std::fs::read_dir(&path).map(|mut d| d.next().is_some()).unwrap_or(false) {
+            SyncReport {
+                name,
+                outcome: Err(format!("{} exists but is not a git repository", path.display())),
+                drift: Vec::new(),
+            }
+        } else {
+            SyncReport { name, outcome: clone_repo(manifest, repo), drift: Vec::new() }
+        };
+
+        on_repo(&report);
+        reports.push(report);
+    }
+
+    let listed: Vec<PathBuf> = manifest
+        .repos
+        .iter()
+        .filter_map(|r| std::fs::canonicalize(manifest.checkout_path(r)).ok())
+        .collect();
+    let root = manifest.root.to_string_lossy().into_owned();
+    for extra in walk_repositories(&root).unwrap_or_default() {
+        // The root itself may be a meta-repo holding the manifest
+        if !listed.contains(&extra.path) && extra.path != manifest.root {
+            let report = SyncReport {
+                name: extra.name.clone(),
+                outcome: Ok(SyncAction::Unlisted),
+                drift: vec![format!("at {}", extra.path.display())],
+            };
+            on_repo(&report);
+            reports.push(report);
+        }
+    }
+
+    reports
+}
+
+/// One line per repository, followed by its drift.
+pub fn format_sync_report(report: &SyncReport) -> String {
+    let mut out = match &report.outcome {
+        Ok(SyncAction::Cloned) => format!("   {}:  ✅ cloned\n", report.name),
+        Ok(SyncAction::Fetched) if report.drift.is_empty() => format!("   {}:  ✅ up to date\n", report.name),
+        Ok(SyncAction::Fetched) => format!("   {}:  ⚠️  drift\n", report.name),
+        Ok(SyncAction::Unlisted) => format!("   {}:  ⚠️  not in manifest\n", report.name),
+        Err(e) => format!("   {}:  ❌ {}\n", report.name, e),
+    };
+    for d in &report.drift {
+        out.push_str(&format!("      • {}\n", d));
+    }
+    out
+}
+
+/// Closing line of a sync, and whether every repository cloned or fetched.
+pub fn sync_summary(reports: &[SyncReport]) -> (String, bool) {
+    let failed = reports.iter().filter(|r| r.outcome.is_err()).count();
+    let drifted = reports.iter().filter(|r| !r.drift.is_empty()).count();
+    let line = if failed > 0 {
+        format!("❌ {} repository/repositories failed to sync.", failed)
+    } else if drifted > 0 {
+        format!("⚠️  Synced; {} repository/repositories drift from the manifest.", drifted)
+    } else {
+        "✅ Workspace matches the manifest.".to_string()
+    };
+    (line, failed == 0)
+}
+
+/// `gitlink workspace sync [--manifest <path>]`. Returns false when any
+/// repository failed to clone or fetch.
+pub fn run_workspace_sync(manifest_path: Option<&str>) -> Result<bool, PrpError> {
+    let path = manifest_path.map(PathBuf::from).unwrap_or_else(|| PathBuf::from(MANIFEST_PATH));
+    let manifest = load_manifest(&path)?;
+
+    println!("\n🗂  Syncing {} repositories from {}\n", manifest.repos.len(), path.display());
+    let reports = sync_workspace(&manifest, |r| print!("{}", format_sync_report(r)));
+
+    let (summary, ok) = sync_summary(&reports);
+    println!("\n{}", summary);
+    Ok(ok)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prp_hub::test_support::{bare_remote, commit_file, git, TempDir};
+
+    fn entry(url: &Path, path: &str, branch: Option<&str>) -> ManifestRepo {
+        ManifestRepo {
+            name: None,
+            url: url.to_string_lossy().into_owned(),
+            path: PathBuf::from(path),
+            branch: branch.map(str::to_string),
+        }
+    }
+
+    /// Workspace root `ws` next to the bare remotes `api.git` and `web.git`
+    fn workspace(tmp: &TempDir) -> Manifest {
+        let api = bare_remote(tmp.path(), "api");
+        let web = bare_remote(tmp.path(), "web");
+        let root = tmp.path().join("ws");
+        std::fs::create_dir_all(&root).unwrap();
+        Manifest {
+            repos: vec![entry(&api, "services/api", Some("main")), entry(&web, "web", None)],
+            root,
+        }
+    }
+
+    #[test]
+    fn load_resolves_paths_from_the_workspace_root() {
+        let tmp = TempDir::new();
+        let dir = tmp.path().join(".gitlink");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("manifest.json"), r#"{ "repos": [{ "url": "u", "path": "services/api" }] }"#).unwrap();
+
+        let manifest = load_manifest(&find_manifest(tmp.path()).unwrap()).unwrap();
+
+        assert_eq!(manifest.root, tmp.path());
+        assert_eq!(manifest.repo_name(&manifest.repos[0]), "api");
+        assert_eq!(manifest.checkout_path(&manifest.repos[0]), tmp.path().join("services/api"));
+    }
+
+    #[test]
+    fn sync_clones_then_fetches() {
+        let tmp = TempDir::new();
+        let manifest = workspace(&tmp);
+        assert!(matches!(manifest_repositories(&manifest), Err(PrpError::NoRepositoriesFound)));
+
+        let first = sync_workspace(&manifest, |_| {});
+        assert!(first.iter().all(|r| matches!(r.outcome, Ok(SyncAction::Cloned))), "{:?}", first);
+        assert_eq!(current_branch(&manifest.root.join("services/api")).as_deref(), Some("main"));
+
+        let second = sync_workspace(&manifest, |_| {});
+        assert!(second.iter().all(|r| matches!(r.outcome, Ok(SyncAction::Fetched)) && r.drift.is_empty()), "{:?}", second);
+        assert_eq!(sync_summary(&second), ("✅ Workspace matches the manifest.".to_string(), true));
+
+        let names: Vec<String> = manifest_repositories(&manifest).unwrap().into_iter().map(|r| r.name).collect();
+        assert_eq!(names, vec!["api", "web"]);
+    }
+
+    #[test]
+    fn sync_reports_drift() {
+        let tmp = TempDir::new();
+        let manifest = workspace(&tmp);
+        sync_workspace(&manifest, |_| {});
+
+        let api = manifest.root.join("services/api");
+        git(&api, &["checkout", "-q", "-b", "topic"]);
+        let web = manifest.root.join("web");
+        commit_file(&web, "local.txt", "unpushed");
+        std::fs::write(web.join("scratch.txt"), "dirty").unwrap();
+        git(&manifest.root, &["init", "-q", "stray"]);
+
+        let reports = sync_workspace(&manifest, |_| {});
+
+        let drift = |name: &str| reports.iter().find(|r| r.name == name).unwrap().drift.clone();
+        assert_eq!(drift("api"), vec!["on topic (manifest: main)"]);
+        assert_eq!(drift("web"), vec!["main is 1 ahead, 0 behind origin/main", "uncommitted changes"]);
+        let stray = reports.iter().find(|r| r.name == "stray").unwrap();
+        assert!(matches!(stray.outcome, Ok(SyncAction::Unlisted)));
+        assert_eq!(drift("stray"), vec![format!("at {}", manifest.root.join("stray").display())]);
+        let (summary, ok) = sync_summary(&reports);
+        assert!(ok);
+        assert!(summary.contains("3 repository/repositories drift"));
+    }
+
+    #[test]
+    fn sync_leaves_foreign_directories_alone() {
+        let tmp = TempDir::new();
+        let mut manifest = workspace(&tmp);
+        manifest.repos.push(entry(&tmp.path().join("missing.git"), "missing", None));
+        std::fs::create_dir_all(manifest.root.join("web")).unwrap();
+        std::fs::write(manifest.root.join("web/notes.txt"), "mine").unwrap();
+
+        let reports = sync_workspace(&manifest, |_| {});
+
+        assert!(matches!(reports[0].outcome, Ok(SyncAction::Cloned)));
+        assert!(matches!(&reports[1].outcome, Err(e) if e.contains("is not a git repository")));
+        assert!(reports[2].outcome.is_err());
+        assert!(manifest.root.join("web/notes.txt").exists());
+        assert!(!sync_summary(&reports).1);
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::prp_hub::branch::current_branch;
+use crate::prp_hub::config::{ConventionalConfig, PrpConfig};
+use crate::prp_hub::errors::PrpError;
+use crate::prp_hub::staging::{collect_changes, ChangeKind, FileChange, RepoSelection};
+use crate::prp_hub::types::RepositoryInfo;
+
+/// Everything needed to build the commit message of one repository
+pub struct MessageInput<'a> {
+    pub info: &'a RepositoryInfo,
+    /// Replaces the shared message for this repository only
+    pub override_message: Option<&'a str>,
+    pub selection: Option<&'a RepoSelection>,
+}
+
+/// Fill in `{message}`, `{repo}`, `{branch}` and `{summary}`.
+pub fn render_template(template: &str, message: &str, repo: &str, branch: &str, summary: &str) -> String {
+    template
+        .replace("{message}", message)
+        .replace("{repo}", repo)
+        .replace("{branch}", branch)
+        .replace("{summary}", summary)
+}
+
+/// Short description of what will be committed, e.g. "2 modified, 1 added".
+/// With a selection only the ticked files count.
+pub fn change_summary(changes: &[FileChange], selection: Option<&RepoSelection>) -> String {
+    let picked = |c: &FileChange| match selection {
+        Some(sel) => sel.files.iter().any(|f| f.path == c.path && f.included),
+        None => true,
+    };
+
+    let mut counts: Vec<(ChangeKind, usize)> = Vec::new();
+    for c in changes.iter().filter(|c| picked(c)) {
+        match counts.iter_mut().find(|(k, _)| *k == c.kind) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((c.kind, 1)),
+        }
+    }
+
+    if counts.is_empty() {
+        return "no changes".to_string();
+    }
+    counts
+        .iter()
+        .map(|(k, n)| format!("{} {}", n, k.label()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// The trailer block of a PRP commit: Group-ID first, then the configured
+/// trailers with {name}, {email}, {repo} and {branch} filled in. Trailers that
+/// render empty or repeat an earlier line are dropped.
+pub fn trailer_block(group_id: &str, templates: &[String], name: &str, email: &str, repo: &str, branch: &str) -> String {
+    let mut lines = vec![format!("Group-ID: {}", group_id)];
+    for template in templates {
+        let line = template
+            .replace("{name}", name)
+            .replace("{email}", email)
+            .replace("{repo}", repo)
+            .replace("{branch}", branch);
+        let line = line.trim();
+        let has_value = line.split_once(':').map(|(_, v)| !v.trim().is_empty()).unwrap_or(false);
+        if has_value && !lines.iter().any(|l| l == line) {
+            lines.push(line.to_string());
+        }
+    }
+    lines.join("\n")
+}
+
+/// Check a message against Conventional Commits:
+/// `type(scope)!: description`, plus an optional `BREAKING CHANGE:` footer.
+pub fn validate_conventional(message: &str, rules: &ConventionalConfig) -> Result<(), String> {
+    if !rules.enabled {
+        return Ok(());
+    }
+
+    let header = message.lines().next().unwrap_or("").trim();
+    let (prefix, description) = header
+        .split_once(": ")
+        .ok_or_else(|| format!("header '{}' is not in the form `type(scope): description`", header))?;
+
+    if description.trim().is_empty() {
+        return Err("description after `type: ` is empty".to_string());
+    }
+
+    let (prefix, breaking) = match prefix.strip_suffix('!') {
+        Some(p) => (p, true),
+        None => (prefix, false),
+    };
+
+    let (kind, scope) = match prefix.split_once('(') {
+        Some((k, rest)) => {
+            let scope = rest
+                .strip_suffix(')')
+                .ok_or_else(|| format!("scope in '{}' is missing its closing ')'", prefix))?;
+            if scope.is_empty() {
+                return Err("scope is empty; drop the parentheses or name a scope".to_string());
+            }
+            (k, Some(scope))
+        }
+        None => (prefix, None),
+    };
+
+    if !rules.types.iter().any(|t| t == kind) {
+        return Err(format!("type '{}' is not one of: {}", kind, rules.types.join(", ")));
+    }
+
+    match scope {
+        None if rules.require_scope => return Err(format!("a scope is required, e.g. `{}(core): ...`", kind)),
+        Some(s) if !rules.scopes.is_empty() && !rules.scopes.iter().any(|a| a == s) => {
+            return Err(format!("scope '{}' is not one of: {}", s, rules.scopes.join(", ")));
+        }
+        _ => {}
+    }
+
+    let footers: Vec<&str> = message.lines().skip(1).map(|l| l.trim()).collect();
+    if let Some(bad) = footers.iter().find(|l| {
+        let lower = l.to_lowercase();
+        (lower.starts_with("breaking change:") || lower.starts_with("breaking-change:"))
+            && !(l.starts_with("BREAKING CHANGE:") || l.starts_with("BREAKING-CHANGE:"))
+    }) {
+        return Err(format!("footer '{}' must be written as `BREAKING CHANGE:`", bad));
+    }
+
+    let has_footer = footers
+        .iter()
+        .any(|l| l.starts_with("BREAKING CHANGE:") || l.starts_with("BREAKING-CHANGE:"));
+    if breaking && rules.require_breaking_footer && !has_footer {
+        return Err("`!` marks a breaking change but there is no `BREAKING CHANGE:` footer".to_string());
+    }
+
+    Ok(())
+}
+
+/// Build and validate the final message for every repository before anything
+/// is written. All rejections are returned together.
+pub fn prepare_messages(
+    inputs: &[MessageInput],
+    base: &str,
+    config: &PrpConfig,
+) -> Result<HashMap<PathBuf, String>, Vec<PrpError>> {
+    let mut messages = HashMap::new();
+    let mut errors = Vec::new();
+
+    for input in inputs {
+        let info = input.info;
+        let message = input.override_message.unwrap_or(base).trim();
+
+        let rendered = match config.template_for(&info.path) {
+            Some(template) => {
+                let branch = current_branch(&info.path).unwrap_or_else(|| "HEAD".to_string());
+                let summary = change_summary(&collect_changes(info), input.selection);
+                render_template(template, message, &info.name, &branch, &summary)
+            }
+            None => message.to_string(),
+        };
+
+        match validate_conventional(&rendered, config.conventional_for(&info.path)) {
+            Ok(()) => {
+                messages.insert(info.path.clone(), rendered);
+            }
+            Err(reason) => errors.push(PrpError::InvalidMessage {
+                repo: info.name.clone(),
+                reason,
+            }),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(messages)
+    } else {
+        Err(errors)
+    }
+}
@@ -8,30 +8,53 @@ pub mod rollback;
 pub mod group;
 pub mod status;
 pub mod config;
-
-use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
+pub mod journal;
+pub mod branch;
+pub mod pull_request;
+pub mod staging;
+pub mod message;
+pub mod signing;
+pub mod workspace;
+pub mod manifest;
+pub mod exec;
+pub mod submodule;
+pub mod dependencies;
+pub mod checks;
+pub mod pull;
+pub mod upstream;
+pub mod tag;
+pub mod headless;
+#[cfg(test)]
+mod test_support;
+
+use std::collections::HashMap;
+
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, MultiSelect, Select};
 
 use crate::prp_hub::{
+    checks::{describe_result, run_all_checks},
     commit::commit_all,
     config::{is_excluded, load_config},
+    dependencies::plan_order,
     discovery::discover_repositories,
     errors::PrpError,
     group::list_groups,
-    push::push_all,
-    rollback::rollback_all,
+    journal::{push_recorded, record_reverted, SessionJournal, SessionPhase},
+    pull::{format_pull_report, pull_all, pull_summary},
+    push::{preflight_all, print_push_progress},
+    rollback::{revert_partial_push, rollback_all},
     state::validate_repo,
+    message::{prepare_messages, MessageInput},
+    staging::{collect_changes, ChangeKind, RepoSelection},
     status::{collect_status, display_repo_status},
-    types::CommitSession,
+    submodule::describe_link,
+    types::{new_group_id, CommitSession},
+    workspace::workspace_repositories,
 };
 
-// NOTE: Cargo.toml dependencies required:
-//   uuid = { version = "1", features = ["v4"] }
-//   walkdir = "2"
-//   serde_json = "1"
-use uuid::Uuid;
-
-/// Entry point for `gitlink prp start`
-pub fn run_prp_start() -> Result<(), Box<dyn std::error::Error>> {
+/// Entry point for `gitlink prp start`.
+/// With `set`, the session covers that named workspace instead of the current directory.
+pub fn run_prp_start(set: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
     println!("\n{}", "=".repeat(80));
     println!("🔗 GitLink PRP Hub — Poly-Repo Commit Session");
     println!("{}", "=".repeat(80));
@@ -39,23 +62,37 @@ pub fn run_prp_start() -> Result<(), Box<dyn std::error::Error>> {
     // ──────────────────────────────────────────────────────
     // 1. Discovery — filter out excluded repos
     // ──────────────────────────────────────────────────────
-    println!("\n🔍 Scanning for git repositories...");
-
-    let all_repos = match discover_repositories(".") {
-        Ok(r) => r,
-        Err(PrpError::NoRepositoriesFound) => {
-            println!("\n❌ No git repositories found in the current directory.");
-            return Ok(());
+    let config = load_config();
+    let mut repos = match set {
+        Some(name) => {
+            println!("\n🗂  Resolving workspace '{}'...", name);
+            match workspace_repositories(name) {
+                Ok(r) => r,
+                Err(e) => {
+                    println!("\n❌ {}", e);
+                    return Ok(());
+                }
+            }
+        }
+        None => {
+            println!("\n🔍 Scanning for git repositories...");
+
+            let all_repos = match discover_repositories(".") {
+                Ok(r) => r,
+                Err(PrpError::NoRepositoriesFound) => {
+                    println!("\n❌ No git repositories found in the current directory.");
+                    return Ok(());
+                }
+                Err(e) => return Err(Box::new(e)),
+            };
+
+            all_repos
+                .into_iter()
+                .filter(|r| !is_excluded(&config, &r.path))
+                .collect::<Vec<_>>()
         }
-        Err(e) => return Err(Box::new(e)),
     };
 
-    let config = load_config();
-    let repos: Vec<_> = all_repos
-        .into_iter()
-        .filter(|r| !is_excluded(&config, &r.path))
-        .collect();
-
     if repos.is_empty() {
         println!("\n❌ No active repositories (all are excluded). Use `gitlink prp list` to re-add repos.");
         return Ok(());
@@ -103,6 +140,21 @@ pub fn run_prp_start() -> Result<(), Box<dyn std::error::Error>> {
 
     println!("✅ All repositories are in a valid state.");
 
+    // Optional: commit on top of the latest upstream, not a stale branch
+    if config.pull.before_commit {
+        println!("\n⬇️  Pulling from upstream...\n");
+        let reports = pull_all(&repos, &config.pull, false);
+        for r in &reports {
+            print!("{}", format_pull_report(r));
+        }
+        let (summary, ok) = pull_summary(&reports);
+        println!("\n{}", summary);
+        if !ok {
+            println!("Aborting. Fix the issues above and try again.");
+            return Ok(());
+        }
+    }
+
     // ──────────────────────────────────────────────────────
     // 3. Collect and display working tree status
     // ──────────────────────────────────────────────────────
@@ -141,6 +193,96 @@ pub fn run_prp_start() -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
+    // Pick files per repository; untracked files start unticked
+    let mut selections = HashMap::new();
+    for (repo, st) in &repo_statuses {
+        if st.is_empty() {
+            continue;
+        }
+        let changes = collect_changes(repo);
+        let items: Vec<String> = changes
+            .iter()
+            .map(|c| format!("{:10} {}", c.kind.label(), c.path))
+            .collect();
+        let defaults: Vec<bool> = changes.iter().map(|c| c.kind != ChangeKind::Untracked).collect();
+
+        let chosen = MultiSelect::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Files to commit in {} (Space to toggle)", repo.name))
+            .items(&items)
+            .defaults(&defaults)
+            .interact()?;
+
+        let mut selection = RepoSelection::default_for(&changes);
+        for (i, file) in selection.files.iter_mut().enumerate() {
+            file.included = chosen.contains(&i);
+        }
+        selections.insert(repo.path.clone(), selection);
+    }
+
+    // Pre-commit checks; a failing repository is blocked unless checks are skipped
+    let to_check: Vec<_> = repos
+        .iter()
+        .filter(|r| selections.contains_key(&r.path) && !config.checks_for(&r.path).is_empty())
+        .cloned()
+        .collect();
+    if !to_check.is_empty() {
+        println!("\n🧪 Running pre-commit checks...\n");
+        let results = run_all_checks(&to_check, &config);
+        for repo in &results {
+            let line: Vec<String> = repo.results.iter().map(describe_result).collect();
+            println!("  {} — {}", repo.name, line.join("   "));
+        }
+
+        let failing: Vec<_> = results.iter().filter(|r| !r.passed()).collect();
+        if !failing.is_empty() {
+            println!();
+            for e in failing.iter().flat_map(|r| r.failures()) {
+                println!("{}\n", e);
+            }
+            let choice = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Pre-commit checks failed")
+                .items(&[
+                    "Abort",
+                    "Leave the failing repositories out of this session",
+                    "Skip the checks and commit anyway",
+                ])
+                .default(0)
+                .interact()?;
+            match choice {
+                1 => {
+                    for r in &failing {
+                        selections.remove(&r.path);
+                    }
+                    repos.retain(|info| !failing.iter().any(|r| r.path == info.path));
+                    if selections.is_empty() {
+                        println!("\nℹ️  Nothing left to commit. No commits were made.");
+                        return Ok(());
+                    }
+                }
+                2 => {
+                    let sure = Confirm::with_theme(&ColorfulTheme::default())
+                        .with_prompt(format!(
+                            "Commit {} repository/repositories with failing checks?",
+                            failing.len()
+                        ))
+                        .default(false)
+                        .interact()?;
+                    if !sure {
+                        println!("\nℹ️  Aborted. No commits were made.");
+                        return Ok(());
+                    }
+                    println!("⚠️  Checks skipped for: {}", failing.iter().map(|r| r.name.as_str()).collect::<Vec<_>>().join(", "));
+                }
+                _ => {
+                    println!("\nℹ️  Aborted. No commits were made.");
+                    return Ok(());
+                }
+            }
+        } else {
+            println!("\n✅ All checks passed.");
+        }
+    }
+
     // ──────────────────────────────────────────────────────
     // 5. Prompt for commit message
     // ──────────────────────────────────────────────────────
@@ -153,13 +295,68 @@ pub fn run_prp_start() -> Result<(), Box<dyn std::error::Error>> {
         return Ok(());
     }
 
+    // Apply templates and commit-lint rules before anything is written
+    let inputs: Vec<MessageInput> = repos
+        .iter()
+        .map(|info| MessageInput {
+            info,
+            override_message: None,
+            selection: selections.get(&info.path),
+        })
+        .collect();
+    let messages = match prepare_messages(&inputs, commit_message.trim(), &config) {
+        Ok(m) => m,
+        Err(errors) => {
+            println!("\n❌ Commit message rejected:\n");
+            for e in &errors {
+                println!("{}\n", e);
+            }
+            println!("Aborting. No commits were made.");
+            return Ok(());
+        }
+    };
+    if messages.values().any(|m| m != commit_message.trim()) {
+        println!("\n📝 Messages per repository:\n");
+        for info in &repos {
+            if let Some(m) = messages.get(&info.path) {
+                println!("  {} — {}", info.name, m.lines().next().unwrap_or(""));
+            }
+        }
+    }
+
+    let signed: Vec<String> = repos
+        .iter()
+        .filter_map(|info| signing::signing_label(&info.path).map(|l| format!("{} ({})", info.name, l)))
+        .collect();
+    if !signed.is_empty() {
+        println!("\n🔏 Signed commits: {}", signed.join(", "));
+    }
+
+    // Libraries go before their consumers, for committing and pushing alike
+    let plan = match plan_order(&repos, &config) {
+        Ok(plan) => plan,
+        Err(e) => {
+            println!("{}", e);
+            println!("\nAborting. No commits were made.");
+            return Ok(());
+        }
+    };
+    if !plan.dependencies.is_empty() {
+        println!("\n🔀 Commit and push order: {}", plan.order_line());
+        for line in plan.describe() {
+            println!("   • {}", line);
+        }
+    }
+
     // ──────────────────────────────────────────────────────
     // 6. Generate session group ID
     // ──────────────────────────────────────────────────────
-    let group_id = format!("gitlink-{}", Uuid::new_v4());
+    let group_id = new_group_id();
     println!("\n🆔 Session Group-ID: {}", group_id);
 
-    let mut session = CommitSession::new(group_id.clone(), repos.clone());
+    let mut session = CommitSession::new(group_id.clone(), plan.order);
+    session.selections = selections;
+    session.messages = messages;
 
     // ──────────────────────────────────────────────────────
     // 7. Commit phase
@@ -170,6 +367,9 @@ pub fn run_prp_start() -> Result<(), Box<dyn std::error::Error>> {
         println!("\n❌ Commit failed: {}", e);
         println!("\n🔄 Rolling back committed repositories...\n");
         rollback_all(&session.committed);
+        if !session.committed.is_empty() {
+            let _ = SessionJournal::from_session(&session, commit_message.trim(), SessionPhase::RolledBack).save();
+        }
         println!("\n✅ Rollback complete. No partial commits remain.");
         return Ok(());
     }
@@ -183,6 +383,13 @@ pub fn run_prp_start() -> Result<(), Box<dyn std::error::Error>> {
         "\n✅ Successfully committed in {} repository/repositories.",
         session.committed.len()
     );
+    for link in &session.gitlink_bumps {
+        println!("🔗 Submodule pointer updated: {}", describe_link(link, &session.repositories));
+    }
+
+    // Recorded from here on so an interrupted session can be resumed or undone
+    let mut journal = SessionJournal::from_session(&session, commit_message.trim(), SessionPhase::Committed);
+    let _ = journal.save();
 
     // ──────────────────────────────────────────────────────
     // 8. Push prompt
@@ -195,22 +402,86 @@ pub fn run_prp_start() -> Result<(), Box<dyn std::error::Error>> {
         .interact()?;
 
     if push_choice == 1 {
-        println!("\n🚀 Pushing repositories...\n");
+        let committed_repos = session.committed_repos();
 
-        let committed_repos: Vec<_> = repos
-            .iter()
-            .filter(|r| session.committed.iter().any(|c| c.path == r.path))
-            .cloned()
-            .collect();
+        // Phase 1: check every remote before anything leaves the machine
+        println!("\n🛫 Checking remotes (dry-run)...\n");
 
-        if let Err(e) = push_all(&committed_repos) {
-            println!("\n❌ Push failed: {}", e);
-            println!("\n🔄 Rolling back all committed repositories...\n");
+        let (ready, preflight_errors) = preflight_all(&committed_repos);
+        for p in &ready {
+            println!("  ✅ {} — {} commit(s) ahead on '{}'", p.repo.name, p.ahead, p.branch);
+        }
+
+        if !preflight_errors.is_empty() {
+            println!("\n❌ One or more remotes cannot accept the push:\n");
+            for e in &preflight_errors {
+                println!("{}\n", e);
+            }
+            println!("🔄 Nothing was pushed. Rolling back all committed repositories...\n");
             rollback_all(&session.committed);
+            journal.phase = SessionPhase::RolledBack;
+            let _ = journal.save();
             println!("\n✅ Rollback complete.");
             return Ok(());
         }
 
+        // Phase 2: push, then recover if a push fails half-way
+        println!("\n🚀 Pushing repositories...\n");
+
+        let mut report = push_recorded(&mut journal, &committed_repos, print_push_progress);
+
+        while let Some((failed, e)) = &report.failed {
+            println!("\n❌ Push failed: {}", e);
+            println!(
+                "\n⚠️  {} pushed, '{}' failed, {} not attempted.",
+                report.pushed.len(),
+                failed.name,
+                report.remaining.len()
+            );
+
+            let recovery_options = vec![
+                "Retry the remaining pushes",
+                "Revert already-pushed repos (revert commits) and roll back the rest",
+                "Record partial state in the session journal and stop",
+            ];
+            let recovery = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("How do you want to recover?")
+                .items(&recovery_options)
+                .default(0)
+                .interact()?;
+
+            match recovery {
+                0 => {
+                    println!("\n🔁 Retrying...\n");
+                    let retry = push_recorded(&mut journal, &report.unpushed(), print_push_progress);
+                    report.absorb(retry);
+                }
+                1 => {
+                    println!("\n🔄 Reverting session...\n");
+                    let outcomes = revert_partial_push(&session, &report);
+                    for (name, outcome) in &outcomes {
+                        match outcome {
+                            Ok(what) => println!("  ↩️  {} ... ✅ {}", name, what),
+                            Err(e) => println!("  ↩️  {} ... ❌ {}", name, e),
+                        }
+                    }
+                    record_reverted(&mut journal, &outcomes);
+                    println!("\n✅ Revert complete.");
+                    return Ok(());
+                }
+                _ => {
+                    match journal.save() {
+                        Ok(path) => println!(
+                            "\n📒 Partial push recorded in {}. Finish it later with /prp resume.",
+                            path.display()
+                        ),
+                        Err(e) => println!("\n❌ Could not write session journal: {}", e),
+                    }
+                    return Ok(());
+                }
+            }
+        }
+
         println!("\n✅ All repositories pushed successfully.");
     } else {
         println!("\nℹ️  Push skipped. Your commits are local.");
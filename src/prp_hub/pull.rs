@@ -0,0 +1,329 @@
+use std::path::Path;
+use std::process::Command;
+
+use git2::Repository;
+use rayon::prelude::*;
+
+use crate::prp_hub::config::{load_config, PullConfig};
+use crate::prp_hub::errors::PrpError;
+use crate::prp_hub::types::RepositoryInfo;
+use crate::prp_hub::upstream::resolve_upstream;
+
+const STASH_MESSAGE: &str = "gitlink prp pull";
+
+/// Where a branch stands against its upstream after fetching
+#[derive(Debug, Clone)]
+pub struct PullStatus {
+    pub branch: String,
+    pub upstream: String,
+    pub ahead: usize,
+    pub behind: usize,
+    pub dirty: bool,
+}
+
+/// What a pull did (or, when only checking, would need to do)
+#[derive(Debug, Clone, PartialEq)]
+pub enum PullAction {
+    UpToDate,
+    /// Only local commits; nothing to pull
+    Ahead,
+    Behind,
+    Diverged,
+    FastForwarded,
+    Rebased,
+}
+
+#[derive(Debug)]
+pub struct PullReport {
+    pub name: String,
+    pub status: Option<PullStatus>,
+    pub outcome: Result<PullAction, PrpError>,
+    /// Uncommitted changes were stashed and put back
+    pub stashed: bool,
+}
+
+fn git(args: &[&str], dir: &Path) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        // Never stop for a password prompt; report the failure instead
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .map_err(|e| format!("Cannot run git: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        return Err(if stderr.is_empty() { stdout } else { stderr });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Fetch the repository's upstream and compare the current branch with it.
+pub fn fetch_status(info: &RepositoryInfo) -> Result<PullStatus, PrpError> {
+    let fail = |reason: String, fix: &str| PrpError::PullFailed {
+        repo: info.name.clone(),
+        reason,
+        fix: fix.to_string(),
+    };
+
+    let repo = Repository::open(&info.path)
+        .map_err(|e| fail(format!("Cannot open repository: {}", e), "Ensure the path is a valid git repository"))?;
+    let head = repo
+        .head()
+        .map_err(|e| fail(format!("Cannot read HEAD: {}", e), "Ensure you are on a branch"))?;
+    if !head.is_branch() {
+        return Err(fail("HEAD is detached".to_string(), "Run `git checkout <branch>`"));
+    }
+    let branch = head.shorthand().unwrap_or("").to_string();
+    let local = head
+        .target()
+        .ok_or_else(|| fail("HEAD does not point to a commit".to_string(), "Run `git status` to inspect"))?;
+
+    let target = resolve_upstream(&repo, info, &branch, &load_config());
+    git(&["fetch", "--quiet", &target.remote], &info.path)
+        .map_err(|e| fail(format!("Fetch from '{}' failed: {}", target.remote, e), "Check the remote connection and credentials"))?;
+
+    let upstream = target.short_name();
+    let remote = repo.refname_to_id(&target.tracking_ref()).map_err(|_| {
+        fail(
+            format!("No upstream tracking branch found for '{}' (expected {})", branch, upstream),
+            &format!("Run `git push --set-upstream {} {}` first", target.remote, branch),
+        )
+    })?;
+    let (ahead, behind) = repo
+        .graph_ahead_behind(local, remote)
+        .map_err(|e| fail(format!("Cannot compare with {}: {}", upstream, e), "Run `git fetch` and retry"))?;
+
+    let dirty = repo
+        .statuses(None)
+        .map(|s| s.iter().any(|e| !e.status().is_ignored()))
+        .unwrap_or(false);
+
+    Ok(PullStatus { branch, upstream, ahead, behind, dirty })
+}
+
+// Commit `refs/stash` points to, if there is any stash
+fn stash_tip(dir: &Path) -> Option<String> {
+    git(&["rev-parse", "--quiet", "--verify", "refs/stash"], dir).ok()
+}
+
+// Put stashed changes back. If they clash with what was pulled, return the
+// branch to `orig` and restore them there, so the repository ends up as before.
+fn restore_stash(info: &RepositoryInfo, orig: &str) -> Result<(), PrpError> {
+    if git(&["stash", "pop"], &info.path).is_ok() {
+        return Ok(());
+    }
+
+    let undone = git(&["reset", "--hard", orig], &info.path)
+        .and_then(|_| git(&["clean", "-fd"], &info.path))
+        .and_then(|_| git(&["stash", "pop"], &info.path));
+    Err(PrpError::PullFailed {
+        repo: info.name.clone(),
+        reason: "Uncommitted changes conflict with the pulled commits".to_string(),
+        fix: match undone {
+            Ok(_) => "The pull was undone. Commit or stash your changes, then pull and resolve the conflicts manually".to_string(),
+            Err(_) => format!(
+                "Your changes are kept in `git stash list` (\"{}\"); restore them with `git stash pop` after resolving",
+                STASH_MESSAGE
+            ),
+        },
+    })
+}
+
+/// Bring the branch up to date with its upstream: fast-forward, or rebase
+/// local commits when `options.rebase` is set. Uncommitted changes are stashed
+/// around the update when `options.autostash` is set. Any conflict aborts and
+/// leaves the repository as it was. Returns the action and whether it stashed.
+pub fn update_repo(info: &RepositoryInfo, status: &PullStatus, options: &PullConfig) -> (Result<PullAction, PrpError>, bool) {
+    let fail = |reason: String, fix: &str| PrpError::PullFailed {
+        repo: info.name.clone(),
+        reason,
+        fix: fix.to_string(),
+    };
+
+    if status.behind == 0 {
+        let action = if status.ahead > 0 { PullAction::Ahead } else { PullAction::UpToDate };
+        return (Ok(action), false);
+    }
+    if status.ahead > 0 && !options.rebase {
+        return (
+            Err(fail(
+                format!(
+                    "'{}' has diverged from {} ({} ahead, {} behind)",
+                    status.branch, status.upstream, status.ahead, status.behind
+                ),
+                "Pull with --rebase (or set pull.rebase in .gitlink/prp_config.json), or merge manually",
+            )),
+            false,
+        );
+    }
+    if status.dirty && !options.autostash {
+        return (
+            Err(fail(
+                "Uncommitted changes".to_string(),
+                "Commit or stash them, or set pull.autostash in .gitlink/prp_config.json",
+            )),
+            false,
+        );
+    }
+
+    let orig = match git(&["rev-parse", "HEAD"], &info.path) {
+        Ok(o) => o,
+        Err(e) => return (Err(fail(format!("Cannot read HEAD: {}", e), "Run `git status` to inspect")), false),
+    };
+
+    let mut stashed = false;
+    if status.dirty {
+        let before = stash_tip(&info.path);
+        if let Err(e) = git(&["stash", "push", "--include-untracked", "-m", STASH_MESSAGE], &info.path) {
+            return (Err(fail(format!("Cannot stash changes: {}", e), "Commit or stash them manually")), false);
+        }
+        // With nothing to save the push still succeeds; only a new stash is ours to pop
+        stashed = stash_tip(&info.path).is_some_and(|tip| before.as_ref() != Some(&tip));
+    }
+
+    let (action, updated) = if status.ahead == 0 {
+        (PullAction::FastForwarded, git(&["merge", "--ff-only", &status.upstream], &info.path))
+    } else {
+        let result = git(&["rebase", &status.upstream], &info.path);
+        if result.is_err() {
+            let _ = git(&["rebase", "--abort"], &info.path);
+        }
+        (PullAction::Rebased, result)
+    };
+
+    if let Err(e) = updated {
+        let restored = if stashed { restore_stash(info, &orig) } else { Ok(()) };
+        let error = restored.err().unwrap_or_else(|| {
+            fail(
+                format!("Cannot update from {}: {}", status.upstream, e),
+                "Nothing was changed. Pull manually and resolve the conflicts",
+            )
+        });
+        return (Err(error), stashed);
+    }
+
+    if stashed {
+        if let Err(e) = restore_stash(info, &orig) {
+            return (Err(e), true);
+        }
+    }
+    (Ok(action), stashed)
+}
+
+/// Fetch every repository in parallel and report ahead/behind. Unless
+/// `check_only`, each one is also brought up to date. Reports keep the input order.
+pub fn pull_all(repos: &[RepositoryInfo], options: &PullConfig, check_only: bool) -> Vec<PullReport> {
+    repos
+        .par_iter()
+        .map(|info| {
+            let status = match fetch_status(info) {
+                Ok(s) => s,
+                Err(e) => return PullReport { name: info.name.clone(), status: None, outcome: Err(e), stashed: false },
+            };
+            let (outcome, stashed) = if check_only {
+                let action = match (status.ahead, status.behind) {
+                    (0, 0) => PullAction::UpToDate,
+                    (_, 0) => PullAction::Ahead,
+                    (0, _) => PullAction::Behind,
+                    _ => PullAction::Diverged,
+                };
+                (Ok(action), false)
+            } else {
+                update_repo(info, &status, options)
+            };
+            PullReport { name: info.name.clone(), status: Some(status), outcome, stashed }
+        })
+        .collect()
+}
+
+/// One line per repository; failures are followed by the full error.
+pub fn format_pull_report(report: &PullReport) -> String {
+    let (ahead, behind, branch) = report
+        .status
+        .as_ref()
+        .map(|s| (s.ahead, s.behind, s.branch.as_str()))
+        .unwrap_or((0, 0, ""));
+    let line = match &report.outcome {
+        Ok(PullAction::UpToDate) => "✅ up to date".to_string(),
+        Ok(PullAction::Ahead) => format!("✅ {} ahead, nothing to pull", ahead),
+        Ok(PullAction::Behind) => format!("⚠️  {} behind", behind),
+        Ok(PullAction::Diverged) => format!("⚠️  {} ahead, {} behind", ahead, behind),
+        Ok(PullAction::FastForwarded) => format!("✅ fast-forwarded {} commit(s)", behind),
+        Ok(PullAction::Rebased) => format!("✅ rebased {} local commit(s) onto {} new", ahead, behind),
+        Err(e) => return format!("   {}:  ❌ failed{}\n", report.name, e),
+    };
+    let stash = if report.stashed { ", local changes restored" } else { "" };
+    format!("   {} ({}):  {}{}\n", report.name, branch, line, stash)
+}
+
+/// Closing line of a pull, and whether the repositories are ready to commit on.
+pub fn pull_summary(reports: &[PullReport]) -> (String, bool) {
+    let failed = reports.iter().filter(|r| r.outcome.is_err()).count();
+    let behind = reports
+        .iter()
+        .filter(|r| matches!(r.outcome, Ok(PullAction::Behind) | Ok(PullAction::Diverged)))
+        .count();
+    if failed > 0 {
+        (format!("❌ {} repository/repositories could not be pulled.", failed), false)
+    } else if behind > 0 {
+        (format!("⚠️  {} repository/repositories behind upstream — run /prp pull.", behind), false)
+    } else {
+        ("✅ All repositories are up to date with upstream.".to_string(), true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prp_hub::test_support::{bare_remote, clone, commit_file, git as run, TempDir};
+
+    /// `api` one commit behind its remote, which `other` pushed to
+    fn behind_by_one(tmp: &TempDir) -> RepositoryInfo {
+        let remote = bare_remote(tmp.path(), "api");
+        let api = clone(&remote, tmp.path(), "api");
+        let other = clone(&remote, tmp.path(), "other");
+        commit_file(&other.path, "upstream.txt", "new");
+        run(&other.path, &["push", "-q", "origin", "main"]);
+        api
+    }
+
+    #[test]
+    fn autostash_leaves_an_earlier_user_stash_alone() {
+        let tmp = TempDir::new();
+        let api = behind_by_one(&tmp);
+        std::fs::write(api.path.join("README.md"), "user work").unwrap();
+        run(&api.path, &["stash", "push", "-q", "-m", "user stash"]);
+        let status = fetch_status(&api).unwrap();
+        // Seen as dirty by an earlier check, but clean by the time of the update
+        let status = PullStatus { dirty: true, ..status };
+
+        let (outcome, stashed) = update_repo(&api, &status, &PullConfig::default());
+
+        assert_eq!(outcome.unwrap(), PullAction::FastForwarded);
+        assert!(!stashed);
+        assert!(run(&api.path, &["stash", "list"]).contains("user stash"));
+        assert_eq!(run(&api.path, &["status", "--porcelain"]), "");
+    }
+
+    #[test]
+    fn autostash_restores_only_its_own_stash() {
+        let tmp = TempDir::new();
+        let api = behind_by_one(&tmp);
+        std::fs::write(api.path.join("README.md"), "user work").unwrap();
+        run(&api.path, &["stash", "push", "-q", "-m", "user stash"]);
+        std::fs::write(api.path.join("local.txt"), "in progress").unwrap();
+        let status = fetch_status(&api).unwrap();
+
+        let (outcome, stashed) = update_repo(&api, &status, &PullConfig::default());
+
+        assert_eq!(outcome.unwrap(), PullAction::FastForwarded);
+        assert!(stashed);
+        assert!(api.path.join("upstream.txt").exists());
+        assert_eq!(run(&api.path, &["status", "--porcelain"]), "?? local.txt");
+        let stashes = run(&api.path, &["stash", "list"]);
+        assert_eq!(stashes.lines().count(), 1);
+        assert!(stashes.contains("user stash"));
+    }
+}
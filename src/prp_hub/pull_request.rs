@@ -0,0 +1,246 @@
+use git2::Repository;
+
+use crate::github::pulls_client::{parse_github_remote, PullsClient};
+use crate::prp_hub::branch::{current_branch, default_branch};
+use crate::prp_hub::config::{load_config, PrpConfig};
+use crate::prp_hub::errors::PrpError;
+use crate::prp_hub::journal::{JournalRepo, PullRequestLink, SessionJournal};
+use crate::prp_hub::types::{RepoOutcomes, RepositoryInfo};
+use crate::prp_hub::upstream::resolve_upstream;
+
+/// Split a session message into the PR title (first line) and description
+/// (the rest, without the Group-ID trailer).
+pub fn split_message(message: &str) -> (String, String) {
+    let mut lines = message.lines();
+    let title = lines.next().unwrap_or("").trim().to_string();
+    let description = lines
+        .filter(|l| !l.trim_start().starts_with("Group-ID:"))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string();
+    (title, description)
+}
+
+/// Markdown table linking every repository of the group to its pull request.
+/// `current` marks the row of the PR the table is rendered into.
+pub fn cross_reference_table(journal: &SessionJournal, current: Option<&str>) -> String {
+    let mut table = String::from("| Repository | Pull request |\n| --- | --- |\n");
+    for repo in &journal.repos {
+        let cell = match &repo.pull_request {
+            Some(pr) if Some(repo.name.as_str()) == current => {
+                format!("{}/{}#{} (this PR)", pr.owner, pr.repo, pr.number)
+            }
+            Some(pr) => format!("[{}/{}#{}]({})", pr.owner, pr.repo, pr.number, pr.url),
+            None => "_pending_".to_string(),
+        };
+        table.push_str(&format!("| {} | {} |\n", repo.name, cell));
+    }
+    table
+}
+
+fn pr_body(journal: &SessionJournal, description: &str, current: Option<&str>) -> String {
+    let mut body = String::new();
+    if !description.is_empty() {
+        body.push_str(description);
+        body.push_str("\n\n");
+    }
+    body.push_str("### Linked pull requests\n\n");
+    body.push_str(&cross_reference_table(journal, current));
+    body.push_str(&format!("\nGroup-ID: `{}`\n", journal.group_id));
+    body
+}
+
+// Everything needed to open the PR for one repository, resolved locally.
+struct PrTarget {
+    owner: String,
+    repo: String,
+    head: String,
+    base: String,
+}
+
+fn resolve_target(jr: &JournalRepo, config: &PrpConfig) -> Result<PrTarget, PrpError> {
+    let fail = |reason: String| PrpError::PullRequestFailed {
+        repo: jr.name.clone(),
+        reason,
+    };
+
+    if !jr.pushed {
+        return Err(fail("Session commit is not pushed yet; run /prp resume first".to_string()));
+    }
+
+    let repo = Repository::open(&jr.path).map_err(|e| fail(format!("Cannot open repository: {}", e)))?;
+    let local = if jr.branch.is_empty() {
+        current_branch(&jr.path).ok_or_else(|| fail("HEAD is detached".to_string()))?
+    } else {
+        jr.branch.clone()
+    };
+
+    // The head is the branch as it was pushed: same remote and refspec as /prp push
+    let info = RepositoryInfo { name: jr.name.clone(), path: jr.path.clone() };
+    let upstream = resolve_upstream(&repo, &info, &local, config);
+    let github = |remote: &str| {
+        repo.find_remote(remote)
+            .ok()
+            .and_then(|r| r.url().and_then(parse_github_remote))
+    };
+    let (head_owner, head_repo) = github(&upstream.remote)
+        .ok_or_else(|| fail(format!("Remote '{}' is not a GitHub repository", upstream.remote)))?;
+
+    // Pushed to a fork: open the PR on origin and name the fork's owner in the head
+    let (owner, name) = github("origin").unwrap_or_else(|| (head_owner.clone(), head_repo));
+    let head = if head_owner == owner {
+        upstream.branch.clone()
+    } else {
+        format!("{}:{}", head_owner, upstream.branch)
+    };
+    let base = default_branch(&repo)
+        .ok_or_else(|| fail("Cannot determine the default branch".to_string()))?;
+
+    if head == base {
+        return Err(fail(format!(
+            "Session was committed on the default branch '{}'; use /prp branch for PR-based work",
+            base
+        )));
+    }
+
+    Ok(PrTarget { owner, repo: name, head, base })
+}
+
+// Rewrite the body of every PR already opened so its table lists the new sibling.
+async fn refresh_bodies(client: &PullsClient, journal: &SessionJournal, description: &str) -> RepoOutcomes {
+    let mut failures = Vec::new();
+    for repo in &journal.repos {
+        if let Some(pr) = &repo.pull_request {
+            let body = pr_body(journal, description, Some(&repo.name));
+            if let Err(e) = client.update_pull_body(&pr.owner, &pr.repo, pr.number, &body).await {
+                failures.push((
+                    repo.name.clone(),
+                    Err(PrpError::PullRequestFailed {
+                        repo: repo.name.clone(),
+                        reason: format!("Could not update sibling links: {}", e),
+                    }),
+                ));
+            }
+        }
+    }
+    failures
+}
+
+/// Open a pull request in every repository of the group that does not have one
+/// yet. Each PR gets the shared title and description plus a table of its
+/// siblings; earlier PRs are updated as later ones are created. The journal is
+/// saved after every PR so a failure part-way can simply be re-run.
+pub async fn open_group_prs(client: &PullsClient, journal: &mut SessionJournal) -> RepoOutcomes {
+    let (title, description) = split_message(&journal.message);
+    let config = load_config();
+    let mut outcomes = Vec::new();
+
+    for i in 0..journal.repos.len() {
+        let name = journal.repos[i].name.clone();
+
+        if let Some(pr) = &journal.repos[i].pull_request {
+            outcomes.push((name, Ok(format!("already open as #{}", pr.number))));
+            continue;
+        }
+
+        let target = match resolve_target(&journal.repos[i], &config) {
+            Ok(t) => t,
+            Err(e) => {
+                outcomes.push((name, Err(e)));
+                continue;
+            }
+        };
+
+        // Row for this repo is still pending; refresh_bodies fills in the number once created
+        let body = pr_body(journal, &description, Some(&name));
+        let created = client
+            .create_pull(&target.owner, &target.repo, &title, &body, &target.head, &target.base)
+            .await;
+
+        match created {
+            Ok(pull) => {
+                journal.repos[i].pull_request = Some(PullRequestLink {
+                    owner: target.owner.clone(),
+                    repo: target.repo.clone(),
+                    number: pull.number,
+                    url: pull.html_url.clone(),
+                });
+                let _ = journal.save();
+                outcomes.push((
+                    name,
+                    Ok(format!("opened #{} ({} → {}) {}", pull.number, target.head, target.base, pull.html_url)),
+                ));
+                outcomes.extend(refresh_bodies(client, journal, &description).await);
+            }
+            Err(e) => outcomes.push((
+                name.clone(),
+                Err(PrpError::PullRequestFailed { repo: name, reason: e.to_string() }),
+            )),
+        }
+    }
+
+    outcomes
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+    use crate::prp_hub::config::RepoMessageRules;
+    use crate::prp_hub::test_support::{commit_file, git, TempDir};
+
+    // Repository on `feature` with a GitHub origin and a fork remote
+    fn pushed_repo(root: &Path) -> JournalRepo {
+        let path = root.join("widget");
+        std::fs::create_dir_all(&path).unwrap();
+        git(&path, &["init", "-q"]);
+        commit_file(&path, "README.md", "widget");
+        git(&path, &["checkout", "-q", "-b", "feature"]);
+        git(&path, &["remote", "add", "origin", "https://github.com/acme/widget.git"]);
+        git(&path, &["remote", "add", "fork", "git@github.com:me/widget.git"]);
+        JournalRepo {
+            name: "widget".to_string(),
+            path,
+            commit_oid: String::new(),
+            pushed: true,
+            undone: false,
+            branch: "feature".to_string(),
+            pull_request: None,
+        }
+    }
+
+    #[test]
+    fn head_is_the_local_branch_on_origin_by_default() {
+        let root = TempDir::new();
+        let jr = pushed_repo(root.path());
+
+        let target = resolve_target(&jr, &PrpConfig::default()).unwrap();
+
+        assert_eq!((target.owner.as_str(), target.repo.as_str()), ("acme", "widget"));
+        assert_eq!(target.head, "feature");
+        assert_eq!(target.base, "main");
+    }
+
+    #[test]
+    fn head_follows_the_configured_remote_and_refspec() {
+        let root = TempDir::new();
+        let jr = pushed_repo(root.path());
+        let mut config = PrpConfig::default();
+        config.repo_rules.push(RepoMessageRules {
+            path: jr.path.clone(),
+            template: None,
+            conventional: None,
+            trailers: Vec::new(),
+            checks: Vec::new(),
+            remote: Some("fork".to_string()),
+            refspec: Some("{branch}:refs/heads/review/{branch}".to_string()),
+        });
+
+        let target = resolve_target(&jr, &config).unwrap();
+
+        assert_eq!((target.owner.as_str(), target.repo.as_str()), ("acme", "widget"));
+        assert_eq!(target.head, "me:review/feature");
+    }
+}
@@ -1,13 +1,18 @@
 use std::process::Command;
 
+use git2::Repository;
+
+use crate::prp_hub::config::load_config;
 use crate::prp_hub::errors::PrpError;
-use crate::prp_hub::types::RepositoryInfo;
+use crate::prp_hub::types::{PushPreflight, PushReport, RepositoryInfo};
+use crate::prp_hub::upstream::{current_upstream, resolve_upstream};
 
-/// Push a single repository using a shell `git push`.
+/// Push a single repository to its resolved upstream using a shell `git push`.
 /// Shell is used intentionally for better SSH/credential-helper compatibility.
 pub fn push_repo(info: &RepositoryInfo) -> Result<(), PrpError> {
+    let upstream = current_upstream(info)?;
     let output = Command::new("git")
-        .arg("push")
+        .args(["push", &upstream.remote, &upstream.refspec])
         .current_dir(&info.path)
         .output()
         .map_err(|e| PrpError::PushFailed {
@@ -26,13 +31,192 @@ pub fn push_repo(info: &RepositoryInfo) -> Result<(), PrpError> {
     Ok(())
 }
 
-/// Push all repositories sequentially. Stops on first failure.
-/// Returns the name of the repo that failed, if any.
-pub fn push_all(repos: &[RepositoryInfo]) -> Result<(), PrpError> {
+/// Phase one of a push: check that a repository can be pushed without pushing it.
+/// Compares the branch against its upstream (the remote must not be ahead) and
+/// runs `git push --dry-run`, which also surfaces auth and permission problems.
+pub fn preflight_repo(info: &RepositoryInfo) -> Result<PushPreflight, PrpError> {
+    let fail = |reason: String, fix: String| PrpError::PreflightFailed {
+        repo: info.name.clone(),
+        reason,
+        fix,
+    };
+
+    let repo = Repository::open(&info.path).map_err(|e| {
+        fail(
+            format!("Cannot open repository: {}", e),
+            "Ensure the path is a valid git repository".to_string(),
+        )
+    })?;
+
+    let head = repo.head().map_err(|e| {
+        fail(format!("Cannot read HEAD: {}", e), "Ensure you are on a branch".to_string())
+    })?;
+    let branch = head.shorthand().unwrap_or("").to_string();
+    let local_oid = head.target().ok_or_else(|| {
+        fail("HEAD does not point to a commit".to_string(), "Run `git status` to inspect".to_string())
+    })?;
+
+    let upstream = resolve_upstream(&repo, info, &branch, &load_config());
+    let remote_oid = repo.refname_to_id(&upstream.tracking_ref()).map_err(|_| {
+        fail(
+            format!("No upstream tracking branch found for '{}' (expected {})", branch, upstream.short_name()),
+            format!("Run `git push --set-upstream {} {}` first", upstream.remote, branch),
+        )
+    })?;
+
+    let (ahead, behind) = repo.graph_ahead_behind(local_oid, remote_oid).map_err(|e| {
+        fail(format!("Cannot compare with upstream: {}", e), "Run `git fetch` and retry".to_string())
+    })?;
+
+    if behind > 0 {
+        return Err(fail(
+            format!("{} is {} commit(s) ahead of local '{}'", upstream.short_name(), behind, branch),
+            "Run `git pull --rebase` and retry".to_string(),
+        ));
+    }
+
+    let output = Command::new("git")
+        .args(["push", "--dry-run", "--porcelain", &upstream.remote, &upstream.refspec])
+        .current_dir(&info.path)
+        .output()
+        .map_err(|e| fail(e.to_string(), "Ensure git is installed and on PATH".to_string()))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        let reason = if stderr.is_empty() { stdout } else { stderr };
+        return Err(fail(
+            format!("Dry-run push rejected: {}", reason),
+            "Check remote permissions, credentials and branch protection".to_string(),
+        ));
+    }
+
+    Ok(PushPreflight {
+        repo: info.clone(),
+        branch,
+        ahead,
+    })
+}
+
+/// Run the remote check for every repository.
+/// Unlike pushing, this does not stop early: all problems are reported at once.
+pub fn preflight_all(repos: &[RepositoryInfo]) -> (Vec<PushPreflight>, Vec<PrpError>) {
+    let mut ready = Vec::new();
+    let mut errors = Vec::new();
     for info in repos {
-        print!("  🚀 {} ... ", info.name);
-        push_repo(info)?;
-        println!("✅ pushed");
+        match preflight_repo(info) {
+            Ok(p) => ready.push(p),
+            Err(e) => errors.push(e),
+        }
     }
-    Ok(())
-}
\ No newline at end of file
+    (ready, errors)
+}
+
+/// Phase two of a push: push repositories in order, stopping at the first failure.
+/// `on_repo` is called after each attempt so callers can report progress.
+pub fn push_repos<F>(repos: &[RepositoryInfo], mut on_repo: F) -> PushReport
+where
+    F: FnMut(&RepositoryInfo, &Result<(), PrpError>),
+{
+    let mut report = PushReport::default();
+
+    for (i, info) in repos.iter().enumerate() {
+        let result = push_repo(info);
+        on_repo(info, &result);
+        match result {
+            Ok(()) => report.pushed.push(info.clone()),
+            Err(e) => {
+                report.failed = Some((info.clone(), e));
+                report.remaining = repos[i + 1..].to_vec();
+                break;
+            }
+        }
+    }
+
+    report
+}
+
+/// Progress printer for the CLI flow, suitable as the `on_repo` callback.
+pub fn print_push_progress(info: &RepositoryInfo, result: &Result<(), PrpError>) {
+    match result {
+        Ok(()) => println!("  🚀 {} ... ✅ pushed", info.name),
+        Err(_) => println!("  🚀 {} ... ❌ failed", info.name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prp_hub::test_support::{bare_remote, clone, commit_file, git, TempDir};
+
+    #[test]
+    fn preflight_counts_commits_ahead() {
+        let tmp = TempDir::new();
+        let remote = bare_remote(tmp.path(), "api");
+        let api = clone(&remote, tmp.path(), "api");
+        commit_file(&api.path, "a.txt", "1");
+        commit_file(&api.path, "b.txt", "2");
+
+        let (ready, errors) = preflight_all(std::slice::from_ref(&api));
+
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(ready[0].branch, "main");
+        assert_eq!(ready[0].ahead, 2);
+        // Only a dry run: the remote has not moved
+        assert_eq!(git(&remote, &["rev-parse", "main"]), git(&api.path, &["rev-parse", "origin/main"]));
+    }
+
+    #[test]
+    fn preflight_reports_every_failing_repo() {
+        let tmp = TempDir::new();
+        let remote = bare_remote(tmp.path(), "api");
+        let api = clone(&remote, tmp.path(), "api");
+        let other = clone(&remote, tmp.path(), "other");
+        commit_file(&other.path, "other.txt", "first");
+        git(&other.path, &["push", "-q", "origin", "main"]);
+        commit_file(&api.path, "api.txt", "behind");
+        git(&api.path, &["fetch", "-q", "origin"]);
+
+        let web_remote = bare_remote(tmp.path(), "web");
+        let web = clone(&web_remote, tmp.path(), "web");
+        git(&web.path, &["checkout", "-q", "-b", "feature"]);
+        commit_file(&web.path, "web.txt", "new");
+
+        let (ready, errors) = preflight_all(&[api, web]);
+
+        assert!(ready.is_empty());
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(&errors[0], PrpError::PreflightFailed { repo, reason, .. }
+            if repo == "api" && reason.contains("ahead of local")));
+        assert!(matches!(&errors[1], PrpError::PreflightFailed { repo, reason, .. }
+            if repo == "web" && reason.contains("No upstream tracking branch")));
+    }
+
+    #[test]
+    fn push_stops_at_the_first_failure() {
+        let tmp = TempDir::new();
+        let remotes: Vec<_> = ["shared", "api", "web"].iter().map(|n| bare_remote(tmp.path(), n)).collect();
+        let repos: Vec<_> = ["shared", "api", "web"]
+            .iter()
+            .zip(&remotes)
+            .map(|(name, remote)| clone(remote, tmp.path(), name))
+            .collect();
+        for repo in &repos {
+            commit_file(&repo.path, "change.txt", &repo.name);
+        }
+        // Someone else pushed to api's remote first, so api's push is rejected
+        let racer = clone(&remotes[1], tmp.path(), "racer");
+        commit_file(&racer.path, "race.txt", "first");
+        git(&racer.path, &["push", "-q", "origin", "main"]);
+
+        let mut attempted = Vec::new();
+        let report = push_repos(&repos, |info, result| attempted.push((info.name.clone(), result.is_ok())));
+
+        assert_eq!(attempted, vec![("shared".to_string(), true), ("api".to_string(), false)]);
+        assert_eq!(report.pushed.len(), 1);
+        assert!(matches!(&report.failed, Some((info, PrpError::PushFailed { .. })) if info.name == "api"));
+        assert_eq!(report.remaining.iter().map(|r| r.name.as_str()).collect::<Vec<_>>(), vec!["web"]);
+        assert_eq!(git(&remotes[0], &["rev-parse", "main"]), git(&repos[0].path, &["rev-parse", "HEAD"]));
+        assert_ne!(git(&remotes[2], &["rev-parse", "main"]), git(&repos[2].path, &["rev-parse", "HEAD"]));
+    }
+}
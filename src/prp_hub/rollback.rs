@@ -1,11 +1,15 @@
-use git2::Repository;
+use std::path::PathBuf;
+
+use git2::{Oid, Repository};
 
 use crate::prp_hub::errors::PrpError;
-use crate::prp_hub::types::RepoCommitResult;
+use crate::prp_hub::push::push_repo;
+use crate::prp_hub::signing::write_commit;
+use crate::prp_hub::types::{CommitSession, PushReport, RepoCommitResult, RepoOutcomes, RepositoryInfo};
 
 /// Roll back a single commit using `git reset --soft HEAD~1`.
 /// Only touches repos that were committed during this session.
-fn rollback_one(result: &RepoCommitResult) -> Result<(), PrpError> {
+pub fn rollback_one(result: &RepoCommitResult) -> Result<(), PrpError> {
     let repo = Repository::open(&result.path).map_err(|e| PrpError::RollbackFailed {
         repo: result.path.display().to_string(),
         reason: e.to_string(),
@@ -58,6 +62,16 @@ fn rollback_one(result: &RepoCommitResult) -> Result<(), PrpError> {
     Ok(())
 }
 
+/// Roll back every commit recorded in this session, newest first, without printing.
+/// Errors are collected per repository and do not stop the loop.
+pub fn rollback_each(committed: &[RepoCommitResult]) -> Vec<(PathBuf, Result<(), PrpError>)> {
+    committed
+        .iter()
+        .rev()
+        .map(|result| (result.path.clone(), rollback_one(result)))
+        .collect()
+}
+
 /// Attempt to roll back all commits recorded in this session.
 /// Prints status for each repo. Errors are reported but do not stop the loop.
 pub fn rollback_all(committed: &[RepoCommitResult]) {
@@ -66,13 +80,103 @@ pub fn rollback_all(committed: &[RepoCommitResult]) {
         return;
     }
 
-    for result in committed.iter().rev() {
-        let label = result.path.display().to_string();
-        print!("  ↩️  {} ... ", label);
-
-        match rollback_one(result) {
-            Ok(()) => println!("✅ rolled back"),
-            Err(e) => println!("❌ {}", e),
+    for (path, outcome) in rollback_each(committed) {
+        match outcome {
+            Ok(()) => println!("  ↩️  {} ... ✅ rolled back", path.display()),
+            Err(e) => println!("  ↩️  {} ... ❌ {}", path.display(), e),
         }
     }
-}
\ No newline at end of file
+}
+
+/// Undo an already-pushed session commit by committing its inverse on top
+/// (`git revert`) and pushing that. History on the remote is never rewritten.
+pub fn revert_pushed(
+    info: &RepositoryInfo,
+    result: &RepoCommitResult,
+    group_id: &str,
+) -> Result<Oid, PrpError> {
+    let fail = |reason: String| PrpError::RollbackFailed {
+        repo: info.name.clone(),
+        reason,
+    };
+
+    let repo = Repository::open(&result.path).map_err(|e| fail(e.to_string()))?;
+
+    let target = repo
+        .find_commit(result.commit_oid)
+        .map_err(|e| fail(format!("Session commit not found: {}", e)))?;
+
+    let head_commit = repo
+        .head()
+        .and_then(|h| h.peel_to_commit())
+        .map_err(|e| fail(format!("Cannot read HEAD: {}", e)))?;
+
+    // Applies the inverse change to both the index and the working tree
+    repo.revert(&target, None)
+        .map_err(|e| fail(format!("Revert failed: {}", e)))?;
+
+    let mut index = repo.index().map_err(|e| fail(format!("Cannot open index: {}", e)))?;
+    if index.has_conflicts() {
+        let _ = repo.cleanup_state();
+        return Err(fail("Revert produced conflicts; resolve manually with `git revert`".to_string()));
+    }
+
+    let tree_oid = index
+        .write_tree()
+        .map_err(|e| fail(format!("Cannot write tree: {}", e)))?;
+    let tree = repo
+        .find_tree(tree_oid)
+        .map_err(|e| fail(format!("Cannot find tree: {}", e)))?;
+
+    let sig = repo
+        .signature()
+        .map_err(|e| fail(format!("Cannot read git config signature: {}", e)))?;
+
+    let message = format!(
+        "Revert \"{}\"\n\nThis reverts commit {}.\n\nGroup-ID: {}",
+        target.summary().unwrap_or(""),
+        target.id(),
+        group_id
+    );
+
+    // Signed like the session commit, so branch protection accepts the revert too
+    let oid = write_commit(&repo, &sig, &message, &tree, &[&head_commit])
+        .map_err(|e| fail(format!("Cannot create revert commit: {}", e)))?;
+
+    repo.cleanup_state()
+        .map_err(|e| fail(format!("Cannot clean up revert state: {}", e)))?;
+
+    push_repo(info).map_err(|e| fail(format!("Revert committed but push failed: {}", e)))?;
+
+    Ok(oid)
+}
+
+/// Recovery for a partially pushed session: repositories that already reached
+/// the remote get a pushed revert commit, the rest are soft-reset as usual.
+/// Returns a human-readable outcome per repository.
+pub fn revert_partial_push(
+    session: &CommitSession,
+    report: &PushReport,
+) -> RepoOutcomes {
+    let mut outcomes = Vec::new();
+
+    for result in session.committed.iter().rev() {
+        let info = session
+            .repositories
+            .iter()
+            .find(|r| r.path == result.path)
+            .cloned()
+            .unwrap_or_else(|| RepositoryInfo::from_path(result.path.clone()));
+
+        let was_pushed = report.pushed.iter().any(|p| p.path == result.path);
+        let outcome = if was_pushed {
+            revert_pushed(&info, result, &session.group_id)
+                .map(|oid| format!("reverted by {}", &oid.to_string()[..8]))
+        } else {
+            rollback_one(result).map(|()| "rolled back".to_string())
+        };
+        outcomes.push((info.name, outcome));
+    }
+
+    outcomes
+}
@@ -0,0 +1,248 @@
+use std::fmt;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use git2::{Commit, Oid, Repository, Signature, Tree};
+
+use crate::prp_hub::workspace::expand_home;
+
+/// Signature backend selected by `gpg.format`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SigningFormat {
+    OpenPgp,
+    X509,
+    Ssh,
+}
+
+impl SigningFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SigningFormat::OpenPgp => "openpgp",
+            SigningFormat::X509 => "x509",
+            SigningFormat::Ssh => "ssh",
+        }
+    }
+}
+
+/// Commit signing settings of one repository, read the way `git commit` does:
+/// `commit.gpgsign`, `gpg.format`, `user.signingkey` and the `gpg.*.program`
+/// overrides, with local config taking precedence over global.
+#[derive(Debug, Clone)]
+pub struct SigningConfig {
+    pub format: SigningFormat,
+    /// None for OpenPGP / X.509 means "use the committer identity"
+    pub key: Option<String>,
+    pub program: String,
+}
+
+impl SigningConfig {
+    /// None when the repository does not ask for signed commits.
+    pub fn from_repo(repo: &Repository) -> Result<Option<Self>, String> {
+        let config = repo.config().map_err(|e| format!("Cannot read git config: {}", e))?;
+
+        if !config.get_bool("commit.gpgsign").unwrap_or(false) {
+            return Ok(None);
+        }
+
+        let format = match config.get_string("gpg.format").as_deref() {
+            Err(_) | Ok("openpgp") => SigningFormat::OpenPgp,
+            Ok("x509") => SigningFormat::X509,
+            Ok("ssh") => SigningFormat::Ssh,
+            Ok(other) => return Err(format!("Unsupported gpg.format '{}'", other)),
+        };
+
+        let program = match format {
+            SigningFormat::OpenPgp => config
+                .get_string("gpg.openpgp.program")
+                .or_else(|_| config.get_string("gpg.program"))
+                .unwrap_or_else(|_| "gpg".to_string()),
+            SigningFormat::X509 => config
+                .get_string("gpg.x509.program")
+                .unwrap_or_else(|_| "gpgsm".to_string()),
+            SigningFormat::Ssh => config
+                .get_string("gpg.ssh.program")
+                .unwrap_or_else(|_| "ssh-keygen".to_string()),
+        };
+
+        let key = config.get_string("user.signingkey").ok().filter(|k| !k.trim().is_empty());
+        if format == SigningFormat::Ssh && key.is_none() {
+            return Err("gpg.format is ssh but user.signingkey is not set".to_string());
+        }
+
+        Ok(Some(Self { format, key, program }))
+    }
+
+    /// Produce the detached, armored signature stored in the commit's `gpgsig` header.
+    pub fn sign(&self, payload: &str, committer: &Signature) -> Result<String, String> {
+        match self.format {
+            SigningFormat::OpenPgp | SigningFormat::X509 => self.sign_gpg(payload, committer),
+            SigningFormat::Ssh => self.sign_ssh(payload),
+        }
+    }
+
+    fn sign_gpg(&self, payload: &str, committer: &Signature) -> Result<String, String> {
+        // Same default as git: sign with the key matching the committer identity
+        let key = self.key.clone().unwrap_or_else(|| {
+            format!("{} <{}>", committer.name().unwrap_or(""), committer.email().unwrap_or(""))
+        });
+
+        let mut child = Command::new(&self.program)
+            .args(["--status-fd=2", "-bsau", &key])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Cannot run '{}': {}", self.program, e))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(payload.as_bytes())
+                .map_err(|e| format!("Cannot write to '{}': {}", self.program, e))?;
+        }
+
+        let output = child
+            .wait_with_output()
+            .map_err(|e| format!("'{}' did not finish: {}", self.program, e))?;
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.status.success() || !stderr.contains("[GNUPG:] SIG_CREATED ") {
+            return Err(format!("{} could not sign: {}", self.program, last_line(&stderr)));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    fn sign_ssh(&self, payload: &str) -> Result<String, String> {
+        let key = self.key.as_deref().unwrap_or_default();
+        let tmp = std::env::temp_dir();
+        let id = uuid::Uuid::new_v4();
+        let buffer = tmp.join(format!("gitlink-sign-{}", id));
+        let sig_file = tmp.join(format!("gitlink-sign-{}.sig", id));
+
+        // A literal public key (`key::ssh-...` or bare `ssh-...`) means the
+        // private half lives in ssh-agent; ssh-keygen needs it in a file plus -U
+        let literal = key.strip_prefix("key::").or_else(|| key.starts_with("ssh-").then_some(key));
+        let (key_file, remove_key) = match literal {
+            Some(public) => {
+                let path = tmp.join(format!("gitlink-sign-{}.pub", id));
+                std::fs::write(&path, public).map_err(|e| format!("Cannot write key file: {}", e))?;
+                (path, true)
+            }
+            None => (expand_home(key), false),
+        };
+
+        let result = std::fs::write(&buffer, payload)
+            .map_err(|e| format!("Cannot write signing buffer: {}", e))
+            .and_then(|()| {
+                let mut cmd = Command::new(&self.program);
+                cmd.args(["-Y", "sign", "-n", "git", "-f"]).arg(&key_file);
+                if remove_key {
+                    cmd.arg("-U");
+                }
+                cmd.arg(&buffer)
+                    .output()
+                    .map_err(|e| format!("Cannot run '{}': {}", self.program, e))
+            })
+            .and_then(|output| {
+                if !output.status.success() {
+                    return Err(format!(
+                        "{} could not sign: {}",
+                        self.program,
+                        last_line(&String::from_utf8_lossy(&output.stderr))
+                    ));
+                }
+                std::fs::read_to_string(&sig_file).map_err(|e| format!("Cannot read signature: {}", e))
+            });
+
+        let _ = std::fs::remove_file(&buffer);
+        let _ = std::fs::remove_file(&sig_file);
+        if remove_key {
+            let _ = std::fs::remove_file(&key_file);
+        }
+
+        result
+    }
+}
+
+/// How commits in this repository will be signed, for display before committing:
+/// the format name, a configuration problem, or None when signing is off.
+pub fn signing_label(path: &std::path::Path) -> Option<String> {
+    let repo = Repository::open(path).ok()?;
+    match SigningConfig::from_repo(&repo) {
+        Ok(Some(config)) => Some(config.format.label().to_string()),
+        Ok(None) => None,
+        Err(reason) => Some(format!("misconfigured: {}", reason)),
+    }
+}
+
+fn last_line(text: &str) -> String {
+    text.lines()
+        .rfind(|l| !l.starts_with("[GNUPG:]") && !l.trim().is_empty())
+        .unwrap_or("no output")
+        .trim()
+        .to_string()
+}
+
+/// Why a commit could not be written
+#[derive(Debug)]
+pub enum WriteCommitError {
+    Signing(String),
+    Git(git2::Error),
+}
+
+impl fmt::Display for WriteCommitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WriteCommitError::Signing(reason) => write!(f, "signing failed: {}", reason),
+            WriteCommitError::Git(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<git2::Error> for WriteCommitError {
+    fn from(e: git2::Error) -> Self {
+        WriteCommitError::Git(e)
+    }
+}
+
+/// Create a commit on HEAD, signing it when the repository is configured to.
+/// Unsigned commits go through `repo.commit` as before; signed ones are built
+/// as a buffer, signed, written with `commit_signed`, and HEAD is advanced by hand.
+pub fn write_commit(
+    repo: &Repository,
+    sig: &Signature,
+    message: &str,
+    tree: &Tree,
+    parents: &[&Commit],
+) -> Result<Oid, WriteCommitError> {
+    let signing = SigningConfig::from_repo(repo).map_err(WriteCommitError::Signing)?;
+
+    let signing = match signing {
+        Some(s) => s,
+        None => return Ok(repo.commit(Some("HEAD"), sig, sig, message, tree, parents)?),
+    };
+
+    let buffer = repo.commit_create_buffer(sig, sig, message, tree, parents)?;
+    let payload = buffer
+        .as_str()
+        .ok_or_else(|| WriteCommitError::Signing("commit buffer is not valid UTF-8".to_string()))?;
+    let signature = signing.sign(payload, sig).map_err(WriteCommitError::Signing)?;
+    let oid = repo.commit_signed(payload, &signature, Some("gpgsig"))?;
+
+    let summary = message.lines().next().unwrap_or("");
+    let reflog = if parents.is_empty() {
+        format!("commit (initial): {}", summary)
+    } else {
+        format!("commit: {}", summary)
+    };
+
+    let head = repo.find_reference("HEAD")?;
+    match head.symbolic_target() {
+        Some(target) => {
+            repo.reference(target, oid, true, &reflog)?;
+        }
+        None => repo.set_head_detached(oid)?,
+    }
+
+    Ok(oid)
+}
@@ -0,0 +1,254 @@
+use std::cell::Cell;
+use std::path::Path;
+
+use git2::{ApplyLocation, ApplyOptions, Diff, DiffOptions, Repository, Tree};
+
+use crate::prp_hub::status::collect_status;
+use crate::prp_hub::types::RepositoryInfo;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChangeKind {
+    Modified,
+    Added,
+    Deleted,
+    Untracked,
+}
+
+impl ChangeKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChangeKind::Modified => "modified",
+            ChangeKind::Added => "added",
+            ChangeKind::Deleted => "deleted",
+            ChangeKind::Untracked => "untracked",
+        }
+    }
+}
+
+/// One hunk of a modified file, as shown in the review step
+#[derive(Debug, Clone)]
+pub struct Hunk {
+    pub header: String,
+    /// Diff lines including their `+` / `-` / ` ` origin
+    pub lines: Vec<String>,
+}
+
+/// A changed path in a repository. Only modified files carry hunks; new,
+/// deleted and untracked files are always taken whole.
+#[derive(Debug, Clone)]
+pub struct FileChange {
+    pub path: String,
+    pub kind: ChangeKind,
+    pub hunks: Vec<Hunk>,
+}
+
+/// What to commit from one file
+#[derive(Debug, Clone)]
+pub struct FileSelection {
+    pub path: String,
+    pub included: bool,
+    /// Per-hunk flags; empty means the whole file
+    pub hunks: Vec<bool>,
+}
+
+impl FileSelection {
+    fn is_partial(&self) -> bool {
+        !self.hunks.is_empty() && !self.hunks.iter().all(|&h| h)
+    }
+}
+
+/// The paths (and hunks) chosen for commit in one repository
+#[derive(Debug, Clone, Default)]
+pub struct RepoSelection {
+    pub files: Vec<FileSelection>,
+}
+
+impl RepoSelection {
+    /// Everything tracked is included; untracked files must be ticked explicitly.
+    pub fn default_for(changes: &[FileChange]) -> Self {
+        let files = changes
+            .iter()
+            .map(|c| FileSelection {
+                path: c.path.clone(),
+                included: c.kind != ChangeKind::Untracked,
+                hunks: vec![true; c.hunks.len()],
+            })
+            .collect();
+        Self { files }
+    }
+
+    pub fn included_count(&self) -> usize {
+        self.files
+            .iter()
+            .filter(|f| f.included && (f.hunks.is_empty() || f.hunks.iter().any(|&h| h)))
+            .count()
+    }
+}
+
+fn head_tree(repo: &Repository) -> Option<Tree<'_>> {
+    repo.head().ok().and_then(|h| h.peel_to_tree().ok())
+}
+
+// HEAD against the working tree, optionally limited to one literal path.
+// Both hunk listing and hunk staging use this so hunk indices line up.
+fn workdir_diff<'r>(repo: &'r Repository, path: Option<&str>) -> Result<Diff<'r>, git2::Error> {
+    let mut opts = DiffOptions::new();
+    if let Some(p) = path {
+        opts.pathspec(p).disable_pathspec_match(true);
+    }
+    let tree = head_tree(repo);
+    repo.diff_tree_to_workdir(tree.as_ref(), Some(&mut opts))
+}
+
+fn collect_hunks(repo: &Repository, path: &str) -> Vec<Hunk> {
+    let diff = match workdir_diff(repo, Some(path)) {
+        Ok(d) => d,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut hunks: Vec<Hunk> = Vec::new();
+    let _ = diff.print(git2::DiffFormat::Patch, |_, hunk, line| {
+        match line.origin() {
+            'H' => {
+                if let Some(h) = hunk {
+                    hunks.push(Hunk {
+                        header: String::from_utf8_lossy(h.header()).trim_end().to_string(),
+                        lines: Vec::new(),
+                    });
+                }
+            }
+            origin @ ('+' | '-' | ' ') => {
+                if let Some(h) = hunks.last_mut() {
+                    let content = String::from_utf8_lossy(line.content());
+                    h.lines.push(format!("{}{}", origin, content.trim_end_matches('\n')));
+                }
+            }
+            _ => {}
+        }
+        true
+    });
+    hunks
+}
+
+/// The working-tree changes of a repository, built from `collect_status`,
+/// with hunks attached to modified files.
+pub fn collect_changes(info: &RepositoryInfo) -> Vec<FileChange> {
+    let status = collect_status(info);
+    let repo = Repository::open(&info.path).ok();
+
+    let mut changes: Vec<FileChange> = Vec::new();
+    let groups = [
+        (&status.modified, ChangeKind::Modified),
+        (&status.added, ChangeKind::Added),
+        (&status.deleted, ChangeKind::Deleted),
+        (&status.untracked, ChangeKind::Untracked),
+    ];
+
+    for (paths, kind) in groups {
+        for path in paths.iter() {
+            // A path can show up twice (e.g. staged edit, then deleted on disk)
+            if changes.iter().any(|c| &c.path == path) {
+                continue;
+            }
+            let hunks = match (&repo, kind) {
+                (Some(r), ChangeKind::Modified) => collect_hunks(r, path),
+                _ => Vec::new(),
+            };
+            changes.push(FileChange { path: path.clone(), kind, hunks });
+        }
+    }
+
+    changes
+}
+
+/// Stage the selected files and hunks on top of the current index. Unticked
+/// files are reset to HEAD in the index, so earlier staging cannot slip them
+/// into the commit; files outside the selection keep their index state.
+/// Whole files are added (or removed when gone from disk); partial files are
+/// reset to HEAD and get only their ticked hunks applied. The working tree is
+/// never touched.
+pub fn stage_selection(repo: &Repository, selection: &RepoSelection) -> Result<(), git2::Error> {
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| git2::Error::from_str("bare repository"))?
+        .to_path_buf();
+
+    let head = repo.head().ok().and_then(|h| h.peel(git2::ObjectType::Commit).ok());
+    let unticked: Vec<&str> = selection.files.iter().filter(|f| !f.included).map(|f| f.path.as_str()).collect();
+    if !unticked.is_empty() {
+        repo.reset_default(head.as_ref(), unticked)?;
+    }
+
+    let mut index = repo.index()?;
+
+    for file in selection.files.iter().filter(|f| f.included && !f.is_partial()) {
+        let rel = Path::new(&file.path);
+        if workdir.join(rel).exists() {
+            index.add_path(rel)?;
+        } else {
+            index.remove_path(rel)?;
+        }
+    }
+    index.write()?;
+
+    for file in selection.files.iter().filter(|f| f.included && f.is_partial()) {
+        // Hunks are counted from HEAD, so earlier staging of this file is replaced
+        repo.reset_default(head.as_ref(), [file.path.as_str()])?;
+        let diff = workdir_diff(repo, Some(&file.path))?;
+        let next = Cell::new(0usize);
+        let mut opts = ApplyOptions::new();
+        opts.hunk_callback(|_| {
+            let i = next.get();
+            next.set(i + 1);
+            file.hunks.get(i).copied().unwrap_or(false)
+        });
+        repo.apply(&diff, ApplyLocation::Index, Some(&mut opts))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prp_hub::commit::commit_repo;
+    use crate::prp_hub::test_support::{bare_remote, clone, git, TempDir};
+
+    #[test]
+    fn unticked_staged_file_stays_out_of_the_commit() {
+        let tmp = TempDir::new();
+        let remote = bare_remote(tmp.path(), "api");
+        let api = clone(&remote, tmp.path(), "api");
+        std::fs::write(api.path.join("a.txt"), "staged beforehand").unwrap();
+        std::fs::write(api.path.join("b.txt"), "ticked").unwrap();
+        git(&api.path, &["add", "a.txt", "b.txt"]);
+
+        let changes = collect_changes(&api);
+        let mut selection = RepoSelection::default_for(&changes);
+        for file in selection.files.iter_mut() {
+            file.included = file.path == "b.txt";
+        }
+        commit_repo(&api, "Add b", "group", Some(&selection), &[], &[]).unwrap().unwrap();
+
+        assert_eq!(git(&api.path, &["show", "--name-only", "--format=", "HEAD"]), "b.txt");
+        // Still on disk, and no longer staged
+        assert_eq!(git(&api.path, &["status", "--porcelain", "a.txt"]), "?? a.txt");
+    }
+
+    #[test]
+    fn files_outside_the_selection_keep_their_staging() {
+        let tmp = TempDir::new();
+        let remote = bare_remote(tmp.path(), "api");
+        let api = clone(&remote, tmp.path(), "api");
+        std::fs::write(api.path.join("README.md"), "edited").unwrap();
+        git(&api.path, &["add", "README.md"]);
+        std::fs::write(api.path.join("b.txt"), "ticked").unwrap();
+
+        let selection = RepoSelection {
+            files: vec![FileSelection { path: "b.txt".to_string(), included: true, hunks: vec![] }],
+        };
+        stage_selection(&Repository::open(&api.path).unwrap(), &selection).unwrap();
+
+        assert_eq!(git(&api.path, &["diff", "--cached", "--name-only"]), "README.md\nb.txt");
+    }
+}
@@ -1,7 +1,9 @@
 use git2::{Repository, RepositoryState};
 
+use crate::prp_hub::config::load_config;
 use crate::prp_hub::errors::PrpError;
 use crate::prp_hub::types::RepositoryInfo;
+use crate::prp_hub::upstream::resolve_upstream;
 
 /// Validate repository state before committing.
 /// Returns Ok(()) if repo is ready, or a descriptive PrpError otherwise.
@@ -69,18 +71,19 @@ pub fn validate_repo(info: &RepositoryInfo) -> Result<(), PrpError> {
     })?;
 
     let branch_name = head.shorthand().unwrap_or("").to_string();
-    let remote_ref = format!("refs/remotes/origin/{}", branch_name);
+    let upstream = resolve_upstream(&repo, info, &branch_name, &load_config());
 
-    if repo.find_reference(&remote_ref).is_err() {
+    if repo.find_reference(&upstream.tracking_ref()).is_err() {
         return Err(PrpError::InvalidState {
             repo: info.name.clone(),
             reason: format!(
-                "No upstream tracking branch found for '{}'",
-                branch_name
+                "No upstream tracking branch found for '{}' (expected {})",
+                branch_name,
+                upstream.short_name()
             ),
             fix: format!(
-                "Run `git push --set-upstream origin {}` first",
-                branch_name
+                "Run `git push --set-upstream {} {}` first",
+                upstream.remote, branch_name
             ),
         });
     }
@@ -0,0 +1,89 @@
+use std::path::{Path, PathBuf};
+
+use git2::Repository;
+
+use crate::prp_hub::types::RepositoryInfo;
+
+/// A discovered repository that is checked out as a submodule of another discovered one
+#[derive(Debug, Clone)]
+pub struct SubmoduleLink {
+    pub parent: PathBuf,
+    pub child: PathBuf,
+    /// Where the submodule lives inside the parent's work tree
+    pub rel_path: PathBuf,
+}
+
+/// Parent/child pairs among `repos`. Submodules that were not discovered
+/// (or are not checked out) are ignored.
+pub fn submodule_links(repos: &[RepositoryInfo]) -> Vec<SubmoduleLink> {
+    let mut links = Vec::new();
+    for parent in repos {
+        let repo = match Repository::open(&parent.path) {
+            Ok(r) => r,
+            Err(_) => continue,
+        };
+        let subs = match repo.submodules() {
+            Ok(s) => s,
+            Err(_) => continue,
+        };
+        for sub in subs {
+            let rel_path = sub.path().to_path_buf();
+            let child = match std::fs::canonicalize(parent.path.join(&rel_path)) {
+                Ok(p) => p,
+                Err(_) => continue,
+            };
+            if repos.iter().any(|r| r.path == child) {
+                links.push(SubmoduleLink {
+                    parent: parent.path.clone(),
+                    child,
+                    rel_path,
+                });
+            }
+        }
+    }
+    links
+}
+
+/// Reorder so every submodule comes before the repository that contains it.
+/// Otherwise the original order is kept.
+pub fn submodules_first(repos: Vec<RepositoryInfo>, links: &[SubmoduleLink]) -> Vec<RepositoryInfo> {
+    fn visit(i: usize, repos: &[RepositoryInfo], links: &[SubmoduleLink], done: &mut Vec<bool>, out: &mut Vec<usize>) {
+        if done[i] {
+            return;
+        }
+        done[i] = true;
+        for link in links.iter().filter(|l| l.parent == repos[i].path) {
+            if let Some(c) = repos.iter().position(|r| r.path == link.child) {
+                visit(c, repos, links, done, out);
+            }
+        }
+        out.push(i);
+    }
+
+    let mut done = vec![false; repos.len()];
+    let mut order = Vec::with_capacity(repos.len());
+    for i in 0..repos.len() {
+        visit(i, &repos, links, &mut done, &mut order);
+    }
+    order.into_iter().map(|i| repos[i].clone()).collect()
+}
+
+/// Point the parent's index at the current HEAD of each submodule in `rel_paths`.
+pub fn stage_gitlinks(repo: &Repository, rel_paths: &[&Path]) -> Result<(), git2::Error> {
+    for mut sub in repo.submodules()? {
+        if rel_paths.contains(&sub.path()) {
+            sub.add_to_index(true)?;
+        }
+    }
+    Ok(())
+}
+
+/// "parent → submodule/path", using the repositories' display names.
+pub fn describe_link(link: &SubmoduleLink, repos: &[RepositoryInfo]) -> String {
+    let parent = repos
+        .iter()
+        .find(|r| r.path == link.parent)
+        .map(|r| r.name.clone())
+        .unwrap_or_else(|| link.parent.display().to_string());
+    format!("{} → {}", parent, link.rel_path.display())
+}
@@ -0,0 +1,236 @@
+use std::path::Path;
+use std::process::Command;
+
+use git2::Repository;
+
+use crate::github::pulls_client::{parse_github_remote, PullsClient};
+use crate::prp_hub::errors::PrpError;
+use crate::prp_hub::types::{new_group_id, RepoOutcomes, RepositoryInfo};
+use crate::prp_hub::upstream::current_upstream;
+
+/// A parsed `/prp tag <name> [--sign] [--release] [--set <workspace>]`
+#[derive(Debug, Clone)]
+pub struct TagRequest {
+    pub name: String,
+    pub sign: bool,
+    pub release: bool,
+    pub set: Option<String>,
+}
+
+/// Parse the words after `tag`.
+pub fn parse_tag_args(args: &[&str]) -> Result<TagRequest, String> {
+    let usage = "Usage: /prp tag <version> [--sign] [--release] [--set <workspace>]";
+    let mut request = TagRequest { name: String::new(), sign: false, release: false, set: None };
+
+    let mut words = args.iter();
+    while let Some(word) = words.next() {
+        match *word {
+            "--sign" | "-s" => request.sign = true,
+            "--release" => request.release = true,
+            "--set" => request.set = Some(words.next().ok_or(usage)?.to_string()),
+            w if w.starts_with('-') => return Err(format!("Unknown option '{}'. {}", w, usage)),
+            w if request.name.is_empty() => request.name = w.to_string(),
+            _ => return Err(usage.to_string()),
+        }
+    }
+    if request.name.is_empty() {
+        return Err(usage.to_string());
+    }
+    Ok(request)
+}
+
+/// One repository's tag, once created
+#[derive(Debug, Clone)]
+pub struct TaggedRepo {
+    pub info: RepositoryInfo,
+    pub remote: String,
+}
+
+/// Tags with the same name and Group-ID, pushed in every repository
+#[derive(Debug, Clone)]
+pub struct TagGroup {
+    pub name: String,
+    pub group_id: String,
+    pub repos: Vec<TaggedRepo>,
+}
+
+/// Annotation of every tag in the group
+pub fn tag_message(name: &str, group_id: &str) -> String {
+    format!("Release {}\n\nGroup-ID: {}", name, group_id)
+}
+
+fn git(args: &[&str], dir: &Path) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        // Never stop for a password prompt; report the failure instead
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .map_err(|e| format!("Cannot run git: {}", e))?;
+
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// The remote the tag goes to, after making sure neither side has it yet.
+fn check_free(info: &RepositoryInfo, name: &str) -> Result<String, PrpError> {
+    let fail = |reason: String, fix: &str| PrpError::TagFailed {
+        repo: info.name.clone(),
+        reason,
+        fix: fix.to_string(),
+    };
+
+    let repo = Repository::open(&info.path)
+        .map_err(|e| fail(format!("Cannot open repository: {}", e), "Ensure the path is a valid git repository"))?;
+    if repo.find_reference(&format!("refs/tags/{}", name)).is_ok() {
+        return Err(fail(
+            format!("Tag '{}' already exists locally", name),
+            &format!("Pick another version, or delete it with `git tag -d {}`", name),
+        ));
+    }
+
+    let remote = current_upstream(info)?.remote;
+    let listed = git(&["ls-remote", "--tags", &remote, &format!("refs/tags/{}", name)], &info.path)
+        .map_err(|e| fail(format!("Cannot reach '{}': {}", remote, e), "Check the remote connection and credentials"))?;
+    if !listed.is_empty() {
+        return Err(fail(
+            format!("Tag '{}' already exists on '{}'", name, remote),
+            "Pick another version; released tags should not be moved",
+        ));
+    }
+    Ok(remote)
+}
+
+// Best-effort removal of tags created so far; returns the repositories it could not clean up.
+fn rollback_tags(tagged: &[TaggedRepo], pushed: usize, name: &str) -> Vec<PrpError> {
+    let mut errors = Vec::new();
+    for (i, t) in tagged.iter().enumerate() {
+        if i < pushed {
+            if let Err(e) = git(&["push", &t.remote, &format!(":refs/tags/{}", name)], &t.info.path) {
+                errors.push(PrpError::RollbackFailed {
+                    repo: t.info.name.clone(),
+                    reason: format!("Cannot delete pushed tag '{}': {}", name, e),
+                });
+            }
+        }
+        if let Err(e) = git(&["tag", "-d", name], &t.info.path) {
+            errors.push(PrpError::RollbackFailed {
+                repo: t.info.name.clone(),
+                reason: format!("Cannot delete local tag '{}': {}", name, e),
+            });
+        }
+    }
+    errors
+}
+
+/// Create an annotated (or signed) tag at HEAD in every repository and push
+/// them. Every repository is checked first; if the tag exists anywhere, nothing
+/// is created. If creating or pushing fails part-way, the tags made so far are
+/// deleted again, locally and on the remote. On failure the cause comes first,
+/// followed by any repositories the rollback could not clean up.
+pub fn tag_all(repos: &[RepositoryInfo], request: &TagRequest) -> Result<TagGroup, Vec<PrpError>> {
+    let mut remotes = Vec::new();
+    let mut errors = Vec::new();
+    for info in repos {
+        match check_free(info, &request.name) {
+            Ok(remote) => remotes.push(remote),
+            Err(e) => errors.push(e),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let group_id = new_group_id();
+    let message = tag_message(&request.name, &group_id);
+    let flag = if request.sign { "-s" } else { "-a" };
+
+    let mut tagged = Vec::new();
+    for (info, remote) in repos.iter().zip(remotes) {
+        if let Err(e) = git(&["tag", flag, "-m", &message, &request.name], &info.path) {
+            let mut errors = vec![PrpError::TagFailed {
+                repo: info.name.clone(),
+                reason: e,
+                fix: if request.sign {
+                    "Check user.signingkey and gpg.format, then try `git tag -s` in that repository".to_string()
+                } else {
+                    "Run `git status` to inspect the repository".to_string()
+                },
+            }];
+            errors.extend(rollback_tags(&tagged, 0, &request.name));
+            return Err(errors);
+        }
+        tagged.push(TaggedRepo { info: info.clone(), remote });
+    }
+
+    for (i, t) in tagged.iter().enumerate() {
+        if let Err(e) = git(&["push", &t.remote, &format!("refs/tags/{}", request.name)], &t.info.path) {
+            let mut errors = vec![PrpError::PushFailed { repo: t.info.name.clone(), stderr: e }];
+            errors.extend(rollback_tags(&tagged, i, &request.name));
+            return Err(errors);
+        }
+    }
+
+    Ok(TagGroup { name: request.name.clone(), group_id, repos: tagged })
+}
+
+/// The tag before `name` in the repository's history, if any.
+pub fn previous_tag(info: &RepositoryInfo, name: &str) -> Option<String> {
+    git(&["describe", "--tags", "--abbrev=0", &format!("{}^", name)], &info.path)
+        .ok()
+        .filter(|t| !t.is_empty())
+}
+
+/// Markdown release notes: one bullet per commit since the previous tag.
+pub fn release_notes(info: &RepositoryInfo, name: &str, group_id: &str) -> String {
+    let previous = previous_tag(info, name);
+    let range = match &previous {
+        Some(prev) => format!("{}..{}", prev, name),
+        None => name.to_string(),
+    };
+    let log = git(&["log", "--no-merges", "--format=- %s (%h)", &range], &info.path).unwrap_or_default();
+
+    let mut notes = match &previous {
+        Some(prev) => format!("## Changes since {}\n\n", prev),
+        None => "## Changes\n\n".to_string(),
+    };
+    if log.is_empty() {
+        notes.push_str("_No new commits._\n");
+    } else {
+        notes.push_str(&log);
+        notes.push('\n');
+    }
+    notes.push_str(&format!("\nGroup-ID: `{}`\n", group_id));
+    notes
+}
+
+/// Publish a GitHub release for every tag of the group. Repositories whose
+/// remote is not on GitHub are reported as failures; the tags stay in place.
+pub async fn create_releases(client: &PullsClient, group: &TagGroup) -> RepoOutcomes {
+    let mut outcomes = Vec::new();
+    for t in &group.repos {
+        let fail = |reason: String| PrpError::ReleaseFailed { repo: t.info.name.clone(), reason };
+
+        let target = Repository::open(&t.info.path)
+            .ok()
+            .and_then(|r| r.find_remote(&t.remote).ok().and_then(|rem| rem.url().and_then(parse_github_remote)));
+        let (owner, repo) = match target {
+            Some(o) => o,
+            None => {
+                outcomes.push((t.info.name.clone(), Err(fail(format!("Remote '{}' is not a GitHub repository", t.remote)))));
+                continue;
+            }
+        };
+
+        let body = release_notes(&t.info, &group.name, &group.group_id);
+        let outcome = client
+            .create_release(&owner, &repo, &group.name, &group.name, &body)
+            .await
+            .map(|r| format!("release {}", r.html_url))
+            .map_err(|e| fail(e.to_string()));
+        outcomes.push((t.info.name.clone(), outcome));
+    }
+    outcomes
+}
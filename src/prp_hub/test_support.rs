@@ -0,0 +1,71 @@
+//! Throwaway repositories for the prp_hub tests
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::prp_hub::types::RepositoryInfo;
+
+/// Directory under the system temp dir, removed on drop
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new() -> Self {
+        let path = std::env::temp_dir().join(format!("gitlink-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&path).unwrap();
+        // Canonical, so paths compare equal to what discovery returns
+        Self(std::fs::canonicalize(&path).unwrap())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Run git in `dir` with a fixed identity, panicking on failure. Returns stdout.
+pub fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(["-c", "user.name=Test", "-c", "user.email=test@example.com", "-c", "init.defaultBranch=main"])
+        .args(args)
+        .current_dir(dir)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+/// Write `file` and commit it
+pub fn commit_file(dir: &Path, file: &str, content: &str) {
+    std::fs::write(dir.join(file), content).unwrap();
+    git(dir, &["add", file]);
+    git(dir, &["commit", "-q", "-m", &format!("Update {}", file)]);
+}
+
+/// Bare repository `<name>.git` under `root` whose `main` has one commit
+pub fn bare_remote(root: &Path, name: &str) -> PathBuf {
+    let remote = root.join(format!("{}.git", name));
+    git(root, &["init", "-q", "--bare", remote.to_str().unwrap()]);
+    let seed = root.join(format!("{}-seed", name));
+    git(root, &["clone", "-q", remote.to_str().unwrap(), seed.to_str().unwrap()]);
+    commit_file(&seed, "README.md", name);
+    git(&seed, &["push", "-q", "origin", "HEAD:main"]);
+    std::fs::remove_dir_all(&seed).unwrap();
+    remote
+}
+
+/// Clone `remote` to `root/<name>`
+pub fn clone(remote: &Path, root: &Path, name: &str) -> RepositoryInfo {
+    git(root, &["clone", "-q", remote.to_str().unwrap(), name]);
+    RepositoryInfo { name: name.to_string(), path: root.join(name) }
+}
@@ -1,7 +1,12 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::SystemTime;
 use git2::Oid;
 
+use crate::prp_hub::errors::PrpError;
+use crate::prp_hub::staging::RepoSelection;
+use crate::prp_hub::submodule::{submodule_links, submodules_first, SubmoduleLink};
+
 /// Basic info about a discovered repository
 #[derive(Debug, Clone)]
 pub struct RepositoryInfo {
@@ -9,6 +14,17 @@ pub struct RepositoryInfo {
     pub path: PathBuf,
 }
 
+impl RepositoryInfo {
+    /// Build from a repository root, using the last path component as the name.
+    pub fn from_path(path: PathBuf) -> Self {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.to_string_lossy().into_owned());
+        Self { name, path }
+    }
+}
+
 /// A single commit recorded during this session
 #[derive(Debug, Clone)]
 pub struct RepoCommitResult {
@@ -23,16 +39,86 @@ pub struct CommitSession {
     pub group_id: String,
     pub repositories: Vec<RepositoryInfo>,
     pub committed: Vec<RepoCommitResult>,
+    /// Files/hunks to stage per repository; repos without an entry stage everything
+    pub selections: HashMap<PathBuf, RepoSelection>,
+    /// Final per-repo messages; repos without an entry use the shared message
+    pub messages: HashMap<PathBuf, String>,
+    /// Submodule relationships among `repositories`
+    pub submodules: Vec<SubmoduleLink>,
+    /// Links whose parent commit recorded the child's new commit
+    pub gitlink_bumps: Vec<SubmoduleLink>,
     pub started_at: SystemTime,
 }
 
 impl CommitSession {
+    /// Repositories are reordered so submodules come before their parents:
+    /// that is the order they are committed and pushed in, and rollback
+    /// undoes them in reverse.
     pub fn new(group_id: String, repositories: Vec<RepositoryInfo>) -> Self {
+        let submodules = submodule_links(&repositories);
+        let repositories = submodules_first(repositories, &submodules);
         Self {
             group_id,
             repositories,
             committed: Vec::new(),
+            selections: HashMap::new(),
+            messages: HashMap::new(),
+            submodules,
+            gitlink_bumps: Vec::new(),
             started_at: SystemTime::now(),
         }
     }
-}
\ No newline at end of file
+
+    /// Repositories that received a commit in this session, in session order.
+    pub fn committed_repos(&self) -> Vec<RepositoryInfo> {
+        self.repositories
+            .iter()
+            .filter(|r| self.committed.iter().any(|c| c.path == r.path))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Per-repository outcome of a multi-repo operation: repo name and what happened
+pub type RepoOutcomes = Vec<(String, Result<String, PrpError>)>;
+
+/// Generate a fresh session Group-ID
+pub fn new_group_id() -> String {
+    format!("gitlink-{}", uuid::Uuid::new_v4())
+}
+
+/// Result of the pre-push remote check for one repository
+#[derive(Debug, Clone)]
+pub struct PushPreflight {
+    pub repo: RepositoryInfo,
+    pub branch: String,
+    /// Commits the local branch is ahead of its upstream
+    pub ahead: usize,
+}
+
+/// Outcome of pushing a list of repositories in order.
+/// Pushing stops at the first failure; everything after it is `remaining`.
+#[derive(Debug, Default)]
+pub struct PushReport {
+    pub pushed: Vec<RepositoryInfo>,
+    pub failed: Option<(RepositoryInfo, PrpError)>,
+    pub remaining: Vec<RepositoryInfo>,
+}
+
+impl PushReport {
+    /// Repositories that still need pushing: the failed one first, then the rest.
+    pub fn unpushed(&self) -> Vec<RepositoryInfo> {
+        self.failed
+            .iter()
+            .map(|(r, _)| r.clone())
+            .chain(self.remaining.iter().cloned())
+            .collect()
+    }
+
+    /// Fold the report of a retry (over `unpushed()`) into this one.
+    pub fn absorb(&mut self, retry: PushReport) {
+        self.pushed.extend(retry.pushed);
+        self.failed = retry.failed;
+        self.remaining = retry.remaining;
+    }
+}
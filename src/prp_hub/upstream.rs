@@ -0,0 +1,89 @@
+use git2::Repository;
+
+use crate::prp_hub::config::{load_config, PrpConfig};
+use crate::prp_hub::errors::PrpError;
+use crate::prp_hub::types::RepositoryInfo;
+
+/// Where a local branch is pushed to and compared against
+#[derive(Debug, Clone, PartialEq)]
+pub struct Upstream {
+    pub local: String,
+    pub remote: String,
+    /// Branch name on the remote
+    pub branch: String,
+    /// Passed to `git push <remote> <refspec>`
+    pub refspec: String,
+}
+
+impl Upstream {
+    /// Remote-tracking ref the branch is compared with, e.g. `refs/remotes/upstream/main`
+    pub fn tracking_ref(&self) -> String {
+        format!("refs/remotes/{}/{}", self.remote, self.branch)
+    }
+
+    /// "upstream/main"
+    pub fn short_name(&self) -> String {
+        format!("{}/{}", self.remote, self.branch)
+    }
+}
+
+/// `branch.<local>.remote` / `.merge` as git resolves them, if set to a real remote.
+pub fn configured_upstream(repo: &Repository, local: &str) -> Option<(String, String)> {
+    let full = format!("refs/heads/{}", local);
+    let remote = repo.branch_upstream_remote(&full).ok()?.as_str()?.to_string();
+    let tracking = repo.branch_upstream_name(&full).ok()?.as_str()?.to_string();
+    let branch = tracking.strip_prefix(&format!("refs/remotes/{}/", remote))?.to_string();
+    Some((remote, branch))
+}
+
+/// Upstream for `local` in the repository at `info.path`. Per-repo overrides in
+/// `.gitlink/prp_config.json` come first, then the branch's configured upstream,
+/// then `origin/<local>`.
+pub fn resolve_upstream(repo: &Repository, info: &RepositoryInfo, local: &str, config: &PrpConfig) -> Upstream {
+    let (remote_override, refspec_override) = config.remote_for(&info.path);
+    let configured = configured_upstream(repo, local);
+
+    let remote = remote_override
+        .map(str::to_string)
+        .or_else(|| configured.as_ref().map(|(r, _)| r.clone()))
+        .unwrap_or_else(|| "origin".to_string());
+
+    let (refspec, branch) = match refspec_override {
+        Some(spec) => {
+            let spec = spec.replace("{branch}", local);
+            let dst = spec.rsplit(':').next().unwrap_or(&spec).trim_start_matches('+');
+            let branch = dst.strip_prefix("refs/heads/").unwrap_or(dst).to_string();
+            (spec, branch)
+        }
+        None => {
+            // A configured merge branch only applies on the remote it belongs to
+            let branch = configured
+                .filter(|(r, _)| *r == remote)
+                .map(|(_, b)| b)
+                .unwrap_or_else(|| local.to_string());
+            (format!("refs/heads/{}:refs/heads/{}", local, branch), branch)
+        }
+    };
+
+    Upstream { local: local.to_string(), remote, branch, refspec }
+}
+
+/// Upstream of the checked-out branch, reading the PRP config from disk.
+pub fn current_upstream(info: &RepositoryInfo) -> Result<Upstream, PrpError> {
+    let fail = |reason: String, fix: &str| PrpError::InvalidState {
+        repo: info.name.clone(),
+        reason,
+        fix: fix.to_string(),
+    };
+
+    let repo = Repository::open(&info.path)
+        .map_err(|e| fail(format!("Cannot open repository: {}", e), "Ensure the path is a valid git repository"))?;
+    let head = repo
+        .head()
+        .map_err(|e| fail(format!("Cannot read HEAD: {}", e), "Ensure you are on a branch"))?;
+    if !head.is_branch() {
+        return Err(PrpError::DetachedHead(info.name.clone()));
+    }
+    let local = head.shorthand().unwrap_or("").to_string();
+    Ok(resolve_upstream(&repo, info, &local, &load_config()))
+}
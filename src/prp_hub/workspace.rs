@@ -0,0 +1,275 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::prp_hub::discovery::discover_repositories;
+use crate::prp_hub::errors::PrpError;
+use crate::prp_hub::types::RepositoryInfo;
+
+const WORKSPACES_FILE: &str = "workspaces.json";
+
+/// A named set of repositories, usable from any directory with `/prp --set <name>`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workspace {
+    pub name: String,
+    /// Repository paths, parent directories to search, or globs such as
+    /// `~/work/services/*`. A leading `~/` expands to the home directory.
+    pub entries: Vec<String>,
+}
+
+/// User-level store of workspaces, kept in the OS config directory
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct WorkspaceStore {
+    pub workspaces: Vec<Workspace>,
+}
+
+impl WorkspaceStore {
+    pub fn find(&self, name: &str) -> Option<&Workspace> {
+        self.workspaces.iter().find(|w| w.name == name)
+    }
+
+    /// Add entries to a workspace, creating it if needed. Returns true if it was created.
+    pub fn add_entries(&mut self, name: &str, entries: &[String]) -> bool {
+        let created = self.find(name).is_none();
+        if created {
+            self.workspaces.push(Workspace {
+                name: name.to_string(),
+                entries: Vec::new(),
+            });
+            self.workspaces.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+        if let Some(ws) = self.workspaces.iter_mut().find(|w| w.name == name) {
+            for entry in entries {
+                if !ws.entries.contains(entry) {
+                    ws.entries.push(entry.clone());
+                }
+            }
+        }
+        created
+    }
+
+    pub fn remove_entry(&mut self, name: &str, entry: &str) -> Result<(), String> {
+        let ws = self
+            .workspaces
+            .iter_mut()
+            .find(|w| w.name == name)
+            .ok_or_else(|| format!("No workspace named '{}'", name))?;
+        let before = ws.entries.len();
+        ws.entries.retain(|e| e != entry);
+        if ws.entries.len() == before {
+            return Err(format!("'{}' is not an entry of workspace '{}'", entry, name));
+        }
+        Ok(())
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.workspaces.len();
+        self.workspaces.retain(|w| w.name != name);
+        self.workspaces.len() != before
+    }
+}
+
+fn store_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("gitlink").join(WORKSPACES_FILE))
+}
+
+pub fn load_workspaces() -> WorkspaceStore {
+    let raw = match store_path().and_then(|p| std::fs::read_to_string(p).ok()) {
+        Some(r) => r,
+        None => return WorkspaceStore::default(),
+    };
+    serde_json::from_str(&raw).unwrap_or_default()
+}
+
+pub fn save_workspaces(store: &WorkspaceStore) -> Result<(), Box<dyn std::error::Error>> {
+    let path = store_path().ok_or("No user config directory found")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(store)?)?;
+    Ok(())
+}
+
+/// Where the workspace store lives, for display.
+pub fn workspaces_location() -> String {
+    store_path()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|| "(no config directory)".to_string())
+}
+
+/// Workspace names must be a single word so they can follow `--set` on the command line.
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('-')
+        && name.chars().all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Expand a leading `~/` to the home directory.
+pub fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().map(|h| h.join(rest)).unwrap_or_else(|| PathBuf::from(path)),
+        None => PathBuf::from(path),
+    }
+}
+
+/// Make an entry independent of the directory it was added from:
+/// relative paths and globs are anchored at the current directory.
+pub fn normalize_entry(entry: &str) -> String {
+    if entry.starts_with("~/") || Path::new(entry).is_absolute() {
+        return entry.to_string();
+    }
+    let cwd = std::env::current_dir().unwrap_or_default();
+    let joined = cwd.join(entry);
+    if has_wildcard(entry) {
+        return joined.to_string_lossy().into_owned();
+    }
+    std::fs::canonicalize(&joined)
+        .unwrap_or(joined)
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn has_wildcard(s: &str) -> bool {
+    s.contains(['*', '?'])
+}
+
+// `*` matches any run of characters, `?` exactly one, within a single path component.
+fn wildcard_match(pattern: &[char], text: &[char]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some(('*', rest)) => (0..=text.len()).any(|i| wildcard_match(rest, &text[i..])),
+        Some(('?', rest)) => !text.is_empty() && wildcard_match(rest, &text[1..]),
+        Some((c, rest)) => text.first() == Some(c) && wildcard_match(rest, &text[1..]),
+    }
+}
+
+// Component-wise match where `**` spans any number of directories.
+fn match_components(pattern: &[Vec<char>], path: &[Vec<char>]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((p, rest)) if p.iter().collect::<String>() == "**" => {
+            (0..=path.len()).any(|i| match_components(rest, &path[i..]))
+        }
+        Some((p, rest)) => match path.split_first() {
+            Some((c, tail)) => wildcard_match(p, c) && match_components(rest, tail),
+            None => false,
+        },
+    }
+}
+
+fn components(path: &Path) -> Vec<Vec<char>> {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy().chars().collect())
+        .collect()
+}
+
+/// Directories an entry refers to: the path itself, or every directory matching the glob.
+fn expand_entry(entry: &str) -> Vec<PathBuf> {
+    let path = expand_home(entry);
+    if !has_wildcard(entry) {
+        return if path.is_dir() { vec![path] } else { Vec::new() };
+    }
+
+    let parts: Vec<_> = path.components().collect();
+    let split = parts
+        .iter()
+        .position(|c| has_wildcard(&c.as_os_str().to_string_lossy()))
+        .unwrap_or(parts.len());
+    let base: PathBuf = parts[..split].iter().collect();
+    let pattern: Vec<Vec<char>> = parts[split..]
+        .iter()
+        .map(|c| c.as_os_str().to_string_lossy().chars().collect())
+        .collect();
+
+    let recursive = pattern.iter().any(|p| p.iter().collect::<String>() == "**");
+    let mut walker = WalkDir::new(&base).min_depth(1).follow_links(false);
+    if !recursive {
+        walker = walker.max_depth(pattern.len());
+    }
+
+    walker
+        .into_iter()
+        .filter_entry(|e| e.file_name() != ".git")
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_dir())
+        .filter(|e| {
+            e.path()
+                .strip_prefix(&base)
+                .map(|rel| match_components(&pattern, &components(rel)))
+                .unwrap_or(false)
+        })
+        .map(|e| e.into_path())
+        .collect()
+}
+
+/// Resolve a workspace to its repositories. A matched directory that is a
+/// repository is taken as is; any other directory is searched the same way
+/// `/prp` searches the current directory.
+pub fn resolve_workspace(ws: &Workspace) -> Result<Vec<RepositoryInfo>, PrpError> {
+    let mut seen: HashSet<PathBuf> = HashSet::new();
+    let mut repos: Vec<RepositoryInfo> = Vec::new();
+
+    for dir in ws.entries.iter().flat_map(|e| expand_entry(e)) {
+        let found = if dir.join(".git").exists() {
+            match std::fs::canonicalize(&dir) {
+                Ok(p) => vec![RepositoryInfo::from_path(p)],
+                Err(_) => continue,
+            }
+        } else {
+            match discover_repositories(&dir.to_string_lossy()) {
+                Ok(r) => r,
+                Err(PrpError::NoRepositoriesFound) => continue,
+                Err(e) => return Err(e),
+            }
+        };
+        for repo in found {
+            if seen.insert(repo.path.clone()) {
+                repos.push(repo);
+            }
+        }
+    }
+
+    if repos.is_empty() {
+        return Err(PrpError::DiscoveryError(format!(
+            "Workspace '{}' does not match any git repository",
+            ws.name
+        )));
+    }
+
+    repos.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(repos)
+}
+
+/// Repositories of the named workspace from the user-level store.
+pub fn workspace_repositories(name: &str) -> Result<Vec<RepositoryInfo>, PrpError> {
+    let store = load_workspaces();
+    let ws = store.find(name).ok_or_else(|| {
+        PrpError::DiscoveryError(format!(
+            "No workspace named '{}'. Create it with /prp set add {} <path|glob>",
+            name, name
+        ))
+    })?;
+    resolve_workspace(ws)
+}
+
+/// One line per workspace: name, repository count and entries.
+pub fn format_workspaces(store: &WorkspaceStore) -> String {
+    if store.workspaces.is_empty() {
+        return "ℹ️  No workspaces defined. Create one with /prp set add <name> <path|glob>...\n".to_string();
+    }
+
+    let mut out = String::new();
+    for ws in &store.workspaces {
+        let count = match resolve_workspace(ws) {
+            Ok(repos) => format!("{} repo(s)", repos.len()),
+            Err(_) => "no repositories".to_string(),
+        };
+        out.push_str(&format!("🗂  {} — {}\n", ws.name, count));
+        for entry in &ws.entries {
+            out.push_str(&format!("   • {}\n", entry));
+        }
+    }
+    out
+}
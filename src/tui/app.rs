@@ -71,6 +71,27 @@ pub struct PlannerOverlay {
     pub input_buf: String,
     pub input_cursor: usize,
     pub input_field: InputField,
+    /// Why the current modal field was not accepted
+    pub input_error: Option<String>,
+    pub sort: crate::planner::schedule::TaskSort,
+    /// Parent of the task being added, when adding a subtask
+    pub new_parent: Option<String>,
+    /// Task whose blockers are being picked
+    pub link_source: Option<String>,
+    /// One-line error at the bottom of the task list
+    pub notice: Option<String>,
+    /// One-line confirmation shown there when there is no error
+    pub status: Option<String>,
+    pub settings: crate::planner::storage::PlannerSettings,
+    pub archive: Vec<Task>,
+    pub archive_selected: usize,
+    pub archive_query: String,
+    /// Typing goes into `archive_query`
+    pub archive_searching: bool,
+    /// Column the cursor is in on the board; the card is `selected`
+    pub board_column: usize,
+    /// Result of the commit scan started on open; Some while it runs
+    pub commit_scan: Option<std::sync::mpsc::Receiver<(Vec<Task>, crate::planner::commits::LinkReport)>>,
     pub scroll: usize,
 }
 
@@ -78,14 +99,58 @@ pub struct PlannerOverlay {
 pub enum PlannerFocus { List, Detail }
 
 #[derive(Debug, Clone, PartialEq)]
-pub enum PlannerMode { Normal, AddingTask, EditingTask, ConfirmDelete }
+pub enum PlannerMode { Normal, AddingTask, EditingTask, ConfirmDelete, PickingBlocker, Archive, Board }
 
 #[derive(Debug, Clone, PartialEq)]
-pub enum InputField { Title, Tags, Description }
+pub enum InputField { Title, Tags, Due, Effort, Description }
+
+impl InputField {
+    fn next(&self) -> Self {
+        match self {
+            InputField::Title       => InputField::Tags,
+            InputField::Tags        => InputField::Due,
+            InputField::Due         => InputField::Effort,
+            InputField::Effort      => InputField::Description,
+            InputField::Description => InputField::Title,
+        }
+    }
+}
 
 impl PlannerOverlay {
     pub fn new() -> Self {
-        let task_list = crate::planner::storage::load_tasks();
+        use crate::planner::archive::{due_for_archive, take_tasks};
+
+        let mut task_list = crate::planner::storage::load_tasks();
+        let mut archive = crate::planner::storage::load_archive().tasks;
+        if task_list.settings.columns.len() < 2 {
+            task_list.settings.columns = crate::planner::storage::PlannerSettings::default().columns;
+        }
+
+        // Completed tasks older than the configured age go to the archive on open
+        let days = task_list.settings.archive_after_days;
+        let stale = due_for_archive(&task_list.tasks, days, chrono::Utc::now());
+        let mut status = None;
+        if !stale.is_empty() {
+            archive.extend(take_tasks(&mut task_list.tasks, &stale).into_iter().map(|(_, t)| t));
+            let _ = crate::planner::storage::save_tasks(&task_list);
+            let _ = crate::planner::storage::save_archive(&crate::planner::storage::TaskList { tasks: archive.clone(), settings: Default::default() });
+            status = Some(format!("Auto-archived {} task(s) done {}d+ ago · v to browse", stale.len(), days));
+        }
+
+        // Pick up commits and branches that mention a task since the last open.
+        // Walking every repository's history is slow, so it runs on a snapshot
+        // and is merged by `commit_scan_tick`.
+        let mut snapshot = task_list.tasks.clone();
+        let close = task_list.settings.close_from_commits;
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            let report = crate::planner::commits::link_commits(&mut snapshot, close);
+            let _ = tx.send((snapshot, report));
+        });
+
+        let sort = crate::planner::schedule::TaskSort::Created;
+        crate::planner::schedule::sort_tasks(&mut task_list.tasks, sort);
+        crate::planner::tree::arrange(&mut task_list.tasks);
         Self {
             tasks: task_list.tasks,
             history: History::new(),
@@ -95,17 +160,95 @@ impl PlannerOverlay {
             input_buf: String::new(),
             input_cursor: 0,
             input_field: InputField::Title,
+            input_error: None,
+            sort,
+            new_parent: None,
+            link_source: None,
+            notice: None,
+            status,
+            settings: task_list.settings,
+            archive,
+            archive_selected: 0,
+            archive_query: String::new(),
+            archive_searching: false,
+            board_column: 0,
+            commit_scan: Some(rx),
             scroll: 0,
         }
     }
 
+    /// Merge the commit scan started on open, once it has finished
+    pub fn commit_scan_tick(&mut self) {
+        let (scanned, report) = match self.commit_scan.as_ref().map(|rx| rx.try_recv()) {
+            Some(Ok(result)) => result,
+            Some(Err(std::sync::mpsc::TryRecvError::Empty)) | None => return,
+            Some(Err(std::sync::mpsc::TryRecvError::Disconnected)) => {
+                self.commit_scan = None;
+                return;
+            }
+        };
+        self.commit_scan = None;
+        if report.is_empty() {
+            return;
+        }
+
+        let id = self.selected_task().map(|t| t.id.clone());
+        crate::planner::commits::merge_links(&mut self.tasks, scanned, &report);
+        self.save();
+        if let Some(id) = id {
+            self.resort(&id);
+        }
+
+        let mut notes = Vec::new();
+        if report.linked > 0 {
+            notes.push(format!("Linked {} commit(s)", report.linked));
+        }
+        if !report.closed.is_empty() {
+            notes.push(format!("Closed {} task(s) from commits", report.closed.len()));
+        }
+        notes.extend(self.status.take());
+        self.status = Some(notes.join(" · "));
+    }
+
     pub fn selected_task(&self) -> Option<&Task> {
         self.tasks.get(self.selected)
     }
 
     pub fn save(&self) {
-        let tl = crate::planner::storage::TaskList { tasks: self.tasks.clone() };
+        let tl = crate::planner::storage::TaskList { tasks: self.tasks.clone(), settings: self.settings.clone() };
         let _ = crate::planner::storage::save_tasks(&tl);
+        let archived = crate::planner::storage::TaskList { tasks: self.archive.clone(), settings: Default::default() };
+        let _ = crate::planner::storage::save_archive(&archived);
+    }
+
+    /// Archive indices matching the search, most recently completed first
+    pub fn archive_matches(&self) -> Vec<usize> {
+        crate::planner::archive::search(&self.archive, &self.archive_query)
+    }
+
+    pub fn selected_archived(&self) -> Option<&Task> {
+        self.archive_matches().get(self.archive_selected).and_then(|i| self.archive.get(*i))
+    }
+
+    /// Task indices per board column
+    pub fn board(&self) -> Vec<Vec<usize>> {
+        crate::planner::board::by_column(&self.tasks, &self.settings.columns)
+    }
+
+    /// The highlighted card: the selected task if it is in the cursor's column, else that column's first
+    pub fn board_card(&self) -> Option<usize> {
+        let board = self.board();
+        let column = board.get(self.board_column)?;
+        column.contains(&self.selected).then_some(self.selected).or_else(|| column.first().copied())
+    }
+
+    /// Re-apply the current sort and subtask nesting, keeping the task with `id` selected.
+    pub fn resort(&mut self, id: &str) {
+        crate::planner::schedule::sort_tasks(&mut self.tasks, self.sort);
+        crate::planner::tree::arrange(&mut self.tasks);
+        if let Some(i) = self.tasks.iter().position(|t| t.id == id) {
+            self.selected = i;
+        }
     }
 }
 
@@ -155,14 +298,35 @@ impl AuthOverlay {
 // ─── PRP Overlay ──────────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, PartialEq)]
-pub enum PrpStep { SelectRepos, ReviewChanges, EnterMessage, ConfirmPush, Result }
+pub enum PrpStep { SelectRepos, ReviewChanges, EnterMessage, ConfirmPush, PushFailed, Result }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum DiffKind { Added, Removed, Modified, Header, Stat, Neutral }
 
+/// Review-step checklist for one repository
+pub struct RepoStaging {
+    pub path: String,
+    pub name: String,
+    pub changes: Vec<crate::prp_hub::staging::FileChange>,
+    pub selection: crate::prp_hub::staging::RepoSelection,
+    /// `git status` / `--stat` summary shown when the repo row is focused
+    pub summary: Vec<(String, DiffKind)>,
+    /// Indices of files whose hunks are listed
+    pub expanded: Vec<usize>,
+    /// Replaces the shared commit message for this repo only
+    pub message_override: Option<String>,
+    /// Pre-commit check results; None while they are still running
+    pub checks: Option<crate::prp_hub::checks::RepoChecks>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StagingRow { Repo(usize), File(usize, usize), Hunk(usize, usize, usize) }
+
 pub struct PrpOverlay {
     pub repos: Vec<String>,
     pub included: Vec<bool>,
+    /// Checked-out branch per repo (None when detached)
+    pub branches: Vec<Option<String>>,
     pub selected: usize,
     pub step: PrpStep,
     pub input_buf: String,
@@ -170,16 +334,51 @@ pub struct PrpOverlay {
     pub result_lines: Vec<String>,
     pub diff_lines: Vec<(String, DiffKind)>,
     pub diff_scroll: usize,
+    pub staging: Vec<RepoStaging>,
+    pub stage_cursor: usize,
+    /// Repo whose message override is being typed in the review step
+    pub override_edit: Option<usize>,
+    pub override_buf: String,
+    /// Rendered per-repo messages, filled when the message step passes validation
+    pub prepared_messages: std::collections::HashMap<std::path::PathBuf, String>,
+    pub message_errors: Vec<String>,
+    /// "repo (format)" for every repo whose commits will be signed
+    pub signing_notes: Vec<String>,
+    /// Selected repos in dependency order, filled with the messages
+    pub commit_order: Vec<crate::prp_hub::types::RepositoryInfo>,
+    /// "api needs shared (Cargo.toml path)" for every dependency found
+    pub dependency_notes: Vec<String>,
+    /// Pre-commit check results arriving from the background run
+    pub checks_rx: Option<std::sync::mpsc::Receiver<crate::prp_hub::checks::RepoChecks>>,
+    /// Failing checks were skipped after confirmation
+    pub checks_skipped: bool,
+    /// Waiting for y/n on skipping failing checks
+    pub confirm_skip_checks: bool,
+    /// Why Enter did not leave the review step
+    pub review_notice: Option<String>,
+    /// Report of the pull run before review, kept when it failed
+    pub pull_notes: Vec<String>,
+    /// Reports of the pull before review; Some while it runs in the background
+    pub pull_rx: Option<std::sync::mpsc::Receiver<Vec<crate::prp_hub::pull::PullReport>>>,
     pub push_to_remote: bool,
+    /// Set while a partially pushed session waits for a recovery choice
+    pub session: Option<crate::prp_hub::types::CommitSession>,
+    pub push_report: Option<crate::prp_hub::types::PushReport>,
+    pub journal: Option<crate::prp_hub::journal::SessionJournal>,
     pub done: bool,
 }
 
 impl PrpOverlay {
     pub fn new(repos: Vec<String>) -> Self {
         let len = repos.len();
+        let branches = repos
+            .iter()
+            .map(|p| crate::prp_hub::branch::current_branch(std::path::Path::new(p)))
+            .collect();
         Self {
             repos,
             included: vec![true; len],
+            branches,
             selected: 0,
             step: PrpStep::SelectRepos,
             input_buf: String::new(),
@@ -187,10 +386,71 @@ impl PrpOverlay {
             result_lines: vec![],
             diff_lines: vec![],
             diff_scroll: 0,
+            staging: vec![],
+            stage_cursor: 0,
+            override_edit: None,
+            override_buf: String::new(),
+            prepared_messages: std::collections::HashMap::new(),
+            message_errors: vec![],
+            signing_notes: vec![],
+            commit_order: vec![],
+            dependency_notes: vec![],
+            checks_rx: None,
+            checks_skipped: false,
+            confirm_skip_checks: false,
+            review_notice: None,
+            pull_notes: vec![],
+            pull_rx: None,
             push_to_remote: false,
+            session: None,
+            push_report: None,
+            journal: None,
             done: false,
         }
     }
+
+    /// True when the included repos are not all on the same branch.
+    pub fn branches_diverge(&self) -> bool {
+        let active: Vec<Option<String>> = self.branches.iter().enumerate()
+            .filter(|(i, _)| self.included[*i])
+            .map(|(_, b)| b.clone())
+            .collect();
+        crate::prp_hub::branch::branches_diverge(&active)
+    }
+
+    /// Repos with files picked whose checks failed (or are still running), unless skipped.
+    pub fn blocking_checks(&self) -> Vec<usize> {
+        self.staging.iter().enumerate()
+            .filter(|(_, r)| r.selection.included_count() > 0)
+            .filter(|(_, r)| match &r.checks {
+                None => true,
+                Some(c) => !c.passed() && !self.checks_skipped,
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Flattened checklist rows: each repo, its files, and hunks of expanded files.
+    pub fn staging_rows(&self) -> Vec<StagingRow> {
+        let mut rows = Vec::new();
+        for (ri, repo) in self.staging.iter().enumerate() {
+            rows.push(StagingRow::Repo(ri));
+            for (fi, change) in repo.changes.iter().enumerate() {
+                rows.push(StagingRow::File(ri, fi));
+                if repo.expanded.contains(&fi) {
+                    rows.extend((0..change.hunks.len()).map(|hi| StagingRow::Hunk(ri, fi, hi)));
+                }
+            }
+        }
+        rows
+    }
+
+    pub fn selected_repos(&self) -> Vec<crate::prp_hub::types::RepositoryInfo> {
+        self.repos.iter().enumerate()
+            .filter(|(i, _)| self.included[*i])
+            .map(|(_, p)| crate::prp_hub::types::RepositoryInfo::from_path(std::path::PathBuf::from(p)))
+            .collect()
+    }
 }
 
 
@@ -250,16 +510,82 @@ impl MultiSyncOverlay {
     }
 }
 
+// ─── Exec Overlay ─────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExecStatus { Queued, Running, Done(Option<i32>) }
+
+/// Streamed output and state of one repository in a `/prp exec` run
+pub struct ExecRepo {
+    pub name: String,
+    pub status: ExecStatus,
+    /// (line, from stderr)
+    pub lines: Vec<(String, bool)>,
+    pub elapsed: Option<Duration>,
+}
+
+pub struct ExecOverlay {
+    pub command: String,
+    pub repos: Vec<ExecRepo>,
+    pub selected: usize,
+    /// Lines scrolled back from the newest output; 0 follows the stream
+    pub scroll_back: usize,
+    pub frame: usize,
+    pub rx: Option<std::sync::mpsc::Receiver<crate::prp_hub::exec::ExecEvent>>,
+    pub cancel: std::sync::Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl ExecOverlay {
+    pub fn new(command: String, names: Vec<String>) -> Self {
+        Self {
+            command,
+            repos: names.into_iter()
+                .map(|name| ExecRepo { name, status: ExecStatus::Queued, lines: vec![], elapsed: None })
+                .collect(),
+            selected: 0,
+            scroll_back: 0,
+            frame: 0,
+            rx: None,
+            cancel: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
+        }
+    }
+
+    pub fn finished(&self) -> bool {
+        self.repos.iter().all(|r| matches!(r.status, ExecStatus::Done(_)))
+    }
+
+    pub fn results(&self) -> Vec<crate::prp_hub::exec::ExecResult> {
+        self.repos.iter().map(|r| crate::prp_hub::exec::ExecResult {
+            name: r.name.clone(),
+            code: match r.status { ExecStatus::Done(code) => code, _ => None },
+            elapsed: r.elapsed.unwrap_or(Duration::ZERO),
+        }).collect()
+    }
+}
+
+// ─── Groups Overlay ───────────────────────────────────────────────────────────
+
+/// `/prp groups`: Group-IDs newest first, with member commits or the combined diff
+pub struct GroupsOverlay {
+    pub groups: Vec<crate::prp_hub::group::CommitGroup>,
+    pub selected: usize,
+    /// Combined diff of the selected group; None shows the commit details
+    pub diff: Option<Vec<crate::prp_hub::group::PatchLine>>,
+    pub scroll: usize,
+}
+
 // ─── Overlay enum ─────────────────────────────────────────────────────────────
 
 pub enum Overlay {
     Scanner(ScannerOverlay),
-    Planner(PlannerOverlay),
+    Planner(Box<PlannerOverlay>),
     Ignore(IgnoreOverlay),
     Info(InfoOverlay),
     Auth(AuthOverlay),
-    Prp(PrpOverlay),
+    Prp(Box<PrpOverlay>),
     MultiSync(MultiSyncOverlay),
+    Exec(ExecOverlay),
+    Groups(GroupsOverlay),
 }
 
 // ─── Output ───────────────────────────────────────────────────────────────────
@@ -318,6 +644,13 @@ impl App {
             kind: OutputKind::Info,
             content: "Welcome to GitLink TUI. Type / to see available commands.".to_string(),
         });
+        if crate::planner::storage::tasks_exist() {
+            let tasks = crate::planner::storage::load_tasks().tasks;
+            let today = chrono::Local::now().date_naive();
+            if let Some(banner) = crate::planner::schedule::agenda_banner(&tasks, today) {
+                app.outputs.push(OutputBlock { kind: OutputKind::Info, content: banner });
+            }
+        }
         app
     }
 
@@ -331,6 +664,16 @@ impl App {
                 self.needs_full_redraw = true;
             }
         }
+        if let Some(Overlay::Exec(ref mut ov)) = self.overlay {
+            exec_overlay_tick(ov);
+        }
+        if let Some(Overlay::Planner(ref mut ov)) = self.overlay {
+            ov.commit_scan_tick();
+        }
+        if let Some(Overlay::Prp(ref mut ov)) = self.overlay {
+            prp_pull_tick(ov);
+            prp_checks_tick(ov);
+        }
     }
 
     pub fn on_key(&mut self, key: KeyEvent) -> bool {
@@ -470,6 +813,32 @@ impl App {
                     self.push_output(OutputBlock { kind: OutputKind::Info, content: "Multi-sync closed.".to_string() });
                 }
             }
+            Some(Overlay::Groups(ref mut ov)) => {
+                let close = handle_groups_key(ov, key);
+                if close {
+                    self.overlay = None;
+                    self.needs_full_redraw = true;
+                }
+            }
+            Some(Overlay::Exec(ref mut ov)) => {
+                let close = handle_exec_key(ov, key);
+                if close {
+                    // Leave the exit-code summary in the output history
+                    let results = ov.results();
+                    let failed = results.iter().any(|r| r.code != Some(0));
+                    let content = format!(
+                        "PRP Exec — {}\n{}",
+                        ov.command,
+                        crate::prp_hub::exec::format_exec_summary(&results)
+                    );
+                    self.overlay = None;
+                    self.needs_full_redraw = true;
+                    self.push_output(OutputBlock {
+                        kind: if failed { OutputKind::Error } else { OutputKind::Success },
+                        content,
+                    });
+                }
+            }
             None => {}
         }
         false
@@ -596,7 +965,7 @@ impl App {
 
     pub fn open_planner_overlay(&mut self) {
         self.is_executing = false;
-        self.overlay = Some(Overlay::Planner(PlannerOverlay::new()));
+        self.overlay = Some(Overlay::Planner(Box::new(PlannerOverlay::new())));
     }
 
     pub fn open_ignore_overlay(&mut self) {
@@ -632,10 +1001,49 @@ impl App {
                 content: "No git repositories found in the current directory.".to_string(),
             });
         } else {
-            self.overlay = Some(Overlay::Prp(PrpOverlay::new(repos)));
+            self.overlay = Some(Overlay::Prp(Box::new(PrpOverlay::new(repos))));
         }
     }
 
+    /// Start `/prp exec` in the background and show its live pane.
+    pub fn open_exec_overlay(&mut self, request: crate::prp_hub::exec::ExecRequest) {
+        self.is_executing = false;
+        let repos = match crate::prp_hub::exec::exec_targets(&request) {
+            Ok(r) => r,
+            Err(e) => {
+                self.push_output(OutputBlock { kind: OutputKind::Error, content: e.to_string() });
+                return;
+            }
+        };
+
+        let mut ov = ExecOverlay::new(request.command.clone(), repos.iter().map(|r| r.name.clone()).collect());
+        let (tx, rx) = std::sync::mpsc::channel();
+        let cancel = ov.cancel.clone();
+        std::thread::spawn(move || {
+            crate::prp_hub::exec::run_parallel(&repos, &request.command, request.jobs, tx, cancel);
+        });
+        ov.rx = Some(rx);
+        self.overlay = Some(Overlay::Exec(ov));
+    }
+
+    /// Browse the Group-IDs found in the repositories of a PRP session.
+    /// Show the groups loaded in the background by run_loop
+    pub fn open_groups_overlay(&mut self, loaded: Result<Vec<crate::prp_hub::group::CommitGroup>, String>) {
+        self.is_executing = false;
+        let groups = match loaded {
+            Ok(g) => g,
+            Err(e) => {
+                self.push_output(OutputBlock { kind: OutputKind::Error, content: e });
+                return;
+            }
+        };
+        if groups.is_empty() {
+            self.push_output(OutputBlock { kind: OutputKind::Info, content: "No commits with Group-ID trailers found.".to_string() });
+            return;
+        }
+        self.overlay = Some(Overlay::Groups(GroupsOverlay { groups, selected: 0, diff: None, scroll: 0 }));
+    }
+
     pub fn open_multi_sync_overlay(&mut self) {
         self.is_executing = false;
         self.overlay = Some(Overlay::MultiSync(MultiSyncOverlay::new_loading()));
@@ -845,7 +1253,38 @@ pub fn handle_auth_key(ov: &mut AuthOverlay, key: KeyEvent) -> bool {
 // ─── PRP overlay key handler ──────────────────────────────────────────────────
 
 pub fn handle_prp_key(ov: &mut PrpOverlay, key: KeyEvent) -> bool {
+    if let Some(ri) = ov.override_edit {
+        match key.code {
+            KeyCode::Esc => { ov.override_edit = None; }
+            KeyCode::Enter => {
+                let text = ov.override_buf.trim().to_string();
+                ov.staging[ri].message_override = if text.is_empty() { None } else { Some(text) };
+                ov.override_edit = None;
+            }
+            KeyCode::Backspace => { ov.override_buf.pop(); }
+            KeyCode::Char(c) => { ov.override_buf.push(c); }
+            _ => {}
+        }
+        return false;
+    }
+
+    if ov.confirm_skip_checks {
+        ov.confirm_skip_checks = false;
+        if key.code == KeyCode::Char('y') {
+            ov.checks_skipped = true;
+            ov.review_notice = Some("⚠ Failing checks skipped".to_string());
+        }
+        return false;
+    }
+
     match ov.step {
+        // Only Esc while the pull before review runs
+        PrpStep::SelectRepos if ov.pull_rx.is_some() => {
+            if key.code == KeyCode::Esc {
+                return true;
+            }
+        }
+
         PrpStep::SelectRepos => match key.code {
             KeyCode::Esc | KeyCode::Char('q') => return true,
             KeyCode::Up   | KeyCode::Char('k') => { if ov.selected > 0 { ov.selected -= 1; } }
@@ -853,31 +1292,58 @@ pub fn handle_prp_key(ov: &mut PrpOverlay, key: KeyEvent) -> bool {
             KeyCode::Char(' ') => {
                 if let Some(b) = ov.included.get_mut(ov.selected) { *b = !*b; }
             }
-            KeyCode::Enter => {
-                if ov.included.iter().any(|&b| b) {
-                    let selected: Vec<String> = ov.repos.iter().enumerate()
-                        .filter(|(i, _)| ov.included[*i])
-                        .map(|(_, p)| p.clone())
-                        .collect();
-                    ov.diff_lines = gather_diff_lines(&selected);
-                    ov.diff_scroll = 0;
-                    ov.step = PrpStep::ReviewChanges;
-                }
+            KeyCode::Enter if ov.included.iter().any(|&b| b) && !start_pull_before_review(ov) => {
+                enter_prp_review(ov);
             }
             _ => {}
         },
 
         PrpStep::ReviewChanges => match key.code {
             KeyCode::Esc | KeyCode::Char('q') => { ov.step = PrpStep::SelectRepos; }
-            KeyCode::Up   | KeyCode::Char('k') => { if ov.diff_scroll > 0 { ov.diff_scroll -= 1; } }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if ov.diff_scroll + 1 < ov.diff_lines.len() { ov.diff_scroll += 1; }
+            KeyCode::Up | KeyCode::Char('k') if ov.stage_cursor > 0 => {
+                ov.stage_cursor -= 1;
+                refresh_staging_preview(ov);
             }
+            KeyCode::Down | KeyCode::Char('j') if ov.stage_cursor + 1 < ov.staging_rows().len() => {
+                ov.stage_cursor += 1;
+                refresh_staging_preview(ov);
+            }
+            KeyCode::Char(' ') => { toggle_staging_row(ov); }
+            KeyCode::Char('m') => {
+                let ri = match ov.staging_rows().get(ov.stage_cursor) {
+                    Some(StagingRow::Repo(ri)) | Some(StagingRow::File(ri, _)) | Some(StagingRow::Hunk(ri, _, _)) => *ri,
+                    None => return false,
+                };
+                ov.override_buf = ov.staging[ri].message_override.clone().unwrap_or_default();
+                ov.override_edit = Some(ri);
+            }
+            KeyCode::Right | KeyCode::Char('l') => { expand_staging_row(ov, true); }
+            KeyCode::Left  | KeyCode::Char('h') => { expand_staging_row(ov, false); }
             KeyCode::PageUp   => { ov.diff_scroll = ov.diff_scroll.saturating_sub(15); }
             KeyCode::PageDown => {
                 ov.diff_scroll = (ov.diff_scroll + 15).min(ov.diff_lines.len().saturating_sub(1));
             }
+            KeyCode::Char('s') if ov.staging.iter().any(|r| r.checks.as_ref().is_some_and(|c| !c.passed())) => {
+                ov.confirm_skip_checks = true;
+            }
+            KeyCode::Char('r') => { start_prp_checks(ov); refresh_staging_preview(ov); }
             KeyCode::Enter => {
+                let blocked = ov.blocking_checks();
+                if let Some(&first) = blocked.first() {
+                    let running = blocked.iter().any(|&i| ov.staging[i].checks.is_none());
+                    let names: Vec<&str> = blocked.iter().map(|&i| ov.staging[i].name.as_str()).collect();
+                    ov.review_notice = Some(if running {
+                        format!("⧗ Checks still running: {}", names.join(", "))
+                    } else {
+                        format!("✖ Checks failed: {}  (r re-run, s skip)", names.join(", "))
+                    });
+                    if let Some(row) = ov.staging_rows().iter().position(|r| *r == StagingRow::Repo(first)) {
+                        ov.stage_cursor = row;
+                        refresh_staging_preview(ov);
+                    }
+                    return false;
+                }
+                ov.review_notice = None;
                 ov.step = PrpStep::EnterMessage;
                 ov.input_buf.clear();
                 ov.input_cursor = 0;
@@ -889,7 +1355,7 @@ pub fn handle_prp_key(ov: &mut PrpOverlay, key: KeyEvent) -> bool {
             KeyCode::Esc => { ov.step = PrpStep::ReviewChanges; }
             KeyCode::Enter => {
                 let msg = ov.input_buf.trim().to_string();
-                if !msg.is_empty() {
+                if !msg.is_empty() && prepare_prp_messages(ov) {
                     ov.step = PrpStep::ConfirmPush;
                 }
             }
@@ -908,30 +1374,30 @@ pub fn handle_prp_key(ov: &mut PrpOverlay, key: KeyEvent) -> bool {
         PrpStep::ConfirmPush => match key.code {
             KeyCode::Esc => { ov.step = PrpStep::EnterMessage; }
             KeyCode::Char('y') | KeyCode::Char('Y') => {
-                let msg = ov.input_buf.trim().to_string();
-                let selected: Vec<String> = ov.repos.iter().enumerate()
-                    .filter(|(i, _)| ov.included[*i])
-                    .map(|(_, p)| p.clone())
-                    .collect();
                 ov.push_to_remote = true;
-                ov.result_lines = run_prp_commit(selected, &msg, true);
-                ov.diff_scroll = 0;
-                ov.step = PrpStep::Result;
+                run_prp_session(ov);
             }
             KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Enter => {
-                let msg = ov.input_buf.trim().to_string();
-                let selected: Vec<String> = ov.repos.iter().enumerate()
-                    .filter(|(i, _)| ov.included[*i])
-                    .map(|(_, p)| p.clone())
-                    .collect();
                 ov.push_to_remote = false;
-                ov.result_lines = run_prp_commit(selected, &msg, false);
-                ov.diff_scroll = 0;
-                ov.step = PrpStep::Result;
+                run_prp_session(ov);
             }
             _ => {}
         },
 
+        PrpStep::PushFailed => match key.code {
+            KeyCode::Char('r') | KeyCode::Char('R') => { retry_prp_push(ov); }
+            KeyCode::Char('v') | KeyCode::Char('V') => { revert_prp_push(ov); }
+            KeyCode::Char('j') | KeyCode::Char('J') => { record_prp_push(ov); }
+            KeyCode::Esc => {
+                // Leaving keeps the partial push resumable, as with j
+                record_prp_push(ov);
+                return true;
+            }
+            KeyCode::Up if ov.diff_scroll > 0 => { ov.diff_scroll -= 1; }
+            KeyCode::Down if ov.diff_scroll + 1 < ov.result_lines.len() => { ov.diff_scroll += 1; }
+            _ => {}
+        },
+
         PrpStep::Result => match key.code {
             KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => return true,
             KeyCode::Up   | KeyCode::Char('k') => { if ov.diff_scroll > 0 { ov.diff_scroll -= 1; } }
@@ -948,6 +1414,315 @@ pub fn handle_prp_key(ov: &mut PrpOverlay, key: KeyEvent) -> bool {
     false
 }
 
+// Renders templates and runs commit-lint for every repo; false keeps the user on
+// the message step with the rejections listed.
+fn prepare_prp_messages(ov: &mut PrpOverlay) -> bool {
+    use crate::prp_hub::message::{prepare_messages, MessageInput};
+
+    let config = crate::prp_hub::config::load_config();
+    let infos = ov.selected_repos();
+    let inputs: Vec<MessageInput> = infos.iter().map(|info| {
+        let staging = ov.staging.iter().find(|r| std::path::Path::new(&r.path) == info.path);
+        MessageInput {
+            info,
+            override_message: staging.and_then(|r| r.message_override.as_deref()),
+            selection: staging.map(|r| &r.selection),
+        }
+    }).collect();
+
+    let plan = match crate::prp_hub::dependencies::plan_order(&infos, &config) {
+        Ok(plan) => plan,
+        Err(e) => {
+            ov.message_errors = e.to_string().lines()
+                .filter(|l| !l.trim().is_empty())
+                .map(|l| l.to_string())
+                .collect();
+            return false;
+        }
+    };
+
+    match prepare_messages(&inputs, ov.input_buf.trim(), &config) {
+        Ok(messages) => {
+            ov.dependency_notes = plan.describe();
+            ov.commit_order = plan.order;
+            ov.prepared_messages = messages;
+            ov.message_errors.clear();
+            ov.signing_notes = infos.iter()
+                .filter_map(|info| {
+                    crate::prp_hub::signing::signing_label(&info.path)
+                        .map(|l| format!("{} ({})", info.name, l))
+                })
+                .collect();
+            true
+        }
+        Err(errors) => {
+            ov.message_errors = errors.iter()
+                .flat_map(|e| e.to_string().lines().map(|l| l.to_string()).collect::<Vec<_>>())
+                .filter(|l| !l.trim().is_empty())
+                .collect();
+            false
+        }
+    }
+}
+
+// Builds the review checklist for the chosen repos. Untracked files start unticked.
+fn build_staging(repo_paths: &[String]) -> Vec<RepoStaging> {
+    use crate::prp_hub::staging::{collect_changes, RepoSelection};
+
+    repo_paths.iter().map(|path| {
+        let info = crate::prp_hub::types::RepositoryInfo::from_path(std::path::PathBuf::from(path));
+        let changes = collect_changes(&info);
+        RepoStaging {
+            path: path.clone(),
+            name: info.name,
+            selection: RepoSelection::default_for(&changes),
+            changes,
+            summary: gather_diff_lines(std::slice::from_ref(path)),
+            expanded: vec![],
+            message_override: None,
+            checks: None,
+        }
+    }).collect()
+}
+
+fn selected_prp_repos(ov: &PrpOverlay) -> Vec<String> {
+    ov.repos.iter().enumerate()
+        .filter(|(i, _)| ov.included[*i])
+        .map(|(_, p)| p.clone())
+        .collect()
+}
+
+fn enter_prp_review(ov: &mut PrpOverlay) {
+    ov.staging = build_staging(&selected_prp_repos(ov));
+    ov.stage_cursor = 0;
+    start_prp_checks(ov);
+    refresh_staging_preview(ov);
+    ov.step = PrpStep::ReviewChanges;
+}
+
+// With `pull.before_commit`, update the selected repos from upstream in the
+// background. False when there is nothing to pull and review can start now.
+fn start_pull_before_review(ov: &mut PrpOverlay) -> bool {
+    ov.pull_notes.clear();
+    let config = crate::prp_hub::config::load_config();
+    if !config.pull.before_commit {
+        return false;
+    }
+    let repos: Vec<_> = selected_prp_repos(ov).into_iter()
+        .map(|p| crate::prp_hub::types::RepositoryInfo::from_path(std::path::PathBuf::from(p)))
+        .collect();
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    ov.pull_rx = Some(rx);
+    std::thread::spawn(move || {
+        let _ = tx.send(crate::prp_hub::pull::pull_all(&repos, &config.pull, false));
+    });
+    true
+}
+
+// Move on to review once every selected repo pulled; otherwise keep the
+// report in `pull_notes` and stay on repo selection.
+pub fn prp_pull_tick(ov: &mut PrpOverlay) {
+    use crate::prp_hub::pull::{format_pull_report, pull_summary};
+
+    let reports = match ov.pull_rx.as_ref().map(|rx| rx.try_recv()) {
+        Some(Ok(reports)) => reports,
+        Some(Err(std::sync::mpsc::TryRecvError::Empty)) | None => return,
+        Some(Err(std::sync::mpsc::TryRecvError::Disconnected)) => {
+            ov.pull_rx = None;
+            ov.pull_notes = vec!["  ❌ Pull failed unexpectedly".to_string()];
+            return;
+        }
+    };
+    ov.pull_rx = None;
+    let (summary, ok) = pull_summary(&reports);
+    if ok {
+        enter_prp_review(ov);
+        return;
+    }
+    ov.pull_notes = reports.iter()
+        .flat_map(|r| format_pull_report(r).lines().map(str::to_string).collect::<Vec<_>>())
+        .filter(|l| !l.trim().is_empty())
+        .collect();
+    ov.pull_notes.push(summary);
+}
+
+// Run the pre-commit checks of every repo with changes in the background.
+fn start_prp_checks(ov: &mut PrpOverlay) {
+    let config = crate::prp_hub::config::load_config();
+    let mut pending = Vec::new();
+    for repo in ov.staging.iter_mut() {
+        let path = std::path::PathBuf::from(&repo.path);
+        if repo.changes.is_empty() || config.checks_for(&path).is_empty() {
+            repo.checks = Some(crate::prp_hub::checks::RepoChecks { path, name: repo.name.clone(), results: vec![] });
+        } else {
+            repo.checks = None;
+            pending.push(crate::prp_hub::types::RepositoryInfo::from_path(path));
+        }
+    }
+    ov.checks_skipped = false;
+    ov.review_notice = None;
+    if pending.is_empty() {
+        ov.checks_rx = None;
+        return;
+    }
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    ov.checks_rx = Some(rx);
+    std::thread::spawn(move || {
+        use rayon::prelude::*;
+        pending.par_iter().for_each_with(tx, |tx, info| {
+            let _ = tx.send(crate::prp_hub::checks::run_repo_checks(info, &config));
+        });
+    });
+}
+
+pub fn prp_checks_tick(ov: &mut PrpOverlay) {
+    let rx = match ov.checks_rx {
+        Some(ref rx) => rx,
+        None => return,
+    };
+    let arrived: Vec<_> = rx.try_iter().collect();
+    if arrived.is_empty() {
+        return;
+    }
+    for result in arrived {
+        if let Some(repo) = ov.staging.iter_mut().find(|r| std::path::Path::new(&r.path) == result.path) {
+            repo.checks = Some(result);
+        }
+    }
+    if ov.staging.iter().all(|r| r.checks.is_some()) {
+        ov.checks_rx = None;
+    }
+    if let Some(StagingRow::Repo(_)) = ov.staging_rows().get(ov.stage_cursor) {
+        let scroll = ov.diff_scroll;
+        refresh_staging_preview(ov);
+        ov.diff_scroll = scroll;
+    }
+}
+
+fn check_lines(repo: &RepoStaging) -> Vec<(String, DiffKind)> {
+    let checks = match &repo.checks {
+        None => return vec![
+            (String::new(), DiffKind::Neutral),
+            ("  ⧗ Pre-commit checks running…".to_string(), DiffKind::Stat),
+        ],
+        Some(c) if c.results.is_empty() => return vec![],
+        Some(c) => c,
+    };
+    let mut lines = vec![
+        (String::new(), DiffKind::Neutral),
+        ("  Pre-commit checks".to_string(), DiffKind::Header),
+    ];
+    for result in &checks.results {
+        let kind = if result.passed { DiffKind::Added } else { DiffKind::Removed };
+        lines.push((format!("  {}", crate::prp_hub::checks::describe_result(result)), kind));
+        if !result.passed {
+            for out in &result.output {
+                lines.push((format!("      {}", out), DiffKind::Neutral));
+            }
+        }
+    }
+    lines
+}
+
+// Shows the focused repo summary, file hunks, or single hunk in the preview pane.
+fn refresh_staging_preview(ov: &mut PrpOverlay) {
+    let rows = ov.staging_rows();
+    ov.diff_scroll = 0;
+    ov.diff_lines = match rows.get(ov.stage_cursor) {
+        None => vec![("  No repositories selected.".to_string(), DiffKind::Neutral)],
+        Some(StagingRow::Repo(ri)) => {
+            let repo = &ov.staging[*ri];
+            let mut lines = check_lines(repo);
+            lines.extend(repo.summary.iter().cloned());
+            lines
+        }
+        Some(StagingRow::File(ri, fi)) => {
+            let change = &ov.staging[*ri].changes[*fi];
+            let mut lines = vec![
+                (String::new(), DiffKind::Neutral),
+                (format!("  {}  ({})", change.path, change.kind.label()), DiffKind::Header),
+            ];
+            if change.hunks.is_empty() {
+                lines.push(("    Whole file — hunk selection applies to modified files only".to_string(), DiffKind::Neutral));
+            }
+            for hunk in &change.hunks {
+                push_hunk_lines(&mut lines, hunk);
+            }
+            lines
+        }
+        Some(StagingRow::Hunk(ri, fi, hi)) => {
+            let change = &ov.staging[*ri].changes[*fi];
+            let mut lines = vec![
+                (String::new(), DiffKind::Neutral),
+                (format!("  {}  — hunk {} of {}", change.path, hi + 1, change.hunks.len()), DiffKind::Header),
+            ];
+            push_hunk_lines(&mut lines, &change.hunks[*hi]);
+            lines
+        }
+    };
+}
+
+fn push_hunk_lines(lines: &mut Vec<(String, DiffKind)>, hunk: &crate::prp_hub::staging::Hunk) {
+    lines.push((String::new(), DiffKind::Neutral));
+    lines.push((format!("  {}", hunk.header), DiffKind::Stat));
+    for l in &hunk.lines {
+        let kind = match l.chars().next() {
+            Some('+') => DiffKind::Added,
+            Some('-') => DiffKind::Removed,
+            _         => DiffKind::Neutral,
+        };
+        lines.push((format!("  {}", l), kind));
+    }
+}
+
+fn toggle_staging_row(ov: &mut PrpOverlay) {
+    match ov.staging_rows().get(ov.stage_cursor).copied() {
+        Some(StagingRow::Repo(ri)) => {
+            let files = &mut ov.staging[ri].selection.files;
+            let on = !files.iter().any(|f| f.included);
+            for f in files.iter_mut() {
+                f.included = on;
+                f.hunks.iter_mut().for_each(|h| *h = on);
+            }
+        }
+        Some(StagingRow::File(ri, fi)) => {
+            let f = &mut ov.staging[ri].selection.files[fi];
+            f.included = !f.included;
+            let on = f.included;
+            f.hunks.iter_mut().for_each(|h| *h = on);
+        }
+        Some(StagingRow::Hunk(ri, fi, hi)) => {
+            let f = &mut ov.staging[ri].selection.files[fi];
+            f.hunks[hi] = !f.hunks[hi];
+            f.included = f.hunks.iter().any(|&h| h);
+        }
+        None => {}
+    }
+}
+
+// Right lists a file's hunks; left folds them (moving the cursor up from a hunk row).
+fn expand_staging_row(ov: &mut PrpOverlay, expand: bool) {
+    let rows = ov.staging_rows();
+    let (ri, fi) = match rows.get(ov.stage_cursor).copied() {
+        Some(StagingRow::File(ri, fi)) | Some(StagingRow::Hunk(ri, fi, _)) => (ri, fi),
+        _ => return,
+    };
+    let repo = &mut ov.staging[ri];
+    if expand {
+        if !repo.changes[fi].hunks.is_empty() && !repo.expanded.contains(&fi) {
+            repo.expanded.push(fi);
+        }
+    } else {
+        repo.expanded.retain(|&e| e != fi);
+        if let Some(pos) = ov.staging_rows().iter().position(|r| *r == StagingRow::File(ri, fi)) {
+            ov.stage_cursor = pos;
+        }
+    }
+    refresh_staging_preview(ov);
+}
+
 // Gathers `git status --short` + `git diff --stat` for each repo into styled lines.
 fn gather_diff_lines(repo_paths: &[String]) -> Vec<(String, DiffKind)> {
     let mut out: Vec<(String, DiffKind)> = Vec::new();
@@ -1054,7 +1829,40 @@ fn gather_diff_lines(repo_paths: &[String]) -> Vec<(String, DiffKind)> {
     out
 }
 
-fn run_prp_commit(repos: Vec<String>, message: &str, push: bool) -> Vec<String> {
+fn prp_error_lines(lines: &mut Vec<String>, e: &crate::prp_hub::errors::PrpError) {
+    for l in e.to_string().lines().filter(|l| !l.trim().is_empty()) {
+        lines.push(format!("    {}", l.trim()));
+    }
+}
+
+fn push_progress_line(lines: &mut Vec<String>, info: &crate::prp_hub::types::RepositoryInfo, result: &Result<(), crate::prp_hub::errors::PrpError>) {
+    match result {
+        Ok(()) => lines.push(format!("    ✔  Pushed {}", info.name)),
+        Err(_) => lines.push(format!("    ✖  Push failed: {}", info.name)),
+    }
+}
+
+fn prp_result_footer(lines: &mut Vec<String>) {
+    lines.push(String::new());
+    lines.push("────────────────────────────────────────────────────────────".to_string());
+    lines.push("  Press Enter or Esc to close.".to_string());
+}
+
+// Runs a full PRP session through prp_hub: validate → commit → (remote check → push).
+fn run_prp_session(ov: &mut PrpOverlay) {
+    use crate::prp_hub::{
+        commit::commit_each,
+        journal::{push_recorded, SessionJournal, SessionPhase},
+        push::preflight_all,
+        rollback::rollback_each,
+        state::validate_repo,
+        types::{new_group_id, CommitSession},
+    };
+
+    let message = ov.input_buf.trim().to_string();
+    let push = ov.push_to_remote;
+    let repos = ov.commit_order.clone();
+
     let mut lines = vec![
         "🔗 PRP Commit Session".to_string(),
         "────────────────────────────────────────────────────────────".to_string(),
@@ -1063,98 +1871,293 @@ fn run_prp_commit(repos: Vec<String>, message: &str, push: bool) -> Vec<String>
         String::new(),
     ];
 
-    for repo in &repos {
-        let name = std::path::Path::new(repo)
-            .file_name()
-            .map(|n| n.to_string_lossy().to_string())
-            .unwrap_or_else(|| repo.clone());
+    ov.diff_scroll = 0;
+    ov.step = PrpStep::Result;
 
-        lines.push(format!("  ▣  {}", name));
+    let errors: Vec<_> = repos.iter().filter_map(|r| validate_repo(r).err()).collect();
+    if !errors.is_empty() {
+        lines.push("  ✖  Validation failed — nothing was committed".to_string());
+        for e in &errors { prp_error_lines(&mut lines, e); }
+        prp_result_footer(&mut lines);
+        ov.result_lines = lines;
+        return;
+    }
 
-        // Stage all
-        if let Ok(o) = std::process::Command::new("git")
-            .args(["-C", repo, "add", "-A"])
-            .output()
-        {
-            if !o.status.success() {
-                let err = String::from_utf8_lossy(&o.stderr).trim().to_string();
-                lines.push(format!("    ✖  git add failed: {}", err));
-                lines.push(String::new());
-                continue;
+    let mut session = CommitSession::new(new_group_id(), repos);
+    session.selections = ov.staging.iter()
+        .map(|r| (std::path::PathBuf::from(&r.path), r.selection.clone()))
+        .collect();
+    session.messages = ov.prepared_messages.clone();
+    lines.push(format!("  Group-ID:        {}", session.group_id));
+    lines.push(String::new());
+
+    let committed = commit_each(&mut session, &message, |info, result| {
+        lines.push(format!("  ▣  {}", info.name));
+        match result {
+            Some(r) => lines.push(format!("    ✔  Committed {}", &r.commit_oid.to_string()[..8])),
+            None    => lines.push("    –  Nothing to commit (working tree clean)".to_string()),
+        }
+    });
+
+    if let Err(e) = committed {
+        lines.push(String::new());
+        lines.push("  ✖  Commit failed — rolling back".to_string());
+        prp_error_lines(&mut lines, &e);
+        for (path, outcome) in rollback_each(&session.committed) {
+            match outcome {
+                Ok(()) => lines.push(format!("    ↩  {} rolled back", path.display())),
+                Err(e) => prp_error_lines(&mut lines, &e),
             }
         }
+        if !session.committed.is_empty() {
+            let _ = SessionJournal::from_session(&session, &message, SessionPhase::RolledBack).save();
+        }
+        prp_result_footer(&mut lines);
+        ov.result_lines = lines;
+        return;
+    }
 
-        // Commit
-        match std::process::Command::new("git")
-            .args(["-C", repo, "commit", "-m", message])
-            .output()
-        {
-            Ok(out) => {
-                let stdout = String::from_utf8_lossy(&out.stdout).trim().to_string();
-                let stderr = String::from_utf8_lossy(&out.stderr).trim().to_string();
-                if out.status.success() {
-                    lines.push("    ✔  Committed".to_string());
-                    if let Some(first) = stdout.lines().next() {
-                        lines.push(format!("       {}", first));
-                    }
+    for link in &session.gitlink_bumps {
+        lines.push(format!("    🔗  Submodule pointer updated: {}", crate::prp_hub::submodule::describe_link(link, &session.repositories)));
+    }
 
-                    if push {
-                        match std::process::Command::new("git")
-                            .args(["-C", repo, "push"])
-                            .output()
-                        {
-                            Ok(po) => {
-                                if po.status.success() {
-                                    lines.push("    ✔  Pushed to remote".to_string());
-                                } else {
-                                    let perr = String::from_utf8_lossy(&po.stderr).trim().to_string();
-                                    lines.push(format!("    ⚠  Push failed: {}",
-                                                       perr.lines().next().unwrap_or("unknown")));
-                                }
-                            }
-                            Err(e) => { lines.push(format!("    ✖  Push error: {}", e)); }
-                        }
-                    }
-                } else {
-                    let reason = if stderr.contains("nothing to commit") {
-                        "Nothing to commit (working tree clean)".to_string()
-                    } else {
-                        stderr.lines().next().unwrap_or("commit failed").to_string()
-                    };
-                    lines.push(format!("    –  {}", reason));
-                }
+    // Recorded from here on so an interrupted session can be resumed or undone
+    let mut journal = SessionJournal::from_session(&session, &message, SessionPhase::Committed);
+    if !session.committed.is_empty() {
+        let _ = journal.save();
+    }
+
+    if session.committed.is_empty() || !push {
+        lines.push(String::new());
+        if session.committed.is_empty() {
+            lines.push("  –  Nothing to commit in any repository".to_string());
+        } else {
+            lines.push(format!("  ✔  Committed in {} repositories — commits are local", session.committed.len()));
+        }
+        prp_result_footer(&mut lines);
+        ov.result_lines = lines;
+        return;
+    }
+
+    // Phase 1: dry-run every remote before pushing anything
+    let committed_repos = session.committed_repos();
+    lines.push(String::new());
+    lines.push("  Checking remotes (dry-run)…".to_string());
+    let (_, preflight_errors) = preflight_all(&committed_repos);
+    if !preflight_errors.is_empty() {
+        lines.push("  ✖  Remote check failed — nothing was pushed, rolling back".to_string());
+        for e in &preflight_errors { prp_error_lines(&mut lines, e); }
+        for (path, outcome) in rollback_each(&session.committed) {
+            match outcome {
+                Ok(()) => lines.push(format!("    ↩  {} rolled back", path.display())),
+                Err(e) => prp_error_lines(&mut lines, &e),
             }
-            Err(e) => { lines.push(format!("    ✖  Failed to run git: {}", e)); }
         }
+        journal.phase = SessionPhase::RolledBack;
+        let _ = journal.save();
+        prp_result_footer(&mut lines);
+        ov.result_lines = lines;
+        return;
+    }
+    lines.push(format!("    ✔  {} remotes ready", committed_repos.len()));
 
-        lines.push(String::new());
+    // Phase 2: push in order
+    let report = push_recorded(&mut journal, &committed_repos, |info, result| push_progress_line(&mut lines, info, result));
+    ov.result_lines = lines;
+    finish_prp_push(ov, session, journal, report);
+}
+
+// Either completes the session or parks it in PushFailed awaiting a recovery choice.
+fn finish_prp_push(
+    ov: &mut PrpOverlay,
+    session: crate::prp_hub::types::CommitSession,
+    journal: crate::prp_hub::journal::SessionJournal,
+    report: crate::prp_hub::types::PushReport,
+) {
+    if let Some((failed, e)) = &report.failed {
+        ov.result_lines.push(String::new());
+        ov.result_lines.push(format!(
+            "  ⚠  Partial push: {} pushed, {} failed, {} not attempted",
+            report.pushed.len(), failed.name, report.remaining.len()
+        ));
+        prp_error_lines(&mut ov.result_lines, e);
+        ov.result_lines.push(String::new());
+        ov.result_lines.push("  r  retry remaining pushes".to_string());
+        ov.result_lines.push("  v  revert pushed repos and roll back the rest".to_string());
+        ov.result_lines.push("  j  stop here and finish later with /prp resume".to_string());
+        ov.result_lines.push("  Esc  same as j, and close".to_string());
+        ov.session = Some(session);
+        ov.journal = Some(journal);
+        ov.push_report = Some(report);
+        ov.step = PrpStep::PushFailed;
+    } else {
+        ov.result_lines.push(String::new());
+        ov.result_lines.push("  ✔  All repositories pushed".to_string());
+        prp_result_footer(&mut ov.result_lines);
+        ov.session = None;
+        ov.journal = None;
+        ov.push_report = None;
+        ov.step = PrpStep::Result;
     }
+}
 
-    lines.push("────────────────────────────────────────────────────────────".to_string());
-    lines.push("  Press Enter or Esc to close.".to_string());
-    lines
+fn retry_prp_push(ov: &mut PrpOverlay) {
+    let (session, mut journal, mut report) = match (ov.session.take(), ov.journal.take(), ov.push_report.take()) {
+        (Some(s), Some(j), Some(r)) => (s, j, r),
+        _ => return,
+    };
+    ov.result_lines.push(String::new());
+    ov.result_lines.push("  Retrying…".to_string());
+    let mut lines = std::mem::take(&mut ov.result_lines);
+    let retry = crate::prp_hub::journal::push_recorded(&mut journal, &report.unpushed(), |info, result| push_progress_line(&mut lines, info, result));
+    ov.result_lines = lines;
+    report.absorb(retry);
+    finish_prp_push(ov, session, journal, report);
+}
+
+fn revert_prp_push(ov: &mut PrpOverlay) {
+    let (session, mut journal, report) = match (ov.session.take(), ov.journal.take(), ov.push_report.take()) {
+        (Some(s), Some(j), Some(r)) => (s, j, r),
+        _ => return,
+    };
+    ov.result_lines.push(String::new());
+    ov.result_lines.push("  Reverting session…".to_string());
+    let outcomes = crate::prp_hub::rollback::revert_partial_push(&session, &report);
+    for (name, outcome) in &outcomes {
+        match outcome {
+            Ok(what) => ov.result_lines.push(format!("    ✔  {} {}", name, what)),
+            Err(e)   => prp_error_lines(&mut ov.result_lines, e),
+        }
+    }
+    crate::prp_hub::journal::record_reverted(&mut journal, &outcomes);
+    prp_result_footer(&mut ov.result_lines);
+    ov.step = PrpStep::Result;
+}
+
+fn record_prp_push(ov: &mut PrpOverlay) {
+    let mut journal = match ov.journal.take() {
+        Some(j) => j,
+        None => return,
+    };
+    ov.session = None;
+    ov.push_report = None;
+    ov.result_lines.push(String::new());
+    match journal.save() {
+        Ok(path) => {
+            ov.result_lines.push(format!("  ✔  Partial push recorded in {}", path.display()));
+            ov.result_lines.push("     Finish it later with /prp resume".to_string());
+        }
+        Err(e) => ov.result_lines.push(format!("  ✖  Could not write session journal: {}", e)),
+    }
+    prp_result_footer(&mut ov.result_lines);
+    ov.step = PrpStep::Result;
 }
 
 // ─── Planner key handler ──────────────────────────────────────────────────────
 
 use std::cell::RefCell;
 
+/// Field values of the add/edit modal while it is open
+#[derive(Debug, Clone, Default)]
+pub struct TaskDraft {
+    pub title: String,
+    pub tags: String,
+    pub due: String,
+    pub effort: String,
+    pub description: String,
+}
+
+impl TaskDraft {
+    pub fn field(&self, field: &InputField) -> &str {
+        match field {
+            InputField::Title       => &self.title,
+            InputField::Tags        => &self.tags,
+            InputField::Due         => &self.due,
+            InputField::Effort      => &self.effort,
+            InputField::Description => &self.description,
+        }
+    }
+
+    fn field_mut(&mut self, field: &InputField) -> &mut String {
+        match field {
+            InputField::Title       => &mut self.title,
+            InputField::Tags        => &mut self.tags,
+            InputField::Due         => &mut self.due,
+            InputField::Effort      => &mut self.effort,
+            InputField::Description => &mut self.description,
+        }
+    }
+}
+
 thread_local! {
-    static TASK_SCRATCH: RefCell<(String, String, String)> =
-        RefCell::new((String::new(), String::new(), String::new()));
+    static TASK_SCRATCH: RefCell<TaskDraft> = RefCell::new(TaskDraft::default());
 }
 
-pub fn planner_scratch_peek() -> (String, String, String) {
-    TASK_SCRATCH.with(|s| {
-        let sc = s.borrow();
-        (sc.0.clone(), sc.1.clone(), sc.2.clone())
-    })
+pub fn planner_scratch_peek() -> TaskDraft {
+    TASK_SCRATCH.with(|s| s.borrow().clone())
+}
+
+// Create or update the task from the modal's fields.
+fn commit_task_draft(ov: &mut PlannerOverlay) {
+    use crate::planner::history::Action;
+    use crate::planner::schedule::{parse_due, parse_effort};
+
+    let draft = planner_scratch_peek();
+    let title = draft.title.trim().to_string();
+    if title.is_empty() { return; }
+    let tags: Vec<String> = draft.tags.split(',')
+        .map(|t| t.trim().to_string())
+        .filter(|t| !t.is_empty()).collect();
+    let description = { let d = draft.description.trim().to_string(); if d.is_empty() { None } else { Some(d) } };
+    let due = parse_due(&draft.due, chrono::Local::now().date_naive()).ok().flatten();
+    let effort = parse_effort(&draft.effort).ok().flatten();
+
+    let id = if ov.mode == PlannerMode::AddingTask {
+        let mut task = Task::new(title);
+        task.set_tags(tags);
+        task.update_description(description);
+        task.set_due(due);
+        task.set_effort(effort);
+        task.set_parent(ov.new_parent.take());
+        ov.history.push(Action::Add { task: task.clone() });
+        let id = task.id.clone();
+        ov.tasks.push(task);
+        id
+    } else if let Some(task) = ov.tasks.get_mut(ov.selected) {
+        let old_title = task.title.clone();
+        let old_desc  = task.description.clone();
+        let old_tags  = task.tags.clone();
+        ov.history.push(Action::UpdateTitle { id: task.id.clone(), old_title, new_title: title.clone() });
+        ov.history.push(Action::UpdateDescription { id: task.id.clone(), old_desc, new_desc: description.clone() });
+        ov.history.push(Action::UpdateTags { id: task.id.clone(), old_tags, new_tags: tags.clone() });
+        if task.due != due {
+            ov.history.push(Action::UpdateDue { id: task.id.clone(), old_due: task.due, new_due: due });
+            task.set_due(due);
+        }
+        if task.effort_minutes != effort {
+            ov.history.push(Action::UpdateEffort { id: task.id.clone(), old_effort: task.effort_minutes, new_effort: effort });
+            task.set_effort(effort);
+        }
+        task.update_title(title);
+        task.update_description(description);
+        task.set_tags(tags);
+        task.id.clone()
+    } else {
+        return;
+    };
+    ov.resort(&id);
+    ov.save();
 }
 
 pub fn handle_planner_key(ov: &mut PlannerOverlay, key: KeyEvent) -> bool {
     use PlannerMode::*;
     use PlannerFocus::*;
 
+    if matches!(ov.mode, Normal | Board) {
+        ov.notice = None;
+        ov.status = None;
+    }
+
     match ov.mode {
         Normal => match key.code {
             KeyCode::Esc | KeyCode::Char('q') => return true,
@@ -1170,126 +2173,188 @@ pub fn handle_planner_key(ov: &mut PlannerOverlay, key: KeyEvent) -> bool {
                 clamp_scroll(ov);
             }
             KeyCode::Char(' ') if ov.focus == List => {
-                if let Some(task) = ov.tasks.get_mut(ov.selected) {
+                let blockers: Vec<String> = ov.selected_task()
+                    .filter(|t| !t.completed)
+                    .map(|t| crate::planner::tree::open_blockers(&ov.tasks, t).iter().map(|b| b.title.clone()).collect())
+                    .unwrap_or_default();
+                if !blockers.is_empty() {
+                    ov.notice = Some(format!("Blocked by: {}", blockers.join(", ")));
+                } else if let Some(task) = ov.tasks.get_mut(ov.selected) {
                     ov.history.push(crate::planner::history::Action::Toggle { id: task.id.clone() });
                     task.toggle();
+                    let id = task.id.clone();
+                    ov.resort(&id);
                     ov.save();
+                    clamp_scroll(ov);
                 }
             }
-            KeyCode::Char('a') => {
+            KeyCode::Char('a') | KeyCode::Char('A') => {
+                // A adds a subtask of the selected task
+                ov.new_parent = if key.code == KeyCode::Char('A') { ov.selected_task().map(|t| t.id.clone()) } else { None };
                 ov.mode = AddingTask;
                 ov.input_field = InputField::Title;
                 ov.input_buf.clear();
                 ov.input_cursor = 0;
-                TASK_SCRATCH.with(|s| *s.borrow_mut() = (String::new(), String::new(), String::new()));
+                ov.input_error = None;
+                TASK_SCRATCH.with(|s| *s.borrow_mut() = TaskDraft::default());
             }
             KeyCode::Char('e') if ov.focus == List => {
                 if let Some(task) = ov.tasks.get(ov.selected) {
                     ov.mode = EditingTask;
                     ov.input_field = InputField::Title;
-                    let title = task.title.clone();
-                    let tags  = task.tags.join(", ");
-                    let desc  = task.description.clone().unwrap_or_default();
-                    TASK_SCRATCH.with(|s| *s.borrow_mut() = (title.clone(), tags, desc));
-                    ov.input_buf = title;
+                    ov.input_error = None;
+                    let draft = TaskDraft {
+                        title: task.title.clone(),
+                        tags: task.tags.join(", "),
+                        due: task.due.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default(),
+                        effort: task.effort_minutes.map(crate::planner::schedule::format_effort).unwrap_or_default(),
+                        description: task.description.clone().unwrap_or_default(),
+                    };
+                    ov.input_buf = draft.title.clone();
                     ov.input_cursor = ov.input_buf.len();
+                    TASK_SCRATCH.with(|s| *s.borrow_mut() = draft);
                 }
             }
-            KeyCode::Char('d') if ov.focus == List => {
-                if !ov.tasks.is_empty() { ov.mode = ConfirmDelete; }
+            KeyCode::Char('p') if ov.focus == List => {
+                if let Some(task) = ov.tasks.get_mut(ov.selected) {
+                    let new_priority = task.priority.next();
+                    ov.history.push(crate::planner::history::Action::UpdatePriority {
+                        id: task.id.clone(),
+                        old_priority: task.priority,
+                        new_priority,
+                    });
+                    task.set_priority(new_priority);
+                    let id = task.id.clone();
+                    ov.resort(&id);
+                    ov.save();
+                    clamp_scroll(ov);
+                }
             }
-            KeyCode::Char('u') => {
-                ov.history.undo(&mut ov.tasks);
-                ov.save();
-                if ov.selected >= ov.tasks.len() && !ov.tasks.is_empty() {
-                    ov.selected = ov.tasks.len() - 1;
+            KeyCode::Char('x') if ov.focus == List => {
+                if let Some(task) = ov.selected_task() {
+                    let ids = crate::planner::archive::subtree_ids(&ov.tasks, &task.id);
+                    let taken = crate::planner::archive::take_tasks(&mut ov.tasks, &ids);
+                    ov.status = Some(format!("Archived {} task(s) · u to undo", taken.len()));
+                    ov.archive.extend(taken.iter().map(|(_, t)| t.clone()));
+                    ov.history.push(crate::planner::history::Action::Archive { tasks: taken });
+                    if ov.selected >= ov.tasks.len() { ov.selected = ov.tasks.len().saturating_sub(1); }
+                    ov.save();
+                    clamp_scroll(ov);
+                }
+            }
+            KeyCode::Char('v') => {
+                ov.mode = Archive;
+                ov.archive_selected = 0;
+                ov.archive_query.clear();
+                ov.archive_searching = false;
+            }
+            KeyCode::Char('b') if ov.focus == List => {
+                if let Some(task) = ov.selected_task() {
+                    ov.link_source = Some(task.id.clone());
+                    ov.mode = PickingBlocker;
                 }
             }
-            KeyCode::Char('r') => {
-                ov.history.redo(&mut ov.tasks);
+            KeyCode::Char('>') | KeyCode::Char('<') if ov.focus == List => {
+                move_in_tree(ov, key.code == KeyCode::Char('>'));
+            }
+            KeyCode::Char('s') => {
+                ov.sort = ov.sort.next();
+                let id = ov.selected_task().map(|t| t.id.clone()).unwrap_or_default();
+                ov.resort(&id);
                 ov.save();
+                clamp_scroll(ov);
+            }
+            KeyCode::Char('d') if ov.focus == List => {
+                if !ov.tasks.is_empty() { ov.mode = ConfirmDelete; }
+            }
+            KeyCode::Char('w') => {
+                if let Some(task) = ov.selected_task() {
+                    ov.board_column = crate::planner::board::column_index(task, &ov.settings.columns);
+                }
+                ov.mode = Board;
             }
+            KeyCode::Char('u') => step_history(ov, false),
+            KeyCode::Char('r') => step_history(ov, true),
             _ => {}
         },
 
+        Board => {
+            let board = ov.board();
+            let card = ov.board_card();
+            let row = card.and_then(|c| board[ov.board_column].iter().position(|i| *i == c)).unwrap_or(0);
+            let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+            match key.code {
+                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('w') => {
+                    if let Some(card) = card { ov.selected = card; }
+                    ov.mode = Normal;
+                    clamp_scroll(ov);
+                }
+                KeyCode::Left | KeyCode::Right if shift => move_card(ov, key.code == KeyCode::Right),
+                KeyCode::Char('H') | KeyCode::Char('L') => move_card(ov, key.code == KeyCode::Char('L')),
+                KeyCode::Left | KeyCode::Char('h') | KeyCode::Right | KeyCode::Char('l') => {
+                    let right = matches!(key.code, KeyCode::Right | KeyCode::Char('l'));
+                    let column = if right {
+                        (ov.board_column + 1).min(board.len().saturating_sub(1))
+                    } else {
+                        ov.board_column.saturating_sub(1)
+                    };
+                    ov.board_column = column;
+                    // Keep roughly the same row in the new column
+                    if let Some(i) = board[column].get(row).or(board[column].last()) {
+                        ov.selected = *i;
+                    }
+                }
+                KeyCode::Up | KeyCode::Char('k') if row > 0 => ov.selected = board[ov.board_column][row - 1],
+                KeyCode::Down | KeyCode::Char('j') => {
+                    if let Some(i) = board[ov.board_column].get(row + 1) { ov.selected = *i; }
+                }
+                KeyCode::Char('u') | KeyCode::Char('r') => {
+                    step_history(ov, key.code == KeyCode::Char('r'));
+                    if let Some(task) = ov.selected_task() {
+                        ov.board_column = crate::planner::board::column_index(task, &ov.settings.columns);
+                    }
+                }
+                _ => {}
+            }
+        }
+
         AddingTask | EditingTask => match key.code {
             KeyCode::Esc => {
                 ov.mode = Normal;
+                ov.new_parent = None;
                 ov.input_buf.clear();
                 ov.input_cursor = 0;
-                TASK_SCRATCH.with(|s| *s.borrow_mut() = (String::new(), String::new(), String::new()));
+                ov.input_error = None;
+                TASK_SCRATCH.with(|s| *s.borrow_mut() = TaskDraft::default());
             }
             KeyCode::Enter | KeyCode::Tab => {
-                match ov.input_field {
-                    InputField::Title => {
-                        if !ov.input_buf.trim().is_empty() {
-                            let saved = ov.input_buf.clone();
-                            TASK_SCRATCH.with(|s| {
-                                let mut sc = s.borrow_mut();
-                                sc.0 = saved;
-                                ov.input_buf = sc.1.clone();
-                            });
-                            ov.input_cursor = ov.input_buf.len();
-                            ov.input_field = InputField::Tags;
-                        }
-                    }
-                    InputField::Tags => {
-                        let saved = ov.input_buf.clone();
-                        TASK_SCRATCH.with(|s| {
-                            let mut sc = s.borrow_mut();
-                            sc.1 = saved;
-                            ov.input_buf = sc.2.clone();
-                        });
-                        ov.input_cursor = ov.input_buf.len();
-                        ov.input_field = InputField::Description;
-                    }
-                    InputField::Description => {
-                        if key.code == KeyCode::Tab {
-                            let saved = ov.input_buf.clone();
-                            TASK_SCRATCH.with(|s| {
-                                let mut sc = s.borrow_mut();
-                                sc.2 = saved;
-                                ov.input_buf = sc.0.clone();
-                            });
-                            ov.input_cursor = ov.input_buf.len();
-                            ov.input_field = InputField::Title;
-                        } else {
-                            let desc_val = ov.input_buf.trim().to_string();
-                            TASK_SCRATCH.with(|s| s.borrow_mut().2 = desc_val);
-                            TASK_SCRATCH.with(|s| {
-                                let sc = s.borrow();
-                                let title = sc.0.trim().to_string();
-                                if title.is_empty() { return; }
-                                let tags: Vec<String> = sc.1.split(',')
-                                    .map(|t| t.trim().to_string())
-                                    .filter(|t| !t.is_empty()).collect();
-                                let description = { let d = sc.2.trim().to_string(); if d.is_empty() { None } else { Some(d) } };
-                                if ov.mode == AddingTask {
-                                    let mut task = Task::new(title);
-                                    task.set_tags(tags);
-                                    task.update_description(description);
-                                    ov.history.push(crate::planner::history::Action::Add { task: task.clone() });
-                                    ov.tasks.push(task);
-                                    ov.selected = ov.tasks.len() - 1;
-                                } else if let Some(task) = ov.tasks.get_mut(ov.selected) {
-                                    let old_title = task.title.clone();
-                                    let old_desc  = task.description.clone();
-                                    let old_tags  = task.tags.clone();
-                                    ov.history.push(crate::planner::history::Action::UpdateTitle { id: task.id.clone(), old_title, new_title: title.clone() });
-                                    ov.history.push(crate::planner::history::Action::UpdateDescription { id: task.id.clone(), old_desc, new_desc: description.clone() });
-                                    ov.history.push(crate::planner::history::Action::UpdateTags { id: task.id.clone(), old_tags, new_tags: tags.clone() });
-                                    task.update_title(title);
-                                    task.update_description(description);
-                                    task.set_tags(tags);
-                                }
-                                ov.save();
-                            });
-                            ov.mode = Normal;
-                            ov.input_buf.clear();
-                            ov.input_cursor = 0;
-                            TASK_SCRATCH.with(|s| *s.borrow_mut() = (String::new(), String::new(), String::new()));
-                        }
-                    }
+                use crate::planner::schedule::{parse_due, parse_effort};
+
+                let value = ov.input_buf.clone();
+                let error = match ov.input_field {
+                    InputField::Title if value.trim().is_empty() => Some("A title is required".to_string()),
+                    InputField::Due    => parse_due(&value, chrono::Local::now().date_naive()).err(),
+                    InputField::Effort => parse_effort(&value).err(),
+                    _ => None,
+                };
+                if error.is_some() {
+                    ov.input_error = error;
+                    return false;
+                }
+                ov.input_error = None;
+                TASK_SCRATCH.with(|s| *s.borrow_mut().field_mut(&ov.input_field) = value);
+
+                if ov.input_field == InputField::Description && key.code == KeyCode::Enter {
+                    commit_task_draft(ov);
+                    ov.mode = Normal;
+                    ov.input_buf.clear();
+                    ov.input_cursor = 0;
+                    TASK_SCRATCH.with(|s| *s.borrow_mut() = TaskDraft::default());
+                    clamp_scroll(ov);
+                } else {
+                    ov.input_field = ov.input_field.next();
+                    ov.input_buf = planner_scratch_peek().field(&ov.input_field).to_string();
+                    ov.input_cursor = ov.input_buf.len();
                 }
             }
             KeyCode::Backspace => {
@@ -1301,6 +2366,50 @@ pub fn handle_planner_key(ov: &mut PlannerOverlay, key: KeyEvent) -> bool {
             _ => {}
         },
 
+        PickingBlocker => match key.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                if ov.selected > 0 { ov.selected -= 1; }
+                clamp_scroll(ov);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if ov.selected + 1 < ov.tasks.len() { ov.selected += 1; }
+                clamp_scroll(ov);
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => toggle_blocker(ov),
+            KeyCode::Esc | KeyCode::Char('b') | KeyCode::Char('q') => {
+                let id = ov.link_source.take().unwrap_or_default();
+                ov.mode = Normal;
+                ov.notice = None;
+                ov.resort(&id);
+                clamp_scroll(ov);
+            }
+            _ => {}
+        },
+
+        Archive if ov.archive_searching => {
+            match key.code {
+                KeyCode::Enter | KeyCode::Esc => ov.archive_searching = false,
+                KeyCode::Backspace => { ov.archive_query.pop(); }
+                KeyCode::Char(c) => ov.archive_query.push(c),
+                _ => {}
+            }
+            ov.archive_selected = 0;
+        }
+
+        Archive => match key.code {
+            KeyCode::Up | KeyCode::Char('k') if ov.archive_selected > 0 => ov.archive_selected -= 1,
+            KeyCode::Down | KeyCode::Char('j') if ov.archive_selected + 1 < ov.archive_matches().len() => {
+                ov.archive_selected += 1;
+            }
+            KeyCode::Char('/') => ov.archive_searching = true,
+            KeyCode::Enter => restore_archived(ov),
+            KeyCode::Esc | KeyCode::Char('v') | KeyCode::Char('q') => {
+                ov.mode = Normal;
+                ov.archive_query.clear();
+            }
+            _ => {}
+        },
+
         ConfirmDelete => match key.code {
             KeyCode::Char('y') | KeyCode::Enter => {
                 if ov.selected < ov.tasks.len() {
@@ -1319,6 +2428,142 @@ pub fn handle_planner_key(ov: &mut PlannerOverlay, key: KeyEvent) -> bool {
     false
 }
 
+// Undo or redo one step, keeping the same task selected where it still exists.
+fn step_history(ov: &mut PlannerOverlay, redo: bool) {
+    let id = ov.selected_task().map(|t| t.id.clone()).unwrap_or_default();
+    if redo {
+        ov.history.redo(&mut ov.tasks, &mut ov.archive);
+    } else {
+        ov.history.undo(&mut ov.tasks, &mut ov.archive);
+    }
+    ov.resort(&id);
+    ov.save();
+    if ov.selected >= ov.tasks.len() && !ov.tasks.is_empty() {
+        ov.selected = ov.tasks.len() - 1;
+    }
+}
+
+// Move the highlighted card one column left or right. Reaching the last
+// column completes the task, so open blockers refuse it like Space does.
+fn move_card(ov: &mut PlannerOverlay, right: bool) {
+    use crate::planner::board::move_to;
+
+    let Some(card) = ov.board_card() else { return };
+    let columns = ov.settings.columns.clone();
+    let to = if right { ov.board_column + 1 } else { ov.board_column.wrapping_sub(1) };
+    if to >= columns.len() {
+        return;
+    }
+
+    let task = &ov.tasks[card];
+    let (new_column, new_completed) = move_to(task, &columns, to);
+    if new_completed && !task.completed {
+        let blockers: Vec<String> = crate::planner::tree::open_blockers(&ov.tasks, task).iter().map(|b| b.title.clone()).collect();
+        if !blockers.is_empty() {
+            ov.notice = Some(format!("Blocked by: {}", blockers.join(", ")));
+            return;
+        }
+    }
+
+    let task = &mut ov.tasks[card];
+    ov.history.push(crate::planner::history::Action::MoveColumn {
+        id: task.id.clone(),
+        old_column: task.column.clone(),
+        new_column: new_column.clone(),
+        old_completed: task.completed,
+        new_completed,
+    });
+    task.set_column(new_column, new_completed);
+    let id = task.id.clone();
+    ov.board_column = to;
+    ov.resort(&id);
+    ov.save();
+}
+
+// Move the selected archived task and its archived subtasks back to the list.
+fn restore_archived(ov: &mut PlannerOverlay) {
+    let Some(task) = ov.selected_archived() else { return };
+    let (id, title) = (task.id.clone(), task.title.clone());
+    let ids = crate::planner::archive::subtree_ids(&ov.archive, &id);
+    let restored: Vec<Task> = crate::planner::archive::take_tasks(&mut ov.archive, &ids)
+        .into_iter()
+        .map(|(_, t)| t)
+        .collect();
+
+    ov.status = Some(format!("Restored \"{}\"{} · u to undo", title, match restored.len() {
+        1 => String::new(),
+        n => format!(" and {} subtask(s)", n - 1),
+    }));
+    ov.tasks.extend(restored.iter().cloned());
+    ov.history.push(crate::planner::history::Action::Restore { tasks: restored });
+    let remaining = ov.archive_matches().len();
+    if ov.archive_selected >= remaining { ov.archive_selected = remaining.saturating_sub(1); }
+    ov.resort(&id);
+    ov.save();
+    clamp_scroll(ov);
+}
+
+// Make the picked task block (or stop blocking) the task in `link_source`.
+fn toggle_blocker(ov: &mut PlannerOverlay) {
+    use crate::planner::history::Action;
+
+    let (Some(source), Some(picked)) = (ov.link_source.clone(), ov.selected_task().map(|t| t.id.clone())) else { return };
+    let Some(index) = ov.tasks.iter().position(|t| t.id == source) else { return };
+
+    if ov.tasks[index].blocked_by.contains(&picked) {
+        ov.history.push(Action::RemoveBlocker { id: source, blocker: picked.clone() });
+        ov.tasks[index].remove_blocker(&picked);
+        ov.notice = None;
+    } else if let Err(e) = crate::planner::tree::can_block(&ov.tasks, &source, &picked) {
+        ov.notice = Some(e);
+        return;
+    } else {
+        ov.history.push(Action::AddBlocker { id: source, blocker: picked.clone() });
+        ov.tasks[index].add_blocker(picked);
+        ov.notice = None;
+    }
+    ov.save();
+}
+
+// Indent the selected task under the sibling above it, or outdent it to its parent's level.
+fn move_in_tree(ov: &mut PlannerOverlay, indent: bool) {
+    use crate::planner::history::Action;
+
+    let Some(task) = ov.selected_task() else { return };
+    let (id, old_parent) = (task.id.clone(), task.parent.clone());
+
+    let new_parent = if indent {
+        // Siblings keep their relative order in the list, so the nearest one above is the new parent
+        let above = ov.tasks[..ov.selected].iter().rev().find(|t| t.parent == old_parent);
+        match above {
+            Some(t) => Some(t.id.clone()),
+            None => {
+                ov.notice = Some("Nothing above to nest this task under".to_string());
+                return;
+            }
+        }
+    } else {
+        match old_parent.as_deref().and_then(|p| ov.tasks.iter().find(|t| t.id == p)) {
+            Some(parent) => parent.parent.clone(),
+            None => return,
+        }
+    };
+    if let Some(parent) = new_parent.as_deref() {
+        if let Err(e) = crate::planner::tree::can_nest(&ov.tasks, &id, parent) {
+            ov.notice = Some(e);
+            return;
+        }
+    }
+
+    ov.history.push(Action::SetParent { id: id.clone(), old_parent, new_parent: new_parent.clone() });
+    if let Some(task) = ov.tasks.get_mut(ov.selected) {
+        task.set_parent(new_parent);
+    }
+    ov.resort(&id);
+    ov.save();
+    clamp_scroll(ov);
+}
+
 fn clamp_scroll(ov: &mut PlannerOverlay) {
     let visible = 15usize;
     if ov.selected < ov.scroll { ov.scroll = ov.selected; }
@@ -1340,6 +2585,104 @@ fn levenshtein(a: &str, b: &str) -> usize {
     }
     dp[m][n]
 }
+
+// ─── Exec overlay ─────────────────────────────────────────────────────────────
+
+pub fn exec_overlay_tick(ov: &mut ExecOverlay) {
+    use crate::prp_hub::exec::ExecEvent;
+
+    ov.frame = ov.frame.wrapping_add(1);
+    let rx = match ov.rx {
+        Some(ref rx) => rx,
+        None => return,
+    };
+    for event in rx.try_iter() {
+        match event {
+            ExecEvent::Started(i) => { ov.repos[i].status = ExecStatus::Running; }
+            ExecEvent::Line { repo, text, stderr } => { ov.repos[repo].lines.push((text, stderr)); }
+            ExecEvent::Finished { repo, code, elapsed } => {
+                ov.repos[repo].status = ExecStatus::Done(code);
+                ov.repos[repo].elapsed = Some(elapsed);
+            }
+        }
+    }
+}
+
+fn cancel_exec(ov: &mut ExecOverlay) {
+    ov.cancel.store(true, std::sync::atomic::Ordering::Relaxed);
+    // Repos that never started are reported as not run
+    for r in ov.repos.iter_mut().filter(|r| r.status == ExecStatus::Queued) {
+        r.status = ExecStatus::Done(None);
+    }
+}
+
+pub fn handle_exec_key(ov: &mut ExecOverlay, key: KeyEvent) -> bool {
+    let lines = ov.repos.get(ov.selected).map(|r| r.lines.len()).unwrap_or(0);
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') if ov.finished() => return true,
+        KeyCode::Char('c') | KeyCode::Esc if !ov.finished() => { cancel_exec(ov); }
+        KeyCode::Up | KeyCode::Char('k') if ov.selected > 0 => {
+            ov.selected -= 1;
+            ov.scroll_back = 0;
+        }
+        KeyCode::Down | KeyCode::Char('j') if ov.selected + 1 < ov.repos.len() => {
+            ov.selected += 1;
+            ov.scroll_back = 0;
+        }
+        KeyCode::PageUp   => { ov.scroll_back = (ov.scroll_back + 15).min(lines.saturating_sub(1)); }
+        KeyCode::PageDown => { ov.scroll_back = ov.scroll_back.saturating_sub(15); }
+        KeyCode::End      => { ov.scroll_back = 0; }
+        _ => {}
+    }
+    false
+}
+
+// ─── Groups overlay ───────────────────────────────────────────────────────────
+
+pub fn handle_groups_key(ov: &mut GroupsOverlay, key: KeyEvent) -> bool {
+    match key.code {
+        KeyCode::Esc if ov.diff.is_some() => {
+            ov.diff = None;
+            ov.scroll = 0;
+        }
+        KeyCode::Esc | KeyCode::Char('q') => return true,
+        KeyCode::Char('d') | KeyCode::Enter => {
+            ov.diff = match ov.diff {
+                Some(_) => None,
+                None => ov.groups.get(ov.selected).map(crate::prp_hub::group::group_patch),
+            };
+            ov.scroll = 0;
+        }
+        KeyCode::Up | KeyCode::Char('k') if ov.selected > 0 => {
+            ov.selected -= 1;
+            ov.scroll = 0;
+            if ov.diff.is_some() {
+                ov.diff = Some(crate::prp_hub::group::group_patch(&ov.groups[ov.selected]));
+            }
+        }
+        KeyCode::Down | KeyCode::Char('j') if ov.selected + 1 < ov.groups.len() => {
+            ov.selected += 1;
+            ov.scroll = 0;
+            if ov.diff.is_some() {
+                ov.diff = Some(crate::prp_hub::group::group_patch(&ov.groups[ov.selected]));
+            }
+        }
+        KeyCode::PageDown | KeyCode::Char(' ') => {
+            let total = match &ov.diff {
+                Some(diff) => diff.len(),
+                None => ov.groups.get(ov.selected)
+                    .map(|g| g.commits.iter().map(|c| c.message.lines().count() + 5).sum())
+                    .unwrap_or(0),
+            };
+            ov.scroll = (ov.scroll + 15).min(total.saturating_sub(1));
+        }
+        KeyCode::PageUp   => { ov.scroll = ov.scroll.saturating_sub(15); }
+        KeyCode::Home     => { ov.scroll = 0; }
+        _ => {}
+    }
+    false
+}
+
 // ─── MultiSync key handler ────────────────────────────────────────────────────
 
 pub fn handle_multi_sync_key(ov: &mut MultiSyncOverlay, key: KeyEvent) -> bool {
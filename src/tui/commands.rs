@@ -11,8 +11,29 @@ pub static COMMANDS: &[Command] = &[
     Command { name: "scan history",     description: "Scan git history for exposed secrets" },
     Command { name: "scan ignored",     description: "List all permanently ignored findings" },
     Command { name: "plan",             description: "Open the task planner" },
+    Command { name: "plan import",      description: "Import a repository's GitHub issues as tasks" },
+    Command { name: "plan issue",       description: "Open a GitHub issue from a task" },
+    Command { name: "plan link",        description: "Link a task to an existing GitHub issue" },
+    Command { name: "plan unlink",      description: "Stop syncing a task with its issue" },
+    Command { name: "plan sync",        description: "Sync linked tasks and GitHub issues both ways" },
+    Command { name: "plan commits",     description: "Link commits that mention a task to it" },
+    Command { name: "plan autoclose",   description: "Complete tasks from Closes trailers on the default branch" },
+    Command { name: "plan columns",     description: "Show or set the planner board's workflow columns" },
     Command { name: "prp",              description: "Start a poly-repo commit session" },
-    Command { name: "prp list",         description: "List all PRP session groups" },
+    Command { name: "prp list",         description: "List PRP session groups and workspaces" },
+    Command { name: "prp groups",       description: "Browse PRP groups with commit details and diffs" },
+    Command { name: "prp --set",        description: "Start a PRP session on a named workspace" },
+    Command { name: "prp set",          description: "Show, add to or remove PRP workspaces" },
+    Command { name: "prp branch",       description: "Create a feature branch in every PRP repo" },
+    Command { name: "prp switch",       description: "Switch every PRP repo to a branch" },
+    Command { name: "prp pr",           description: "Open linked pull requests for a PRP group" },
+    Command { name: "prp pr status",    description: "Show review/merge status of a PRP group" },
+    Command { name: "prp tag",          description: "Tag and release every PRP repo as one group" },
+    Command { name: "prp pull",         description: "Fetch and update every PRP repo before committing" },
+    Command { name: "prp resume",       description: "Finish an interrupted PRP push" },
+    Command { name: "prp undo",         description: "Undo a PRP session by Group-ID" },
+    Command { name: "prp exec",         description: "Run a shell command in every PRP repo in parallel" },
+    Command { name: "workspace sync",   description: "Clone and fetch the repos listed in the workspace manifest" },
     Command { name: "show-activity",    description: "Show your GitHub contribution activity" },
     Command { name: "commits",          description: "Show recent commits for a repository" },
     Command { name: "pull-requests",    description: "Show open pull requests" },
@@ -90,9 +90,14 @@ pub fn draw_prp(f: &mut Frame, ov: &crate::tui::app::PrpOverlay) {
 
     let hint = match ov.step {
         PrpStep::SelectRepos  => "  ↑↓  navigate    Space  toggle    Enter  review changes    Esc  close  ",
-        PrpStep::ReviewChanges=> "  ↑↓ / PgUp PgDn  scroll    Enter  write message    Esc  back  ",
+        PrpStep::ReviewChanges if ov.override_edit.is_some()
+                              => "  Type message for this repo    Enter  save (empty clears)    Esc  cancel  ",
+        PrpStep::ReviewChanges if ov.confirm_skip_checks
+                              => "  Commit repositories whose checks failed?    y  skip checks    any other key  cancel  ",
+        PrpStep::ReviewChanges=> "  ↑↓  navigate    Space  include    →←  hunks    m  repo message    r / s  re-run / skip checks    Enter  message    Esc  back  ",
         PrpStep::EnterMessage => "  Type commit message    Enter  confirm    Esc  back  ",
         PrpStep::ConfirmPush  => "  y  push to remote    n / Enter  commit only    Esc  back  ",
+        PrpStep::PushFailed   => "  r  retry    v  revert pushed    j  record in journal    ↑↓  scroll  ",
         PrpStep::Result       => "  ↑↓ / PgUp PgDn  scroll    Enter / Esc  close  ",
     };
 
@@ -101,6 +106,7 @@ pub fn draw_prp(f: &mut Frame, ov: &crate::tui::app::PrpOverlay) {
         PrpStep::ReviewChanges => "  🔗 PRP Hub — Review Changes  ",
         PrpStep::EnterMessage  => "  🔗 PRP Hub — Commit Message  ",
         PrpStep::ConfirmPush   => "  🔗 PRP Hub — Push to Remote?  ",
+        PrpStep::PushFailed    => "  🔗 PRP Hub — Partial Push  ",
         PrpStep::Result        => "  🔗 PRP Hub — Result  ",
     };
 
@@ -132,7 +138,16 @@ pub fn draw_prp(f: &mut Frame, ov: &crate::tui::app::PrpOverlay) {
             draw_prp_select_detail(f, ov, panels[1], accent);
         }
         PrpStep::ReviewChanges => {
-            draw_prp_diff(f, ov, inner, accent);
+            let panels = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(42), Constraint::Percentage(58)])
+                .split(inner);
+            draw_prp_staging_list(f, ov, panels[0], accent);
+            if ov.override_edit.is_some() {
+                draw_prp_override_input(f, ov, panels[1], accent);
+            } else {
+                draw_prp_diff(f, ov, panels[1], accent);
+            }
         }
         PrpStep::EnterMessage => {
             let panels = Layout::default()
@@ -145,7 +160,7 @@ pub fn draw_prp(f: &mut Frame, ov: &crate::tui::app::PrpOverlay) {
         PrpStep::ConfirmPush => {
             draw_prp_confirm_push(f, ov, inner, accent);
         }
-        PrpStep::Result => {
+        PrpStep::PushFailed | PrpStep::Result => {
             draw_prp_result(f, ov, inner, accent);
         }
     }
@@ -214,7 +229,12 @@ fn draw_prp_repo_list(f: &mut Frame, ov: &crate::tui::app::PrpOverlay, area: Rec
 
         let bg = if is_cursor { Style::default().bg(Color::Rgb(22, 20, 38)) } else { Style::default() };
 
-        Line::from(vec![pointer, checkbox, Span::styled(display, name_style)]).style(bg)
+        let branch = match ov.branches.get(i).cloned().flatten() {
+            Some(b) => Span::styled(format!("  {}", b), Style::default().fg(Color::Rgb(120, 100, 170))),
+            None    => Span::styled("  (detached)", Style::default().fg(Color::Rgb(230, 180, 60))),
+        };
+
+        Line::from(vec![pointer, checkbox, Span::styled(display, name_style), branch]).style(bg)
     }).collect();
 
     f.render_widget(Paragraph::new(lines), list_area);
@@ -277,6 +297,36 @@ fn draw_prp_select_detail(f: &mut Frame, ov: &crate::tui::app::PrpOverlay, area:
         ]),
     ];
 
+    if ov.branches_diverge() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "  ⚠  Selected repos are on different branches",
+            Style::default().fg(Color::Rgb(230, 180, 60)).add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(Span::styled(
+            "     Align them with /prp switch <name>",
+            Style::default().fg(Color::Rgb(230, 180, 60)),
+        )));
+    }
+
+    if ov.pull_rx.is_some() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "  ⧗ Pulling selected repos from upstream…",
+            Style::default().fg(Color::Rgb(230, 180, 60)),
+        )));
+    }
+
+    if !ov.pull_notes.is_empty() {
+        lines.push(Line::from(""));
+        for note in &ov.pull_notes {
+            let color = if note.contains('❌') { Color::Rgb(220, 80, 80) }
+                else if note.contains('✅') { Color::Rgb(80, 210, 130) }
+                else { Color::Rgb(140, 148, 175) };
+            lines.push(Line::from(Span::styled(note.clone(), Style::default().fg(color))));
+        }
+    }
+
     if included > 0 {
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
@@ -297,6 +347,170 @@ fn draw_prp_select_detail(f: &mut Frame, ov: &crate::tui::app::PrpOverlay, area:
     f.render_widget(Paragraph::new(lines), inner);
 }
 
+fn draw_prp_staging_list(f: &mut Frame, ov: &crate::tui::app::PrpOverlay, area: Rect, accent: Color) {
+    use crate::prp_hub::staging::ChangeKind;
+    use crate::tui::app::StagingRow;
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Rgb(45, 50, 68)))
+        .style(Style::default().bg(Color::Rgb(10, 12, 18)))
+        .title(Span::styled("  Stage  ", Style::default().fg(accent).add_modifier(Modifier::BOLD)));
+
+    f.render_widget(block, area);
+
+    let list_area = Rect {
+        x: area.x + 2,
+        y: area.y + 1,
+        width: area.width.saturating_sub(4),
+        height: area.height.saturating_sub(2),
+    };
+
+    // Last line explains why Enter was refused
+    let list_area = match &ov.review_notice {
+        Some(notice) if list_area.height > 2 => {
+            let color = if ov.checks_skipped { Color::Rgb(230, 180, 60) } else { Color::Rgb(220, 80, 80) };
+            let notice_area = Rect { y: list_area.y + list_area.height - 1, height: 1, ..list_area };
+            f.render_widget(
+                Paragraph::new(Span::styled(notice.clone(), Style::default().fg(color))),
+                notice_area,
+            );
+            Rect { height: list_area.height - 2, ..list_area }
+        }
+        _ => list_area,
+    };
+
+    let rows = ov.staging_rows();
+    let visible = list_area.height as usize;
+    let scroll = if ov.stage_cursor >= visible { ov.stage_cursor + 1 - visible } else { 0 };
+
+    let check = |on: bool, partial: bool| {
+        if partial {
+            Span::styled("◐ ", Style::default().fg(Color::Rgb(230, 180, 60)))
+        } else if on {
+            Span::styled("☑ ", Style::default().fg(Color::Rgb(80, 210, 130)).add_modifier(Modifier::BOLD))
+        } else {
+            Span::styled("☐ ", Style::default().fg(Color::Rgb(55, 60, 78)))
+        }
+    };
+
+    let lines: Vec<Line> = rows.iter().enumerate().skip(scroll).take(visible).map(|(i, row)| {
+        let is_cursor = i == ov.stage_cursor;
+        let pointer = if is_cursor {
+            Span::styled("▶ ", Style::default().fg(accent))
+        } else {
+            Span::raw("  ")
+        };
+        let bg = if is_cursor { Style::default().bg(Color::Rgb(22, 20, 38)) } else { Style::default() };
+
+        let line = match *row {
+            StagingRow::Repo(ri) => {
+                let repo = &ov.staging[ri];
+                let picked = repo.selection.included_count();
+                let total  = repo.changes.len();
+                let mut spans = vec![
+                    pointer,
+                    check(picked > 0, picked > 0 && picked < total),
+                    Span::styled(repo.name.clone(), Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
+                    Span::styled(format!("  {}/{}", picked, total), Style::default().fg(Color::Rgb(90, 100, 130))),
+                ];
+                if total == 0 {
+                    spans.push(Span::styled("  clean", Style::default().fg(Color::Rgb(75, 80, 100))));
+                }
+                if repo.message_override.is_some() {
+                    spans.push(Span::styled("  ✎ own message", Style::default().fg(Color::Rgb(180, 140, 255))));
+                }
+                match &repo.checks {
+                    None => spans.push(Span::styled("  ⧗ checks", Style::default().fg(Color::Rgb(230, 180, 60)))),
+                    Some(c) if c.results.is_empty() => {}
+                    Some(c) if c.passed() => spans.push(Span::styled("  ✔ checks", Style::default().fg(Color::Rgb(80, 210, 130)))),
+                    Some(_) if ov.checks_skipped => spans.push(Span::styled("  ⚠ checks skipped", Style::default().fg(Color::Rgb(230, 180, 60)))),
+                    Some(_) => spans.push(Span::styled("  ✖ checks", Style::default().fg(Color::Rgb(220, 80, 80)).add_modifier(Modifier::BOLD))),
+                }
+                Line::from(spans)
+            }
+            StagingRow::File(ri, fi) => {
+                let change = &ov.staging[ri].changes[fi];
+                let sel    = &ov.staging[ri].selection.files[fi];
+                let partial = sel.included && sel.hunks.iter().any(|&h| !h);
+                let kind_color = match change.kind {
+                    ChangeKind::Modified  => Color::Rgb(230, 180, 60),
+                    ChangeKind::Added     => Color::Rgb(80, 210, 130),
+                    ChangeKind::Deleted   => Color::Rgb(220, 80, 80),
+                    ChangeKind::Untracked => Color::Rgb(120, 125, 150),
+                };
+                let fold = if change.hunks.is_empty() { "  " }
+                    else if ov.staging[ri].expanded.contains(&fi) { "▾ " } else { "▸ " };
+                Line::from(vec![
+                    pointer,
+                    Span::raw("  "),
+                    check(sel.included, partial),
+                    Span::styled(fold, Style::default().fg(Color::Rgb(90, 100, 130))),
+                    Span::styled(format!("{:9} ", change.kind.label()), Style::default().fg(kind_color)),
+                    Span::styled(change.path.clone(), Style::default().fg(Color::Rgb(200, 208, 230))),
+                ])
+            }
+            StagingRow::Hunk(ri, fi, hi) => {
+                let hunk = &ov.staging[ri].changes[fi].hunks[hi];
+                let on   = ov.staging[ri].selection.files[fi].hunks[hi];
+                Line::from(vec![
+                    pointer,
+                    Span::raw("      "),
+                    check(on, false),
+                    Span::styled(hunk.header.clone(), Style::default().fg(Color::Rgb(100, 155, 245))),
+                ])
+            }
+        };
+        line.style(bg)
+    }).collect();
+
+    f.render_widget(Paragraph::new(lines), list_area);
+}
+
+fn draw_prp_override_input(f: &mut Frame, ov: &crate::tui::app::PrpOverlay, area: Rect, accent: Color) {
+    let name = ov.override_edit
+        .and_then(|ri| ov.staging.get(ri))
+        .map(|r| r.name.clone())
+        .unwrap_or_default();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(accent))
+        .style(Style::default().bg(Color::Rgb(12, 14, 20)))
+        .title(Span::styled(format!("  Message for {}  ", name), Style::default().fg(accent).add_modifier(Modifier::BOLD)));
+
+    f.render_widget(block, area);
+
+    let inner = Rect {
+        x: area.x + 3,
+        y: area.y + 2,
+        width: area.width.saturating_sub(6),
+        height: area.height.saturating_sub(3),
+    };
+
+    let lines = vec![
+        Line::from(Span::styled(
+            "Replaces the shared message for this repository only:",
+            Style::default().fg(Color::Rgb(140, 148, 175)),
+        )),
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("  ❯ "),
+            Span::styled(ov.override_buf.clone(), Style::default().fg(Color::White)),
+            Span::styled(" ", Style::default().bg(Color::Rgb(180, 140, 255))),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "  Templates and commit-lint rules still apply.",
+            Style::default().fg(Color::Rgb(70, 78, 100)),
+        )),
+    ];
+
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
 fn draw_prp_diff(f: &mut Frame, ov: &crate::tui::app::PrpOverlay, area: Rect, accent: Color) {
     use crate::tui::app::DiffKind;
 
@@ -394,7 +608,7 @@ fn draw_prp_message_input(f: &mut Frame, ov: &crate::tui::app::PrpOverlay, area:
     else if char_count > 50 { Color::Rgb(230, 180, 60) }
     else { Color::Rgb(70, 78, 100) };
 
-    let lines = vec![
+    let mut lines = vec![
         Line::from(Span::styled(
             "Describe what you changed:",
             Style::default().fg(Color::Rgb(140, 148, 175)),
@@ -430,6 +644,16 @@ fn draw_prp_message_input(f: &mut Frame, ov: &crate::tui::app::PrpOverlay, area:
         ]),
     ];
 
+    if !ov.message_errors.is_empty() {
+        lines.push(Line::from(""));
+        for e in &ov.message_errors {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", e.trim_start()),
+                Style::default().fg(Color::Rgb(220, 80, 80)),
+            )));
+        }
+    }
+
     f.render_widget(Paragraph::new(lines), inner);
 }
 
@@ -475,6 +699,39 @@ fn draw_prp_confirm_push(f: &mut Frame, ov: &crate::tui::app::PrpOverlay, area:
                 Style::default().fg(Color::Rgb(80, 210, 130)),
             ),
         ]),
+    ];
+
+    if !ov.signing_notes.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled("  Signing:  ", Style::default().fg(Color::Rgb(90, 100, 130))),
+            Span::styled(ov.signing_notes.join(", "), Style::default().fg(Color::Rgb(180, 140, 255))),
+        ]));
+    }
+    let unchecked: Vec<&str> = ov.staging.iter()
+        .filter(|r| r.selection.included_count() > 0 && r.checks.as_ref().is_some_and(|c| !c.passed()))
+        .map(|r| r.name.as_str())
+        .collect();
+    if !unchecked.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled("  Checks:   ", Style::default().fg(Color::Rgb(90, 100, 130))),
+            Span::styled(format!("⚠ skipped for {}", unchecked.join(", ")), Style::default().fg(Color::Rgb(230, 180, 60))),
+        ]));
+    }
+    if !ov.dependency_notes.is_empty() {
+        let order = ov.commit_order.iter().map(|r| r.name.as_str()).collect::<Vec<_>>().join(" → ");
+        lines.push(Line::from(vec![
+            Span::styled("  Order:    ", Style::default().fg(Color::Rgb(90, 100, 130))),
+            Span::styled(order, Style::default().fg(Color::Rgb(100, 155, 245))),
+        ]));
+        for note in &ov.dependency_notes {
+            lines.push(Line::from(Span::styled(
+                format!("            • {}", note),
+                Style::default().fg(Color::Rgb(120, 130, 160)),
+            )));
+        }
+    }
+
+    lines.extend([
         Line::from(""),
         Line::from(Span::styled(
             "  ─────────────────────────────────────────────────────",
@@ -504,7 +761,7 @@ fn draw_prp_confirm_push(f: &mut Frame, ov: &crate::tui::app::PrpOverlay, area:
             Span::styled("  [ Esc ]  ", Style::default().fg(Color::Rgb(90, 100, 130))),
             Span::styled("Back to message", Style::default().fg(Color::Rgb(80, 88, 112))),
         ]),
-    ];
+    ]);
 
     // Show which repos will be committed
     lines.push(Line::from(""));
@@ -513,15 +770,22 @@ fn draw_prp_confirm_push(f: &mut Frame, ov: &crate::tui::app::PrpOverlay, area:
         Style::default().fg(Color::Rgb(38, 42, 58)),
     )));
     lines.push(Line::from(""));
-    for repo in &included {
-        let name = std::path::Path::new(repo)
-            .file_name()
-            .map(|n| n.to_string_lossy().to_string())
-            .unwrap_or_else(|| repo.to_string());
-        lines.push(Line::from(vec![
+    for info in &ov.commit_order {
+        let name = info.name.clone();
+        let mut spans = vec![
             Span::styled("  ▣  ", Style::default().fg(Color::Rgb(100, 155, 245))),
             Span::styled(name, Style::default().fg(Color::Rgb(180, 188, 215))),
-        ]));
+        ];
+        // Only call out repos whose message differs from the shared one
+        if let Some(msg) = ov.prepared_messages.get(&info.path) {
+            if msg != ov.input_buf.trim() {
+                spans.push(Span::styled(
+                    format!("  — {}", msg.lines().next().unwrap_or("")),
+                    Style::default().fg(Color::Rgb(140, 148, 175)),
+                ));
+            }
+        }
+        lines.push(Line::from(spans));
     }
 
     f.render_widget(Paragraph::new(lines), inner);
@@ -1217,4 +1481,379 @@ fn draw_multi_sync_results(f: &mut Frame, ov: &crate::tui::app::MultiSyncOverlay
         .collect();
 
     f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
-}
\ No newline at end of file
+}
+// ─── PRP Exec ─────────────────────────────────────────────────────────────────
+
+pub fn draw_exec(f: &mut Frame, ov: &crate::tui::app::ExecOverlay) {
+    let area = f.area();
+    let popup = centered_rect(94, 88, area);
+    f.render_widget(Clear, popup);
+
+    let accent = Color::Rgb(130, 90, 200);
+    let hint = if ov.finished() {
+        "  ↑↓  repo    PgUp PgDn End  scroll    q / Esc  close  "
+    } else {
+        "  ↑↓  repo    PgUp PgDn End  scroll    c / Esc  cancel  "
+    };
+
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Rgb(60, 70, 90)))
+        .style(Style::default().bg(Color::Rgb(10, 12, 18)))
+        .title(Span::styled(
+            format!("  ⚡ PRP Exec — {}  ", ov.command),
+            Style::default().fg(accent).add_modifier(Modifier::BOLD),
+        ))
+        .title_alignment(Alignment::Left)
+        .title_bottom(Span::styled(hint, Style::default().fg(Color::Rgb(70, 80, 100))));
+
+    f.render_widget(outer, popup);
+
+    let inner = Rect {
+        x: popup.x + 1,
+        y: popup.y + 1,
+        width: popup.width.saturating_sub(2),
+        height: popup.height.saturating_sub(2),
+    };
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(2)])
+        .split(inner);
+    let panels = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+        .split(rows[0]);
+
+    draw_exec_repos(f, ov, panels[0], accent);
+    draw_exec_output(f, ov, panels[1], accent);
+    draw_exec_summary(f, ov, rows[1]);
+}
+
+fn exec_badge(status: &crate::tui::app::ExecStatus, frame: usize) -> (String, Color) {
+    use crate::tui::app::ExecStatus;
+    const SPINNER: [&str; 4] = ["◐", "◓", "◑", "◒"];
+    match status {
+        ExecStatus::Queued => ("·  queued".to_string(), Color::Rgb(90, 100, 130)),
+        ExecStatus::Running => (format!("{}  running", SPINNER[(frame / 6) % 4]), Color::Rgb(230, 190, 80)),
+        ExecStatus::Done(Some(0)) => ("✔  exit 0".to_string(), Color::Rgb(80, 210, 130)),
+        ExecStatus::Done(Some(code)) => (format!("✖  exit {}", code), Color::Rgb(220, 80, 80)),
+        ExecStatus::Done(None) => ("✖  killed".to_string(), Color::Rgb(220, 80, 80)),
+    }
+}
+
+fn draw_exec_repos(f: &mut Frame, ov: &crate::tui::app::ExecOverlay, area: Rect, accent: Color) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Rgb(45, 52, 68)))
+        .title(Span::styled("  Repositories  ", Style::default().fg(accent)));
+    f.render_widget(block, area);
+
+    let inner = Rect {
+        x: area.x + 2,
+        y: area.y + 1,
+        width: area.width.saturating_sub(4),
+        height: area.height.saturating_sub(2),
+    };
+
+    let visible = inner.height as usize;
+    let skip = (ov.selected + 1).saturating_sub(visible);
+    let lines: Vec<Line> = ov.repos.iter().enumerate().skip(skip).take(visible).map(|(i, r)| {
+        let (badge, color) = exec_badge(&r.status, ov.frame);
+        let name_style = if i == ov.selected {
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Rgb(180, 188, 215))
+        };
+        Line::from(vec![
+            Span::styled(if i == ov.selected { "❯ " } else { "  " }, Style::default().fg(accent)),
+            Span::styled(format!("{:<18} ", r.name), name_style),
+            Span::styled(badge, Style::default().fg(color)),
+        ])
+    }).collect();
+
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+fn draw_exec_output(f: &mut Frame, ov: &crate::tui::app::ExecOverlay, area: Rect, accent: Color) {
+    let repo = match ov.repos.get(ov.selected) {
+        Some(r) => r,
+        None => return,
+    };
+    let title = match repo.elapsed {
+        Some(e) => format!("  {} · {:.1}s  ", repo.name, e.as_secs_f32()),
+        None => format!("  {}  ", repo.name),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Rgb(45, 52, 68)))
+        .title(Span::styled(title, Style::default().fg(accent)));
+    f.render_widget(block, area);
+
+    let inner = Rect {
+        x: area.x + 2,
+        y: area.y + 1,
+        width: area.width.saturating_sub(4),
+        height: area.height.saturating_sub(2),
+    };
+
+    let visible = inner.height as usize;
+    let top = repo.lines.len().saturating_sub(visible + ov.scroll_back);
+    let lines: Vec<Line> = if repo.lines.is_empty() {
+        vec![Line::from(Span::styled("(no output yet)", Style::default().fg(Color::Rgb(75, 80, 100))))]
+    } else {
+        repo.lines.iter().skip(top).take(visible).map(|(text, stderr)| {
+            let color = if *stderr { Color::Rgb(230, 150, 110) } else { Color::Rgb(200, 205, 225) };
+            Line::from(Span::styled(text.clone(), Style::default().fg(color)))
+        }).collect()
+    };
+
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+fn draw_exec_summary(f: &mut Frame, ov: &crate::tui::app::ExecOverlay, area: Rect) {
+    use crate::tui::app::ExecStatus;
+
+    let count = |pred: &dyn Fn(&ExecStatus) -> bool| ov.repos.iter().filter(|r| pred(&r.status)).count();
+    let passed = count(&|s| *s == ExecStatus::Done(Some(0)));
+    let failed = count(&|s| matches!(s, ExecStatus::Done(c) if *c != Some(0)));
+    let running = count(&|s| *s == ExecStatus::Running);
+    let queued = count(&|s| *s == ExecStatus::Queued);
+
+    let line = Line::from(vec![
+        Span::styled(format!("  ✔ {} passed   ", passed), Style::default().fg(Color::Rgb(80, 210, 130))),
+        Span::styled(format!("✖ {} failed   ", failed), Style::default().fg(Color::Rgb(220, 80, 80))),
+        Span::styled(format!("● {} running   ", running), Style::default().fg(Color::Rgb(230, 190, 80))),
+        Span::styled(format!("· {} queued", queued), Style::default().fg(Color::Rgb(90, 100, 130))),
+    ]);
+    f.render_widget(Paragraph::new(vec![Line::from(""), line]), area);
+}
+
+pub fn draw_groups(f: &mut Frame, ov: &crate::tui::app::GroupsOverlay) {
+    let area = f.area();
+    let popup = centered_rect(94, 88, area);
+    f.render_widget(Clear, popup);
+
+    let accent = Color::Rgb(130, 90, 200);
+    let hint = if ov.diff.is_some() {
+        "  ↑↓  group    PgUp PgDn  scroll    d  details    Esc  back  "
+    } else {
+        "  ↑↓  group    PgUp PgDn  scroll    d / Enter  combined diff    q / Esc  close  "
+    };
+
+    let outer = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Rgb(60, 70, 90)))
+        .style(Style::default().bg(Color::Rgb(10, 12, 18)))
+        .title(Span::styled(
+            format!("  🔗 PRP Groups — {}  ", ov.groups.len()),
+            Style::default().fg(accent).add_modifier(Modifier::BOLD),
+        ))
+        .title_alignment(Alignment::Left)
+        .title_bottom(Span::styled(hint, Style::default().fg(Color::Rgb(70, 80, 100))));
+
+    f.render_widget(outer, popup);
+
+    let inner = Rect {
+        x: popup.x + 1,
+        y: popup.y + 1,
+        width: popup.width.saturating_sub(2),
+        height: popup.height.saturating_sub(2),
+    };
+
+    let panels = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(34), Constraint::Percentage(66)])
+        .split(inner);
+
+    draw_group_list(f, ov, panels[0], accent);
+    match &ov.diff {
+        Some(diff) => draw_group_diff(f, diff, ov.scroll, panels[1], accent),
+        None => draw_group_details(f, ov, panels[1], accent),
+    }
+}
+
+fn draw_group_list(f: &mut Frame, ov: &crate::tui::app::GroupsOverlay, area: Rect, accent: Color) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Rgb(45, 52, 68)))
+        .title(Span::styled("  Groups  ", Style::default().fg(accent)));
+    f.render_widget(block, area);
+
+    let inner = Rect {
+        x: area.x + 2,
+        y: area.y + 1,
+        width: area.width.saturating_sub(4),
+        height: area.height.saturating_sub(2),
+    };
+
+    // Two lines per group
+    let visible = (inner.height as usize / 2).max(1);
+    let skip = (ov.selected + 1).saturating_sub(visible);
+    let mut lines: Vec<Line> = Vec::new();
+    for (i, g) in ov.groups.iter().enumerate().skip(skip).take(visible) {
+        let selected = i == ov.selected;
+        let summary = g.commits.first().map(|c| c.first_line.clone()).unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::styled(if selected { "❯ " } else { "  " }, Style::default().fg(accent)),
+            Span::styled(
+                summary,
+                if selected {
+                    Style::default().fg(Color::White).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::Rgb(180, 188, 215))
+                },
+            ),
+        ]));
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  {}  ·  {} repo(s)  ·  {}",
+                crate::prp_hub::group::format_commit_time(g.latest()),
+                g.repo_count(),
+                g.group_id.trim_start_matches("gitlink-").chars().take(8).collect::<String>()
+            ),
+            Style::default().fg(Color::Rgb(90, 100, 130)),
+        )));
+    }
+
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+fn draw_group_details(f: &mut Frame, ov: &crate::tui::app::GroupsOverlay, area: Rect, accent: Color) {
+    let group = match ov.groups.get(ov.selected) {
+        Some(g) => g,
+        None => return,
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Rgb(45, 52, 68)))
+        .title(Span::styled(format!("  {}  ", group.group_id), Style::default().fg(accent)));
+    f.render_widget(block, area);
+
+    let inner = Rect {
+        x: area.x + 2,
+        y: area.y + 1,
+        width: area.width.saturating_sub(4),
+        height: area.height.saturating_sub(2),
+    };
+
+    let label = Style::default().fg(Color::Rgb(120, 130, 160));
+    let value = Style::default().fg(Color::Rgb(200, 205, 225));
+    let mut lines: Vec<Line> = Vec::new();
+    for c in &group.commits {
+        lines.push(Line::from(vec![
+            Span::styled(format!("▣  {}  ", c.repo_name), Style::default().fg(Color::Rgb(160, 120, 240)).add_modifier(Modifier::BOLD)),
+            Span::styled(c.short_sha.clone(), Style::default().fg(Color::Rgb(230, 180, 60))),
+        ]));
+        lines.push(Line::from(vec![Span::styled("   Author  ", label), Span::styled(c.author.clone(), value)]));
+        lines.push(Line::from(vec![
+            Span::styled("   Date    ", label),
+            Span::styled(crate::prp_hub::group::format_commit_time(c.time), value),
+        ]));
+        lines.push(Line::from(""));
+        for l in c.message.trim_end().lines() {
+            lines.push(Line::from(Span::styled(format!("     {}", l), value)));
+        }
+        lines.push(Line::from(""));
+    }
+
+    let scroll = ov.scroll.min(lines.len().saturating_sub(1));
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }).scroll((scroll as u16, 0)), inner);
+}
+
+fn draw_group_diff(f: &mut Frame, diff: &[crate::prp_hub::group::PatchLine], scroll: usize, area: Rect, accent: Color) {
+    use crate::prp_hub::group::PatchLine;
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Rgb(45, 52, 68)))
+        .title(Span::styled("  Combined diff  ", Style::default().fg(accent)));
+    f.render_widget(block, area);
+
+    let inner = Rect {
+        x: area.x + 1,
+        y: area.y + 1,
+        width: area.width.saturating_sub(2),
+        height: area.height.saturating_sub(2),
+    };
+
+    let visible = inner.height as usize;
+    let scroll = scroll.min(diff.len().saturating_sub(visible));
+    let lines: Vec<Line> = diff.iter().skip(scroll).take(visible).map(|l| match l {
+        PatchLine::Commit(t) => Line::from(Span::styled(
+            format!("▣  {}", t),
+            Style::default().fg(Color::Rgb(160, 120, 240)).add_modifier(Modifier::BOLD),
+        )),
+        PatchLine::File(t) => Line::from(Span::styled(
+            format!("  ● {}", t),
+            Style::default().fg(Color::Rgb(230, 180, 60)).add_modifier(Modifier::BOLD),
+        )),
+        PatchLine::Hunk(t) => Line::from(Span::styled(format!("  {}", t), Style::default().fg(Color::Rgb(100, 155, 245)))),
+        PatchLine::Added(t) => code_line("+", t, Color::Rgb(80, 210, 130)),
+        PatchLine::Removed(t) => code_line("-", t, Color::Rgb(220, 80, 80)),
+        PatchLine::Context(t) => code_line(" ", t, Color::Rgb(140, 148, 172)),
+        PatchLine::Note(t) => Line::from(Span::styled(format!("    {}", t), Style::default().fg(Color::Rgb(90, 100, 130)))),
+    }).collect();
+
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+// A diff body line: the +/- marker in the line colour, then the code with
+// strings, numbers, comments and common keywords picked out.
+fn code_line(marker: &str, text: &str, base: Color) -> Line<'static> {
+    const KEYWORDS: &[&str] = &[
+        "fn", "let", "mut", "pub", "use", "mod", "impl", "struct", "enum", "trait", "match", "if", "else",
+        "for", "while", "loop", "return", "break", "continue", "const", "static", "async", "await", "self",
+        "Self", "true", "false", "None", "Some", "Ok", "Err", "def", "class", "import", "from", "function",
+        "var", "export", "interface", "type", "func", "package", "null", "nil", "new", "in", "as",
+    ];
+    let keyword = Style::default().fg(Color::Rgb(190, 140, 250));
+    let string = Style::default().fg(Color::Rgb(230, 200, 110));
+    let number = Style::default().fg(Color::Rgb(240, 150, 90));
+    let comment = Style::default().fg(Color::Rgb(90, 100, 130)).add_modifier(Modifier::ITALIC);
+    let plain = Style::default().fg(base);
+
+    let mut spans = vec![Span::styled(format!("  {} ", marker), plain)];
+    let chars: Vec<char> = text.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let rest: String = chars[i..].iter().collect();
+        if rest.starts_with("//") || (c == '#' && chars.get(i + 1) != Some(&'[') && chars[..i].iter().all(|c| c.is_whitespace())) {
+            spans.push(Span::styled(rest, comment));
+            break;
+        }
+        let start = i;
+        let style = if c == '"' || c == '\'' || c == '`' {
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                i += if chars[i] == '\\' { 2 } else { 1 };
+            }
+            i = (i + 1).min(chars.len());
+            string
+        } else if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.' || chars[i] == '_') {
+                i += 1;
+            }
+            number
+        } else if c.is_alphabetic() || c == '_' {
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            if KEYWORDS.contains(&word.as_str()) { keyword } else { plain }
+        } else {
+            i += 1;
+            plain
+        };
+        spans.push(Span::styled(chars[start..i].iter().collect::<String>(), style));
+    }
+    Line::from(spans)
+}
@@ -7,6 +7,10 @@ use ratatui::{
     widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
 };
 
+use crate::planner::commits::commit_label;
+use crate::planner::schedule::{format_due, format_effort};
+use crate::planner::task::{Priority, Task};
+use crate::planner::tree;
 use crate::tui::app::{InputField, PlannerFocus, PlannerMode, PlannerOverlay, planner_scratch_peek};
 
 pub fn draw(f: &mut Frame, ov: &PlannerOverlay) {
@@ -25,14 +29,20 @@ pub fn draw(f: &mut Frame, ov: &PlannerOverlay) {
         .border_style(Style::default().fg(Color::Rgb(70, 80, 95))) // Steel Gray
         .style(Style::default().bg(Color::Rgb(15, 17, 20)))     // Deep Slate
         .title(Span::styled(
-            format!("  📋 GitLink Planner  ·  {}/{} done  ", done, total),
+            format!("  📋 GitLink Planner  ·  {}/{} done  ·  sorted by {}  ", done, total, ov.sort.label()),
             Style::default()
                 .fg(Color::Rgb(100, 149, 237)) // Cornflower Blue
                 .add_modifier(Modifier::BOLD),
         ))
         .title_alignment(Alignment::Left)
         .title_bottom(Span::styled(
-            "  Tab: switch panel  ·  a: add  ·  e: edit  ·  d: delete  ·  Space: toggle  ·  u/r: undo/redo  ·  q: close  ",
+            if ov.mode == PlannerMode::Archive {
+                "  j/k move · / search · Enter restore · Esc back  "
+            } else if ov.mode == PlannerMode::Board {
+                "  h/l column · j/k card · H/L or Shift+←/→ move card · u/r undo/redo · Enter/Esc list  "
+            } else {
+                "  a/A add · e edit · d del · x/v archive · w board · Space done · >/< nest · b block · p prio · s sort · u/r undo · q quit  "
+            },
             Style::default().fg(Color::Rgb(140, 150, 170)), // Muted Silver
         ));
 
@@ -51,8 +61,15 @@ pub fn draw(f: &mut Frame, ov: &PlannerOverlay) {
         .constraints([Constraint::Percentage(38), Constraint::Percentage(62)])
         .split(inner);
 
-    draw_list_panel(f, ov, panels[0]);
-    draw_detail_panel(f, ov, panels[1]);
+    if ov.mode == PlannerMode::Board {
+        draw_board(f, ov, inner);
+    } else if ov.mode == PlannerMode::Archive {
+        draw_archive_panel(f, ov, panels[0]);
+        draw_detail_panel(f, ov, &ov.archive, ov.selected_archived(), panels[1]);
+    } else {
+        draw_list_panel(f, ov, panels[0]);
+        draw_detail_panel(f, ov, &ov.tasks, ov.selected_task(), panels[1]);
+    }
 
     // ── Input modal ───────────────────────────────────────────────────────────
     match ov.mode {
@@ -114,6 +131,15 @@ fn draw_list_panel(f: &mut Frame, ov: &PlannerOverlay, area: Rect) {
         return;
     }
 
+    let today = Local::now().date_naive();
+    let depths = tree::depths(&ov.tasks);
+    let picking = ov.mode == PlannerMode::PickingBlocker;
+    let source = ov.link_source.as_deref().and_then(|id| ov.tasks.iter().find(|t| t.id == id));
+    let list_area = if ov.notice.is_some() || ov.status.is_some() || picking {
+        Rect { height: list_area.height.saturating_sub(2), ..list_area }
+    } else {
+        list_area
+    };
     let visible_h = list_area.height as usize;
     let start = ov.scroll;
     let end = (start + visible_h).min(ov.tasks.len());
@@ -125,11 +151,22 @@ fn draw_list_panel(f: &mut Frame, ov: &PlannerOverlay, area: Rect) {
             let real_idx = start + i;
             let is_sel = real_idx == ov.selected;
 
-            let pointer = if is_sel && ov.focus == PlannerFocus::List {
+            let pointer = if is_sel && picking {
+                Span::styled("▶ ", Style::default().fg(Color::Rgb(230, 180, 80)))
+            } else if is_sel && ov.focus == PlannerFocus::List {
                 Span::styled("▶ ", Style::default().fg(Color::Rgb(100, 149, 237)))
             } else {
                 Span::raw("  ")
             };
+            let indent = Span::raw("  ".repeat(depths[real_idx]));
+
+            // While picking blockers, mark the task being edited and its current blockers
+            let pick_mark = match source {
+                Some(src) if src.id == task.id => Span::styled("◆ ", Style::default().fg(Color::Rgb(230, 180, 80))),
+                Some(src) if src.blocked_by.contains(&task.id) => Span::styled("⛓ ", Style::default().fg(Color::Rgb(230, 180, 80))),
+                Some(_) => Span::raw("  "),
+                None => Span::raw(""),
+            };
 
             let checkbox = if task.completed {
                 Span::styled("✔ ", Style::default().fg(Color::Rgb(46, 139, 87))) // Sea Green
@@ -141,6 +178,10 @@ fn draw_list_panel(f: &mut Frame, ov: &PlannerOverlay, area: Rect) {
                 Style::default()
                     .fg(Color::Rgb(75, 85, 100))
                     .add_modifier(Modifier::CROSSED_OUT)
+            } else if task.is_overdue(today) {
+                Style::default()
+                    .fg(Color::Rgb(240, 110, 110)) // Soft Red
+                    .add_modifier(Modifier::BOLD)
             } else if is_sel {
                 Style::default()
                     .fg(Color::White)
@@ -155,7 +196,7 @@ fn draw_list_panel(f: &mut Frame, ov: &PlannerOverlay, area: Rect) {
                 Style::default()
             };
 
-            let max_title = (list_area.width as usize).saturating_sub(6);
+            let max_title = (list_area.width as usize).saturating_sub(6 + 2 * depths[real_idx]);
             let title_text = if task.title.len() > max_title {
                 format!("{}…", &task.title[..max_title.saturating_sub(1)])
             } else {
@@ -178,10 +219,45 @@ fn draw_list_panel(f: &mut Frame, ov: &PlannerOverlay, area: Rect) {
                 Span::raw("")
             };
 
+            let priority_span = priority_span(task.priority);
+
+            let due_span = match task.due {
+                Some(due) if !task.completed => {
+                    let color = if task.is_overdue(today) {
+                        Color::Rgb(240, 110, 110)
+                    } else if task.is_due_today(today) {
+                        Color::Rgb(230, 180, 80) // Amber
+                    } else {
+                        Color::Rgb(110, 115, 130)
+                    };
+                    Span::styled(format!("  ⏰ {}", format_due(due, today)), Style::default().fg(color))
+                }
+                _ => Span::raw(""),
+            };
+
+            let progress_span = match tree::progress(&ov.tasks, &task.id) {
+                Some((done, total)) => Span::styled(
+                    format!("  [{}/{}]", done, total),
+                    Style::default().fg(if done == total { Color::Rgb(46, 139, 87) } else { Color::Rgb(110, 115, 130) }),
+                ),
+                None => Span::raw(""),
+            };
+            let blocked_span = if !task.completed && !tree::open_blockers(&ov.tasks, task).is_empty() {
+                Span::styled("⛔ ", Style::default().fg(Color::Rgb(240, 110, 110)))
+            } else {
+                Span::raw("")
+            };
+
             Line::from(vec![
                 pointer,
+                indent,
+                pick_mark,
                 checkbox,
+                blocked_span,
+                priority_span,
                 Span::styled(title_text, title_style),
+                progress_span,
+                due_span,
                 tags_span,
             ]).style(bg)
         })
@@ -189,6 +265,19 @@ fn draw_list_panel(f: &mut Frame, ov: &PlannerOverlay, area: Rect) {
 
     f.render_widget(Paragraph::new(lines), list_area);
 
+    let notice = match (&ov.notice, source) {
+        (Some(n), _) => Some((format!("✖ {}", n), Color::Rgb(240, 110, 110))),
+        (None, Some(src)) if picking => Some((
+            format!("Blockers of \"{}\" — Enter toggles · Esc done", src.title),
+            Color::Rgb(230, 180, 80),
+        )),
+        (None, _) => ov.status.as_ref().map(|s| (s.clone(), Color::Rgb(120, 200, 140))),
+    };
+    if let Some((text, color)) = notice {
+        let notice_area = Rect { y: list_area.y + list_area.height + 1, height: 1, ..list_area };
+        f.render_widget(Paragraph::new(Span::styled(text, Style::default().fg(color))), notice_area);
+    }
+
     if ov.tasks.len() > visible_h {
         let hint = Paragraph::new(Line::from(Span::styled(
             format!(" {}/{} ", ov.selected + 1, ov.tasks.len()),
@@ -206,7 +295,186 @@ fn draw_list_panel(f: &mut Frame, ov: &PlannerOverlay, area: Rect) {
     }
 }
 
-fn draw_detail_panel(f: &mut Frame, ov: &PlannerOverlay, area: Rect) {
+fn priority_span(priority: Priority) -> Span<'static> {
+    match priority {
+        Priority::Urgent => Span::styled("‼ ", Style::default().fg(Color::Rgb(240, 110, 110))),
+        Priority::High   => Span::styled("↑ ", Style::default().fg(Color::Rgb(230, 180, 80))),
+        Priority::Low    => Span::styled("↓ ", Style::default().fg(Color::Rgb(90, 95, 110))),
+        Priority::Normal => Span::raw(""),
+    }
+}
+
+fn draw_board(f: &mut Frame, ov: &PlannerOverlay, area: Rect) {
+    let columns = &ov.settings.columns;
+    let board = ov.board();
+    let card = ov.board_card();
+    let today = Local::now().date_naive();
+
+    let area = if ov.notice.is_some() {
+        let notice_area = Rect { y: area.y + area.height.saturating_sub(1), height: 1, x: area.x + 2, width: area.width.saturating_sub(4) };
+        let notice = format!("✖ {}", ov.notice.as_deref().unwrap_or_default());
+        f.render_widget(Paragraph::new(Span::styled(notice, Style::default().fg(Color::Rgb(240, 110, 110)))), notice_area);
+        Rect { height: area.height.saturating_sub(1), ..area }
+    } else {
+        area
+    };
+
+    let lanes = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Ratio(1, columns.len().max(1) as u32); columns.len()])
+        .split(area);
+
+    for (col, (name, lane)) in columns.iter().zip(lanes.iter()).enumerate() {
+        let is_focused = col == ov.board_column;
+        let border_color = if is_focused { Color::Rgb(100, 149, 237) } else { Color::Rgb(45, 50, 60) };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(border_color))
+            .style(Style::default().bg(Color::Rgb(15, 17, 20)))
+            .title(Span::styled(
+                format!("  {} · {}  ", name, board[col].len()),
+                Style::default().fg(border_color).add_modifier(Modifier::BOLD),
+            ));
+        f.render_widget(block, *lane);
+
+        let inner = Rect {
+            x: lane.x + 1,
+            y: lane.y + 1,
+            width: lane.width.saturating_sub(2),
+            height: lane.height.saturating_sub(2),
+        };
+
+        // Keep the highlighted card in view
+        let visible_h = inner.height as usize;
+        let row = card.and_then(|c| board[col].iter().position(|i| *i == c)).filter(|_| is_focused).unwrap_or(0);
+        let start = row.saturating_sub(visible_h.saturating_sub(1));
+
+        let lines: Vec<Line> = board[col]
+            .iter()
+            .skip(start)
+            .take(visible_h)
+            .map(|i| {
+                let task = &ov.tasks[*i];
+                let selected = is_focused && card == Some(*i);
+                let title_style = if selected {
+                    Style::default().fg(Color::White).add_modifier(Modifier::BOLD)
+                } else if task.completed {
+                    Style::default().fg(Color::Rgb(80, 85, 100)).add_modifier(Modifier::CROSSED_OUT)
+                } else {
+                    Style::default().fg(Color::Rgb(170, 175, 190))
+                };
+                let blocked = !task.completed && !tree::open_blockers(&ov.tasks, task).is_empty();
+                let mut spans = vec![
+                    Span::styled(if selected { "▶ " } else { "  " }, Style::default().fg(Color::Rgb(100, 149, 237))),
+                    priority_span(task.priority),
+                ];
+                if blocked {
+                    spans.push(Span::styled("⛔ ", Style::default().fg(Color::Rgb(240, 110, 110))));
+                }
+                spans.push(Span::styled(task.title.clone(), title_style));
+                if let Some(due) = task.due.filter(|_| !task.completed) {
+                    let color = if task.is_overdue(today) { Color::Rgb(240, 110, 110) } else { Color::Rgb(110, 115, 130) };
+                    spans.push(Span::styled(format!("  ⏰ {}", format_due(due, today)), Style::default().fg(color)));
+                }
+                let bg = if selected { Style::default().bg(Color::Rgb(35, 40, 50)) } else { Style::default() };
+                Line::from(spans).style(bg)
+            })
+            .collect();
+
+        f.render_widget(Paragraph::new(lines), inner);
+    }
+}
+
+fn draw_archive_panel(f: &mut Frame, ov: &PlannerOverlay, area: Rect) {
+    let border_color = Color::Rgb(100, 149, 237);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(border_color))
+        .style(Style::default().bg(Color::Rgb(15, 17, 20)))
+        .title(Span::styled(
+            format!("  Archive · {}  ", ov.archive.len()),
+            Style::default().fg(border_color).add_modifier(Modifier::BOLD),
+        ));
+
+    f.render_widget(block, area);
+
+    let inner = Rect {
+        x: area.x + 2,
+        y: area.y + 1,
+        width: area.width.saturating_sub(4),
+        height: area.height.saturating_sub(2),
+    };
+
+    // ── Search box ────────────────────────────────────────────────────────────
+    let query_style = if ov.archive_searching {
+        Style::default().fg(Color::White)
+    } else {
+        Style::default().fg(Color::Rgb(140, 150, 170))
+    };
+    let cursor = if ov.archive_searching { "█" } else { "" };
+    let search = if ov.archive_query.is_empty() && !ov.archive_searching {
+        Line::from(Span::styled("/ to search", Style::default().fg(Color::Rgb(80, 85, 100))))
+    } else {
+        Line::from(vec![
+            Span::styled("🔍 ", Style::default().fg(border_color)),
+            Span::styled(format!("{}{}", ov.archive_query, cursor), query_style),
+        ])
+    };
+    f.render_widget(Paragraph::new(search), Rect { height: 1, ..inner });
+
+    let list_area = Rect {
+        y: inner.y + 2,
+        height: inner.height.saturating_sub(2),
+        ..inner
+    };
+
+    let matches = ov.archive_matches();
+    if matches.is_empty() {
+        let text = if ov.archive.is_empty() { "Nothing archived yet." } else { "No archived task matches." };
+        let empty = Paragraph::new(Span::styled(text, Style::default().fg(Color::Rgb(80, 85, 100))))
+            .alignment(Alignment::Center);
+        f.render_widget(empty, list_area);
+        return;
+    }
+
+    let visible_h = list_area.height as usize;
+    let start = ov.archive_selected.saturating_sub(visible_h.saturating_sub(1));
+    let lines: Vec<Line> = matches
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(visible_h)
+        .map(|(i, idx)| {
+            let task = &ov.archive[*idx];
+            let selected = i == ov.archive_selected;
+            let bg = if selected {
+                Style::default().bg(Color::Rgb(35, 40, 50))
+            } else {
+                Style::default()
+            };
+            let title_style = if selected {
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::Rgb(140, 150, 170))
+            };
+            let when = task
+                .completed_at
+                .map(|at| at.with_timezone(&Local).format("  %b %-d").to_string())
+                .unwrap_or_default();
+            Line::from(vec![
+                Span::styled(if selected { "▶ " } else { "  " }, Style::default().fg(border_color)),
+                Span::styled(task.title.clone(), title_style),
+                Span::styled(when, Style::default().fg(Color::Rgb(80, 85, 100))),
+            ]).style(bg)
+        })
+        .collect();
+
+    f.render_widget(Paragraph::new(lines), list_area);
+}
+
+fn draw_detail_panel(f: &mut Frame, ov: &PlannerOverlay, tasks: &[Task], task: Option<&Task>, area: Rect) {
     let is_focused = ov.focus == PlannerFocus::Detail;
     let border_color = if is_focused {
         Color::Rgb(100, 149, 237)
@@ -233,7 +501,7 @@ fn draw_detail_panel(f: &mut Frame, ov: &PlannerOverlay, area: Rect) {
         height: area.height.saturating_sub(3),
     };
 
-    let task = match ov.selected_task() {
+    let task = match task {
         Some(t) => t,
         None => {
             let empty = Paragraph::new(Span::styled(
@@ -301,6 +569,58 @@ fn draw_detail_panel(f: &mut Frame, ov: &PlannerOverlay, area: Rect) {
         ]));
     }
 
+    let today = Local::now().date_naive();
+    let priority_color = match task.priority {
+        Priority::Urgent => Color::Rgb(240, 110, 110),
+        Priority::High   => Color::Rgb(230, 180, 80),
+        _                => Color::Rgb(170, 175, 190),
+    };
+    lines.push(Line::from(vec![
+        Span::styled("Priority ", Style::default().fg(Color::Rgb(110, 115, 130))),
+        Span::styled(task.priority.label(), Style::default().fg(priority_color)),
+    ]));
+    let columns = &ov.settings.columns;
+    if let Some(column) = columns.get(crate::planner::board::column_index(task, columns)) {
+        lines.push(Line::from(vec![
+            Span::styled("Column   ", Style::default().fg(Color::Rgb(110, 115, 130))),
+            Span::styled(column.clone(), Style::default().fg(Color::Rgb(170, 175, 190))),
+        ]));
+    }
+    if let Some(due) = task.due {
+        let color = if task.is_overdue(today) {
+            Color::Rgb(240, 110, 110)
+        } else if task.is_due_today(today) {
+            Color::Rgb(230, 180, 80)
+        } else {
+            Color::Rgb(170, 175, 190)
+        };
+        lines.push(Line::from(vec![
+            Span::styled("Due      ", Style::default().fg(Color::Rgb(110, 115, 130))),
+            Span::styled(
+                format!("{}  ({})", due.format("%a %Y-%m-%d"), format_due(due, today)),
+                Style::default().fg(color),
+            ),
+        ]));
+    }
+    if let Some(effort) = task.effort_minutes {
+        lines.push(Line::from(vec![
+            Span::styled("Effort   ", Style::default().fg(Color::Rgb(110, 115, 130))),
+            Span::styled(format_effort(effort), Style::default().fg(Color::Rgb(170, 175, 190))),
+        ]));
+    }
+
+    if let Some(link) = &task.issue {
+        let synced = link
+            .synced_at
+            .map(|s| format!("  synced {}", s.with_timezone(&Local).format("%Y-%m-%d  %H:%M")))
+            .unwrap_or_else(|| "  not synced yet".to_string());
+        lines.push(Line::from(vec![
+            Span::styled("Issue    ", Style::default().fg(Color::Rgb(110, 115, 130))),
+            Span::styled(link.to_string(), Style::default().fg(Color::Rgb(100, 149, 237))),
+            Span::styled(synced, Style::default().fg(Color::Rgb(80, 85, 100))),
+        ]));
+    }
+
     lines.push(Line::from(""));
 
     if !task.tags.is_empty() {
@@ -325,6 +645,70 @@ fn draw_detail_panel(f: &mut Frame, ov: &PlannerOverlay, area: Rect) {
         lines.push(Line::from(""));
     }
 
+    let label = Style::default().fg(Color::Rgb(110, 115, 130));
+    if let Some(parent) = task.parent.as_deref().and_then(|p| tasks.iter().find(|t| t.id == p)) {
+        lines.push(Line::from(vec![
+            Span::styled("Subtask of  ", label),
+            Span::styled(parent.title.clone(), Style::default().fg(Color::Rgb(170, 175, 190))),
+        ]));
+    }
+    if let Some((done, total)) = tree::progress(tasks, &task.id) {
+        let width = 20;
+        let filled = done * width / total;
+        lines.push(Line::from(vec![
+            Span::styled("Subtasks    ", label),
+            Span::styled("█".repeat(filled), Style::default().fg(Color::Rgb(46, 139, 87))),
+            Span::styled("░".repeat(width - filled), Style::default().fg(Color::Rgb(50, 55, 65))),
+            Span::styled(format!("  {}/{} done", done, total), Style::default().fg(Color::Rgb(170, 175, 190))),
+        ]));
+    }
+    let blockers: Vec<&Task> = tasks.iter().filter(|t| task.blocked_by.contains(&t.id)).collect();
+    if !blockers.is_empty() {
+        lines.push(Line::from(Span::styled("Blocked by", label)));
+        for b in blockers {
+            let (mark, color) = if b.completed { ("✔", Color::Rgb(46, 139, 87)) } else { ("⛔", Color::Rgb(240, 110, 110)) };
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {} ", mark), Style::default().fg(color)),
+                Span::styled(b.title.clone(), Style::default().fg(Color::Rgb(190, 195, 210))),
+            ]));
+        }
+    }
+    let blocks = tree::blocked_by_this(tasks, &task.id);
+    if !blocks.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled("Blocks      ", label),
+            Span::styled(
+                blocks.iter().map(|t| t.title.as_str()).collect::<Vec<_>>().join(", "),
+                Style::default().fg(Color::Rgb(170, 175, 190)),
+            ),
+        ]));
+    }
+    if task.parent.is_some() || !task.blocked_by.is_empty() || !blocks.is_empty() || tree::progress(tasks, &task.id).is_some() {
+        lines.push(Line::from(""));
+    }
+
+    if !task.commits.is_empty() {
+        lines.push(Line::from(Span::styled(format!("Commits ({})", task.commits.len()), label)));
+        for c in task.commits.iter().take(5) {
+            let mut spans = vec![
+                Span::styled(format!("  {} ", if c.closes { "✔" } else { "•" }), Style::default().fg(Color::Rgb(46, 139, 87))),
+                Span::styled(commit_label(c), Style::default().fg(Color::Rgb(230, 180, 80))),
+                Span::styled(format!("  {}", c.summary), Style::default().fg(Color::Rgb(190, 195, 210))),
+            ];
+            if let Some(branch) = &c.branch {
+                spans.push(Span::styled(format!("  ⎇ {}", branch), Style::default().fg(Color::Rgb(80, 85, 100))));
+            }
+            lines.push(Line::from(spans));
+        }
+        if task.commits.len() > 5 {
+            lines.push(Line::from(Span::styled(
+                format!("  … and {} more", task.commits.len() - 5),
+                Style::default().fg(Color::Rgb(80, 85, 100)),
+            )));
+        }
+        lines.push(Line::from(""));
+    }
+
     if let Some(desc) = &task.description {
         lines.push(Line::from(Span::styled(
             "Description",
@@ -341,7 +725,7 @@ fn draw_detail_panel(f: &mut Frame, ov: &PlannerOverlay, area: Rect) {
 
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
-        format!("ID  {}", &task.id[..8]),
+        format!("ID  {}  ·  mention {} in commits or branches", &task.id[..8], task.short_ref(&ov.tasks)),
         Style::default().fg(Color::Rgb(60, 65, 80)),
     )));
 
@@ -349,7 +733,7 @@ fn draw_detail_panel(f: &mut Frame, ov: &PlannerOverlay, area: Rect) {
 }
 
 fn draw_input_modal(f: &mut Frame, ov: &PlannerOverlay, parent: Rect, title: &str) {
-    let modal = centered_rect_abs(70, 13, parent);
+    let modal = centered_rect_abs(70, 17, parent);
     f.render_widget(Clear, modal);
 
     let block = Block::default()
@@ -366,12 +750,9 @@ fn draw_input_modal(f: &mut Frame, ov: &PlannerOverlay, parent: Rect, title: &st
 
     f.render_widget(block, modal);
 
-    let (scratch_title, scratch_tags, scratch_desc) = planner_scratch_peek();
-
-    let (title_val, tags_val, desc_val) = match ov.input_field {
-        InputField::Title       => (ov.input_buf.as_str(), scratch_tags.as_str(),  scratch_desc.as_str()),
-        InputField::Tags        => (scratch_title.as_str(), ov.input_buf.as_str(), scratch_desc.as_str()),
-        InputField::Description => (scratch_title.as_str(), scratch_tags.as_str(),  ov.input_buf.as_str()),
+    let draft = planner_scratch_peek();
+    let value_of = |field: InputField| -> String {
+        if ov.input_field == field { ov.input_buf.clone() } else { draft.field(&field).to_string() }
     };
 
     let make_row = |buf: &str, cursor: usize, label: &str, active: bool| -> Line {
@@ -408,18 +789,22 @@ fn draw_input_modal(f: &mut Frame, ov: &PlannerOverlay, parent: Rect, title: &st
         }
     };
 
-    let cursor = ov.input_cursor;
-    let fields = Paragraph::new(vec![
-        Line::from(""),
-        make_row(title_val, if ov.input_field == InputField::Title { cursor } else { title_val.len() },
-                 "Title* : ", ov.input_field == InputField::Title),
-        Line::from(""),
-        make_row(tags_val,  if ov.input_field == InputField::Tags  { cursor } else { tags_val.len() },
-                 "Tags    : ", ov.input_field == InputField::Tags),
-        Line::from(""),
-        make_row(desc_val,  if ov.input_field == InputField::Description { cursor } else { desc_val.len() },
-                 "Desc    : ", ov.input_field == InputField::Description),
-    ]);
+    let rows = [
+        (InputField::Title,       "Title* : "),
+        (InputField::Tags,        "Tags    : "),
+        (InputField::Due,         "Due     : "),
+        (InputField::Effort,      "Effort  : "),
+        (InputField::Description, "Desc    : "),
+    ];
+    let values: Vec<String> = rows.iter().map(|(field, _)| value_of(field.clone())).collect();
+    let mut field_lines = Vec::new();
+    for ((field, label), value) in rows.iter().zip(&values) {
+        let active = ov.input_field == *field;
+        let cursor = if active { ov.input_cursor } else { value.len() };
+        field_lines.push(Line::from(""));
+        field_lines.push(make_row(value, cursor, label, active));
+    }
+    let fields = Paragraph::new(field_lines);
 
     let fields_area = Rect {
         x: modal.x + 1,
@@ -435,6 +820,25 @@ fn draw_input_modal(f: &mut Frame, ov: &PlannerOverlay, parent: Rect, title: &st
         width: modal.width.saturating_sub(4),
         height: 1,
     };
+    if let Some(error) = &ov.input_error {
+        f.render_widget(
+            Paragraph::new(Span::styled(format!("✖ {}", error), Style::default().fg(Color::Rgb(240, 110, 110)))),
+            hint_area,
+        );
+        return;
+    }
+    let example = match ov.input_field {
+        InputField::Due    => Some("e.g.  fri · next mon · +3d · tomorrow · 2026-10-24 · empty for none"),
+        InputField::Effort => Some("e.g.  30m · 2h · 1.5h · 1d (8h) · empty for none"),
+        _ => None,
+    };
+    if let Some(example) = example {
+        let example_area = Rect { y: hint_area.y.saturating_sub(1), ..hint_area };
+        f.render_widget(
+            Paragraph::new(Span::styled(example, Style::default().fg(Color::Rgb(130, 135, 150)))),
+            example_area,
+        );
+    }
     f.render_widget(
         Paragraph::new(Line::from(vec![
             Span::styled("Enter/Tab ", Style::default().fg(Color::Rgb(100, 149, 237))),
@@ -487,7 +891,13 @@ fn draw_confirm_modal(f: &mut Frame, ov: &PlannerOverlay, parent: Rect) {
             Span::styled(trimmed, Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
             Span::styled(" ?", Style::default().fg(Color::Rgb(180, 185, 200))),
         ]),
-        Line::from(""),
+        match ov.selected_task().map(|t| ov.tasks.iter().filter(|c| c.parent.as_deref() == Some(t.id.as_str())).count()) {
+            Some(n) if n > 0 => Line::from(Span::styled(
+                format!("Its {} subtask(s) move to the top level.", n),
+                Style::default().fg(Color::Rgb(120, 125, 140)),
+            )),
+            _ => Line::from(""),
+        },
         Line::from(vec![
             Span::styled("y / Enter ", Style::default().fg(Color::Rgb(240, 110, 110)).add_modifier(Modifier::BOLD)),
             Span::styled("confirm  ", Style::default().fg(Color::Rgb(120, 90, 90))),
@@ -54,6 +54,8 @@ fn run_loop(
     let mut pending_cmd_name: Option<String> = None;
     // Multi-sync overlay: separate channel for repo list fetch and sync results
     let mut pending_multi_sync: Option<mpsc::Receiver<MultiSyncMsg>> = None;
+    // `/prp groups` walks every repo's history, so it loads in the background too
+    let mut pending_groups: Option<mpsc::Receiver<GroupsResult>> = None;
 
     loop {
         if let Some(ref rx) = pending_result {
@@ -67,7 +69,7 @@ fn run_loop(
                     cmd.as_str(),
                     "show-activity" | "commits" | "pull-requests" | "repo-sync"
                     | "push-check" | "push-verify" | "branches"
-                    | "issues" | "user-info"
+                    | "issues" | "user-info" | "prp"
                 );
 
                 if is_overlay_cmd && output.kind != OutputKind::Error {
@@ -111,6 +113,16 @@ fn run_loop(
             }
         }
 
+        // Poll the commit-group scan
+        if let Some(ref rx) = pending_groups {
+            if let Ok(loaded) = rx.try_recv() {
+                pending_groups = None;
+                exec_start = None;
+                app.open_groups_overlay(loaded);
+                app.needs_full_redraw = true;
+            }
+        }
+
         // If multi-sync is in Running state, kick off the sync task
         let should_run_sync = if let Some(crate::tui::app::Overlay::MultiSync(ref ov)) = app.overlay {
             ov.step == crate::tui::app::MultiSyncStep::Running && pending_multi_sync.is_none()
@@ -159,6 +171,23 @@ fn run_loop(
                     match root {
                         // ── Local / overlay commands ───────────────────────
 
+                        "plan" if matches!(sub, "import" | "issue" | "link" | "unlink" | "sync" | "commits" | "autoclose" | "columns") => {
+                            // Talks to GitHub or walks every repo's history — run off-thread
+                            let (tx, rx) = mpsc::channel::<OutputBlock>();
+                            let raw = cmd.clone();
+                            std::thread::spawn(move || {
+                                let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| router::execute(&raw)));
+                                let output = result.unwrap_or_else(|_| crate::tui::app::OutputBlock {
+                                    kind: crate::tui::app::OutputKind::Error,
+                                    content: "Command failed unexpectedly.".to_string(),
+                                });
+                                let _ = tx.send(output);
+                            });
+                            pending_result = Some(rx);
+                            pending_cmd_name = Some("plan".to_string());
+                            exec_start = Some(std::time::Instant::now());
+                        }
+
                         "plan" => {
                             app.outputs.push(crate::tui::app::OutputBlock { kind: crate::tui::app::OutputKind::Info, content: "Opening task planner…".to_string() });
                             app.open_planner_overlay();
@@ -170,7 +199,47 @@ fn run_loop(
                         }
 
                         "prp" => {
-                            if sub == "list" {
+                            if matches!(sub, "resume" | "undo" | "branch" | "switch" | "pr" | "pull" | "tag" | "set") {
+                                // These touch every repo and may hit the network — run off-thread
+                                let (tx, rx) = mpsc::channel::<OutputBlock>();
+                                let raw = cmd.clone();
+                                std::thread::spawn(move || {
+                                    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| router::execute(&raw)));
+                                    let output = result.unwrap_or_else(|_| crate::tui::app::OutputBlock {
+                                        kind: crate::tui::app::OutputKind::Error,
+                                        content: "Command failed unexpectedly.".to_string(),
+                                    });
+                                    let _ = tx.send(output);
+                                });
+                                pending_result = Some(rx);
+                                pending_cmd_name = Some("prp".to_string());
+                                exec_start = Some(std::time::Instant::now());
+                            } else if sub == "exec" {
+                                // Streams into its own pane; see ExecOverlay
+                                let rest = trimmed.trim_start_matches("prp").trim_start();
+                                match crate::prp_hub::exec::parse_exec_args(rest.strip_prefix("exec").unwrap_or(rest)) {
+                                    Ok(request) => app.open_exec_overlay(request),
+                                    Err(e) => app.outputs.push(crate::tui::app::OutputBlock { kind: crate::tui::app::OutputKind::Error, content: e }),
+                                }
+                            } else if sub == "--set" {
+                                let name = parts.get(2).copied().unwrap_or("");
+                                match crate::prp_hub::workspace::workspace_repositories(name) {
+                                    Ok(repos) => {
+                                        app.outputs.push(crate::tui::app::OutputBlock { kind: crate::tui::app::OutputKind::Info, content: format!("Opening poly-repo commit session for workspace '{}'…", name) });
+                                        app.open_prp_overlay(repos.iter().map(|r| r.path.to_string_lossy().to_string()).collect());
+                                    }
+                                    Err(e) => app.outputs.push(crate::tui::app::OutputBlock { kind: crate::tui::app::OutputKind::Error, content: e.to_string() }),
+                                }
+                            } else if sub == "groups" {
+                                let set = parts.iter().position(|p| *p == "--set").and_then(|i| parts.get(i + 1)).map(|s| s.to_string());
+                                let (tx, rx) = mpsc::channel::<GroupsResult>();
+                                std::thread::spawn(move || {
+                                    let result = std::panic::catch_unwind(|| load_groups(set.as_deref()));
+                                    let _ = tx.send(result.unwrap_or_else(|_| Err("Group scan failed unexpectedly.".to_string())));
+                                });
+                                pending_groups = Some(rx);
+                                exec_start = Some(std::time::Instant::now());
+                            } else if sub == "list" {
                                 // Show group list as info overlay
                                 let lines = build_prp_list_lines();
                                 app.open_info_overlay(
@@ -396,6 +465,14 @@ fn run_loop(
 
 // ─── Helpers ─────────────────────────────────────────────────────────────────
 
+type GroupsResult = Result<Vec<crate::prp_hub::group::CommitGroup>, String>;
+
+/// Resolve the repositories of `set` (or the working directory) and collect their commit groups
+fn load_groups(set: Option<&str>) -> GroupsResult {
+    let repos = crate::prp_hub::discovery::resolve_repositories(set).map_err(|e| e.to_string())?;
+    Ok(crate::prp_hub::group::collect_groups(&repos))
+}
+
 fn overlay_meta(cmd: &str) -> (String, Color) {
     match cmd {
         "show-activity"  => ("📊 GitHub Activity".to_string(),          Color::Rgb(80,  180, 120)),
@@ -408,6 +485,7 @@ fn overlay_meta(cmd: &str) -> (String, Color) {
         "branches"       => ("🌿 Branches".to_string(),                  Color::Rgb(120, 200, 100)),
         "issues"         => ("📝 Issues".to_string(),                    Color::Rgb(230, 160, 60)),
         "user-info"      => ("👤 User Info".to_string(),                 Color::Rgb(100, 149, 237)),
+        "prp"            => ("🔗 PRP Hub".to_string(),                   Color::Rgb(130, 90,  200)),
         _                => (format!("  {}", cmd),                        Color::Rgb(100, 149, 237)),
     }
 }
@@ -1384,7 +1462,7 @@ fn discover_repo_names() -> Vec<String> {
 }
 
 fn build_prp_list_lines() -> Vec<Line<'static>> {
-    vec![
+    let mut lines = vec![
         Line::from(""),
         Line::from(Span::styled(
             "  PRP Session Groups",
@@ -1396,11 +1474,76 @@ fn build_prp_list_lines() -> Vec<Line<'static>> {
             Style::default().fg(Color::Rgb(150, 155, 175)),
         )),
         Line::from(""),
-        Line::from(Span::styled(
-            "  Use /prp to start a new session.",
-            Style::default().fg(Color::Rgb(100, 149, 237)),
-        )),
-    ]
+    ];
+
+    let journals = crate::prp_hub::journal::list_journals();
+    if !journals.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  Recorded Sessions",
+            Style::default().fg(Color::Rgb(200, 200, 220)).add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(""));
+        for j in &journals {
+            let phase_color = if j.phase.is_resumable() { C_YELLOW } else { C_GREEN };
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {}  ", j.updated_at.format("%Y-%m-%d %H:%M")), Style::default().fg(C_TEAL)),
+                Span::styled(format!("{:18}", j.phase.label()), Style::default().fg(phase_color)),
+                Span::styled(j.group_id.clone(), Style::default().fg(C_PURPLE)),
+            ]));
+            lines.push(Line::from(Span::styled(
+                format!("      {} — {} repo(s)", j.message, j.repos.len()),
+                Style::default().fg(C_BODY),
+            )));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "  /prp resume [group-id] finishes a push · /prp undo <group-id> undoes a session",
+            Style::default().fg(Color::Rgb(150, 155, 175)),
+        )));
+        lines.push(Line::from(""));
+    }
+
+    let store = crate::prp_hub::workspace::load_workspaces();
+    lines.push(Line::from(Span::styled(
+        "  Workspaces",
+        Style::default().fg(Color::Rgb(200, 200, 220)).add_modifier(Modifier::BOLD),
+    )));
+    lines.push(Line::from(""));
+    if store.workspaces.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No workspaces yet.",
+            Style::default().fg(C_BODY),
+        )));
+    }
+    for ws in &store.workspaces {
+        let count = match crate::prp_hub::workspace::resolve_workspace(ws) {
+            Ok(repos) => format!("{} repo(s)", repos.len()),
+            Err(_) => "no repositories".to_string(),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:18}", ws.name), Style::default().fg(C_PURPLE).add_modifier(Modifier::BOLD)),
+            Span::styled(count, Style::default().fg(C_TEAL)),
+        ]));
+        for entry in &ws.entries {
+            lines.push(Line::from(Span::styled(format!("      {}", entry), Style::default().fg(C_BODY))));
+        }
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        "  /prp --set <name> starts a session · /prp set add <name> <path|glob>… · /prp set remove <name> [entry]",
+        Style::default().fg(Color::Rgb(150, 155, 175)),
+    )));
+    lines.push(Line::from(Span::styled(
+        format!("  Stored in {}", crate::prp_hub::workspace::workspaces_location()),
+        Style::default().fg(Color::Rgb(150, 155, 175)),
+    )));
+    lines.push(Line::from(""));
+
+    lines.push(Line::from(Span::styled(
+        "  Use /prp to start a new session.",
+        Style::default().fg(Color::Rgb(100, 149, 237)),
+    )));
+    lines
 }
 
 fn build_help_lines() -> Vec<Line<'static>> {
@@ -1410,8 +1553,29 @@ fn build_help_lines() -> Vec<Line<'static>> {
         ("/scan history",   "Scan git history for exposed secrets"),
         ("/scan ignored",   "Manage permanently ignored findings"),
         ("/plan",           "Open the task planner overlay"),
+        ("/plan import",    "Import issues as tasks: /plan import <owner/repo> [--closed]"),
+        ("/plan issue",     "Open an issue from a task: /plan issue <task-id> <owner/repo>"),
+        ("/plan link",      "Link a task to an issue: /plan link <task-id> <owner/repo#n>"),
+        ("/plan unlink",    "Stop syncing a task with its issue"),
+        ("/plan sync",      "Sync title, body, labels and open/closed both ways; newer edit wins"),
+        ("/plan commits",   "Link commits and branches that mention a task's gl-xxxx ref"),
+        ("/plan autoclose", "on|off — complete tasks named in a 'Closes gl-xxxx' trailer on the default branch"),
+        ("/plan columns",   "Set the board columns: /plan columns Backlog, In progress, Review, Done"),
         ("/prp",            "Start an interactive poly-repo commit session"),
-        ("/prp list",       "View PRP session groups"),
+        ("/prp list",       "View PRP session groups and workspaces"),
+        ("/prp groups",     "Browse groups newest first; d shows the combined cross-repo diff"),
+        ("/prp --set",      "Start a session on a named workspace: /prp --set backend"),
+        ("/prp set",        "Manage workspaces: /prp set add <name> <path|glob>… · /prp set remove <name> [entry]"),
+        ("/prp branch",     "Create a branch in every repo from its default branch"),
+        ("/prp switch",     "Check out the same branch in every repo"),
+        ("/prp pr",         "Open linked pull requests for the latest pushed group"),
+        ("/prp pr status",  "Show combined review and merge status of a group"),
+        ("/prp tag",        "Tag every repo as one release: /prp tag <version> [--sign] [--release]"),
+        ("/prp pull",       "Fetch every repo and update it, stashing local changes: /prp pull [--check] [--rebase]"),
+        ("/prp resume",     "Finish an interrupted push (latest session or by Group-ID)"),
+        ("/prp undo",       "Undo every commit of a session: /prp undo <group-id>"),
+        ("/prp exec",       "Run a command in every repo in parallel: /prp exec [--changed-only] [-j N] <cmd>"),
+        ("/workspace sync", "Clone/fetch every repo in .gitlink/manifest.json and report drift"),
         ("/show-activity",  "Show your GitHub contribution activity"),
         ("/commits",        "Show recent commits"),
         ("/pull-requests",  "Show open pull requests"),
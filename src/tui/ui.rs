@@ -102,6 +102,8 @@ pub fn draw(f: &mut Frame, app: &App, spin_elapsed: f32) {
         Some(Overlay::Auth(ov))    => { info_overlay::draw_auth(f, ov); }
         Some(Overlay::Prp(ov))     => { info_overlay::draw_prp(f, ov); }
         Some(Overlay::MultiSync(ov)) => { info_overlay::draw_multi_sync(f, ov); }
+        Some(Overlay::Exec(ov))    => { info_overlay::draw_exec(f, ov); }
+        Some(Overlay::Groups(ov))  => { info_overlay::draw_groups(f, ov); }
         None => {}
     }
 }
\ No newline at end of file